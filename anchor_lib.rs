@@ -1,3 +1,5 @@
+// LEGACY: early draft of the program, not built or deployed.
+// The live program is programs/agentalpha (program id in programs/agentalpha/src/cluster.rs).
 use anchor_lang::prelude::*;
 
 declare_id!("AgentA1phaSigna1Marketp1ace11111111111111111");
@@ -0,0 +1,33 @@
+/**
+ * Check providerHotAccounts against the lookup table layout the program's
+ * lookup_table tests pin (programs/agentalpha/test-vectors/lookup_table.json)
+ */
+
+import { PublicKey } from '@solana/web3.js';
+import { ALT_FIXED_ENTRIES, providerHotAccounts } from '../src/onchain.js';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+interface LookupTableVector {
+  provider: string;
+  accounts: string[];
+}
+
+const vectorsPath = path.join(
+  path.dirname(fileURLToPath(import.meta.url)),
+  '../programs/agentalpha/test-vectors/lookup_table.json'
+);
+const vectors: LookupTableVector[] = JSON.parse(fs.readFileSync(vectorsPath, 'utf8'));
+
+let failures = 0;
+for (const v of vectors) {
+  const got = providerHotAccounts(new PublicKey(v.provider)).map((k) => k.toBase58());
+  if (got.length !== ALT_FIXED_ENTRIES || got.join() !== v.accounts.join()) {
+    console.error(`❌ ${v.provider}: got [${got.join(', ')}], want [${v.accounts.join(', ')}]`);
+    failures++;
+  }
+}
+
+console.log(`${vectors.length - failures}/${vectors.length} lookup table vectors match`);
+process.exit(failures === 0 ? 0 : 1);
@@ -0,0 +1,34 @@
+/**
+ * Check AgentAlphaClient.formatPriceCents against the vectors the program's
+ * price_display tests use (programs/agentalpha/test-vectors/price_display.json)
+ */
+
+import { AgentAlphaClient } from '../src/onchain.js';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+interface PriceDisplayVector {
+  price_cents: string;
+  decimals: number;
+  exponent: number;
+  display: string;
+}
+
+const vectorsPath = path.join(
+  path.dirname(fileURLToPath(import.meta.url)),
+  '../programs/agentalpha/test-vectors/price_display.json'
+);
+const vectors: PriceDisplayVector[] = JSON.parse(fs.readFileSync(vectorsPath, 'utf8'));
+
+let failures = 0;
+for (const v of vectors) {
+  const got = AgentAlphaClient.formatPriceCents(BigInt(v.price_cents), v.decimals, v.exponent);
+  if (got !== v.display) {
+    console.error(`❌ ${v.price_cents} cents (${v.decimals}, ${v.exponent}): got ${got}, want ${v.display}`);
+    failures++;
+  }
+}
+
+console.log(`${vectors.length - failures}/${vectors.length} price display vectors match`);
+process.exit(failures === 0 ? 0 : 1);
@@ -0,0 +1,490 @@
+//! Canonical JSON for off-chain signal payloads
+//!
+//! Providers hash the JSON rationale attached to a signal and put the hash on-chain. Two
+//! serializers rarely agree byte for byte, so the hash is taken over the RFC 8785 (JCS)
+//! form instead: no insignificant whitespace, object members sorted by the UTF-16 code
+//! units of their names, strings with only the mandatory escapes and numbers written the
+//! way ECMAScript's Number.prototype.toString writes them. Keepers and clients link this
+//! module so the same document hashes the same everywhere.
+
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Nesting depth accepted by `canonicalize`
+pub const MAX_JSON_DEPTH: usize = 128;
+
+/// A parsed JSON document (object members keep their input order until encoded)
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CanonicalJsonError {
+    /// Malformed JSON at this byte offset
+    Syntax(usize),
+    /// An object repeats a member name
+    DuplicateKey(String),
+    /// An escape encodes an unpaired UTF-16 surrogate
+    LoneSurrogate(usize),
+    /// A number is outside the IEEE 754 double range, or NaN/infinite
+    InvalidNumber(usize),
+    /// Nesting deeper than MAX_JSON_DEPTH
+    TooDeep,
+}
+
+impl fmt::Display for CanonicalJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Syntax(at) => write!(f, "invalid JSON at byte {}", at),
+            Self::DuplicateKey(key) => write!(f, "duplicate object member {:?}", key),
+            Self::LoneSurrogate(at) => write!(f, "unpaired surrogate escape at byte {}", at),
+            Self::InvalidNumber(at) => write!(f, "number out of range at byte {}", at),
+            Self::TooDeep => write!(f, "nesting deeper than {}", MAX_JSON_DEPTH),
+        }
+    }
+}
+
+/// Parse JSON text and return its canonical form
+pub fn canonicalize(json: &str) -> Result<String, CanonicalJsonError> {
+    Ok(encode(&parse(json)?))
+}
+
+/// SHA256 of the canonical form, the hash committed for an off-chain payload
+pub fn content_hash(json: &str) -> Result<[u8; 32], CanonicalJsonError> {
+    let mut hasher = Sha256::new();
+    hasher.update(canonicalize(json)?.as_bytes());
+    Ok(hasher.finalize().into())
+}
+
+/// Parse JSON text (RFC 8259), rejecting duplicate member names and unpaired surrogates
+pub fn parse(json: &str) -> Result<JsonValue, CanonicalJsonError> {
+    let mut parser = Parser { bytes: json.as_bytes(), pos: 0 };
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    if parser.pos != parser.bytes.len() {
+        return Err(CanonicalJsonError::Syntax(parser.pos));
+    }
+    Ok(value)
+}
+
+/// Canonical encoding of a value
+pub fn encode(value: &JsonValue) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &JsonValue) {
+    match value {
+        JsonValue::Null => out.push_str("null"),
+        JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        JsonValue::Number(n) => out.push_str(&format_number(*n)),
+        JsonValue::String(s) => write_string(out, s),
+        JsonValue::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        JsonValue::Object(members) => {
+            let mut sorted: Vec<&(String, JsonValue)> = members.iter().collect();
+            sorted.sort_by(|a, b| a.0.encode_utf16().cmp(b.0.encode_utf16()));
+            out.push('{');
+            for (i, (key, item)) in sorted.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// ECMAScript Number::toString for a finite double (the shortest digits that round-trip)
+pub fn format_number(n: f64) -> String {
+    if n == 0.0 {
+        return "0".to_string();
+    }
+    let (digits, point) = shortest_digits(n.abs());
+    let k = digits.len() as i32;
+
+    let body = if k <= point && point <= 21 {
+        format!("{}{}", digits, "0".repeat((point - k) as usize))
+    } else if 0 < point && point <= 21 {
+        format!("{}.{}", &digits[..point as usize], &digits[point as usize..])
+    } else if -6 < point && point <= 0 {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+    } else {
+        let sign = if point - 1 < 0 { '-' } else { '+' };
+        let fraction = if k > 1 { format!(".{}", &digits[1..]) } else { String::new() };
+        format!("{}{}e{}{}", &digits[..1], fraction, sign, (point - 1).abs())
+    };
+    if n < 0.0 { format!("-{}", body) } else { body }
+}
+
+/// Shortest digits that round-trip and the position of the decimal point after the first `point`
+/// Rust resolves a value exactly halfway between two candidates upward; ECMAScript takes the
+/// even one
+fn shortest_digits(n: f64) -> (String, i32) {
+    let (digits, point) = split_scientific(&format!("{:e}", n));
+    // An f64 has at most 767 significant digits, so this expansion is exact
+    let (exact, exact_point) = split_scientific(&format!("{:.767e}", n));
+    let exact = exact.trim_end_matches('0');
+    let k = digits.len();
+    if exact.len() != k + 1 || !exact.ends_with('5') {
+        return (digits, point);
+    }
+    // Halfway: the even neighbour of the truncation (never a 9, or fewer digits would round-trip)
+    let mut even = exact.as_bytes()[..k].to_vec();
+    if even[k - 1] % 2 == 1 {
+        even[k - 1] += 1;
+    }
+    (String::from_utf8(even).unwrap(), exact_point)
+}
+
+/// Digits and decimal point position of "{:e}" output, d[.ddd]e<exp>
+fn split_scientific(sci: &str) -> (String, i32) {
+    let (mantissa, exponent) = sci.split_once('e').unwrap();
+    let digits = mantissa.chars().filter(|c| *c != '.').collect();
+    (digits, exponent.parse::<i32>().unwrap() + 1)
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), CanonicalJsonError> {
+        if self.peek() != Some(byte) {
+            return Err(CanonicalJsonError::Syntax(self.pos));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn literal(&mut self, word: &str, value: JsonValue) -> Result<JsonValue, CanonicalJsonError> {
+        if !self.bytes[self.pos..].starts_with(word.as_bytes()) {
+            return Err(CanonicalJsonError::Syntax(self.pos));
+        }
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    fn value(&mut self, depth: usize) -> Result<JsonValue, CanonicalJsonError> {
+        if depth > MAX_JSON_DEPTH {
+            return Err(CanonicalJsonError::TooDeep);
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'n') => self.literal("null", JsonValue::Null),
+            Some(b't') => self.literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.literal("false", JsonValue::Bool(false)),
+            Some(b'"') => Ok(JsonValue::String(self.string()?)),
+            Some(b'[') => self.array(depth),
+            Some(b'{') => self.object(depth),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err(CanonicalJsonError::Syntax(self.pos)),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<JsonValue, CanonicalJsonError> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(CanonicalJsonError::Syntax(self.pos)),
+            }
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<JsonValue, CanonicalJsonError> {
+        self.expect(b'{')?;
+        let mut members: Vec<(String, JsonValue)> = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            if members.iter().any(|(k, _)| *k == key) {
+                return Err(CanonicalJsonError::DuplicateKey(key));
+            }
+            self.skip_whitespace();
+            self.expect(b':')?;
+            members.push((key, self.value(depth + 1)?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(members));
+                }
+                _ => return Err(CanonicalJsonError::Syntax(self.pos)),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, CanonicalJsonError> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            // Copy the run up to the next quote, escape or control character
+            while matches!(self.peek(), Some(b) if b != b'"' && b != b'\\' && b >= 0x20) {
+                self.pos += 1;
+            }
+            // The input is a &str and the run stops on ASCII, so it is valid UTF-8
+            out.push_str(std::str::from_utf8(&self.bytes[start..self.pos]).unwrap());
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    out.push(self.escape()?);
+                }
+                _ => return Err(CanonicalJsonError::Syntax(self.pos)),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, CanonicalJsonError> {
+        let at = self.pos;
+        let c = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                self.pos += 1;
+                let unit = self.hex4()?;
+                return match unit {
+                    0xD800..=0xDBFF => {
+                        if !self.bytes[self.pos..].starts_with(b"\\u") {
+                            return Err(CanonicalJsonError::LoneSurrogate(at));
+                        }
+                        self.pos += 2;
+                        let low = self.hex4()?;
+                        if !(0xDC00..=0xDFFF).contains(&low) {
+                            return Err(CanonicalJsonError::LoneSurrogate(at));
+                        }
+                        let code = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+                        char::from_u32(code).ok_or(CanonicalJsonError::LoneSurrogate(at))
+                    }
+                    0xDC00..=0xDFFF => Err(CanonicalJsonError::LoneSurrogate(at)),
+                    _ => char::from_u32(unit).ok_or(CanonicalJsonError::Syntax(at)),
+                };
+            }
+            _ => return Err(CanonicalJsonError::Syntax(at)),
+        };
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn hex4(&mut self) -> Result<u32, CanonicalJsonError> {
+        let hex = self.bytes.get(self.pos..self.pos + 4).ok_or(CanonicalJsonError::Syntax(self.pos))?;
+        let hex = std::str::from_utf8(hex).map_err(|_| CanonicalJsonError::Syntax(self.pos))?;
+        let unit = u32::from_str_radix(hex, 16).map_err(|_| CanonicalJsonError::Syntax(self.pos))?;
+        self.pos += 4;
+        Ok(unit)
+    }
+
+    fn number(&mut self) -> Result<JsonValue, CanonicalJsonError> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            let from = p.pos;
+            while matches!(p.peek(), Some(b'0'..=b'9')) {
+                p.pos += 1;
+            }
+            p.pos > from
+        };
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        if self.peek() == Some(b'0') {
+            self.pos += 1;
+        } else if !digits(self) {
+            return Err(CanonicalJsonError::Syntax(self.pos));
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !digits(self) {
+                return Err(CanonicalJsonError::Syntax(self.pos));
+            }
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                return Err(CanonicalJsonError::Syntax(self.pos));
+            }
+        }
+        // The grammar above only admits ASCII
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap();
+        let n: f64 = text.parse().map_err(|_| CanonicalJsonError::InvalidNumber(start))?;
+        if !n.is_finite() {
+            return Err(CanonicalJsonError::InvalidNumber(start));
+        }
+        Ok(JsonValue::Number(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 8785 appendix B: IEEE 754 bit patterns and their canonical number form
+    const NUMBER_VECTORS: &[(u64, &str)] = &[
+        (0x0000000000000000, "0"),
+        (0x8000000000000000, "0"),
+        (0x0000000000000001, "5e-324"),
+        (0x8000000000000001, "-5e-324"),
+        (0x7fefffffffffffff, "1.7976931348623157e+308"),
+        (0xffefffffffffffff, "-1.7976931348623157e+308"),
+        (0x4340000000000000, "9007199254740992"),
+        (0xc340000000000000, "-9007199254740992"),
+        (0x4430000000000000, "295147905179352830000"),
+        (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+        (0x44b52d02c7e14af6, "1e+23"),
+        (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+        (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+        (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+        (0x444b1ae4d6e2ef50, "1e+21"),
+        (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+        (0x3eb0c6f7a0b5ed8d, "0.000001"),
+        (0x41b3de4355555553, "333333333.3333332"),
+        (0x41b3de4355555554, "333333333.33333325"),
+        (0x41b3de4355555555, "333333333.3333333"),
+        (0x41b3de4355555556, "333333333.3333334"),
+        (0x41b3de4355555557, "333333333.33333343"),
+        (0xbecbf647612f3696, "-0.0000033333333333333333"),
+        (0x43143ff3c1cb0959, "1424953923781206.2"),
+    ];
+
+    #[test]
+    fn numbers_match_rfc8785_appendix_b() {
+        for &(bits, expected) in NUMBER_VECTORS {
+            assert_eq!(format_number(f64::from_bits(bits)), expected, "bits {:016x}", bits);
+        }
+    }
+
+    #[test]
+    fn numbers_round_trip_through_their_canonical_form() {
+        for &(bits, expected) in NUMBER_VECTORS {
+            assert_eq!(canonicalize(expected).unwrap(), expected);
+            let n: f64 = expected.parse().unwrap();
+            assert_eq!(n, f64::from_bits(bits), "{}", expected);
+        }
+    }
+
+    /// RFC 8785 section 3.2.3: members sorted by the UTF-16 code units of their names
+    #[test]
+    fn members_sort_by_utf16_code_units() {
+        let input = r#"{
+            "\u20ac": "Euro Sign",
+            "\r": "Carriage Return",
+            "\ufb33": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "\ud83d\ude00": "Emoji: Grinning Face",
+            "\u0080": "Control",
+            "\u00f6": "Latin Small Letter O With Diaeresis"
+        }"#;
+        let expected = concat!(
+            "{\"\\r\":\"Carriage Return\",",
+            "\"1\":\"One\",",
+            "\"\u{80}\":\"Control\",",
+            "\"\u{f6}\":\"Latin Small Letter O With Diaeresis\",",
+            "\"\u{20ac}\":\"Euro Sign\",",
+            "\"\u{1f600}\":\"Emoji: Grinning Face\",",
+            "\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}",
+        );
+        assert_eq!(canonicalize(input).unwrap(), expected);
+    }
+
+    /// RFC 8785 section 3.2.2: the worked example
+    #[test]
+    fn canonicalizes_the_rfc8785_example() {
+        let input = r#"{
+            "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+            "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+            "literals": [null, true, false]
+        }"#;
+        let expected = concat!(
+            r#"{"literals":[null,true,false],"#,
+            r#""numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"#,
+            "\"string\":\"\u{20ac}$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"}",
+        );
+        assert_eq!(canonicalize(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn rejects_what_jcs_cannot_represent() {
+        assert_eq!(parse(r#"{"a":1,"a":2}"#), Err(CanonicalJsonError::DuplicateKey("a".to_string())));
+        assert_eq!(parse(r#""\ud83d""#), Err(CanonicalJsonError::LoneSurrogate(2)));
+        assert_eq!(parse("1e400"), Err(CanonicalJsonError::InvalidNumber(0)));
+        assert_eq!(parse(&"[".repeat(MAX_JSON_DEPTH + 2)), Err(CanonicalJsonError::TooDeep));
+    }
+}
@@ -0,0 +1,34 @@
+//! Cluster-specific deployment settings
+//!
+//! Exactly one of the `devnet` or `mainnet` features selects the program id and the
+//! environment-dependent defaults below. `devnet` is the default feature; build for
+//! mainnet with `--no-default-features --features mainnet`.
+
+use anchor_lang::prelude::*;
+
+#[cfg(all(feature = "devnet", feature = "mainnet"))]
+compile_error!("features `devnet` and `mainnet` are mutually exclusive (build mainnet with --no-default-features)");
+
+#[cfg(not(any(feature = "devnet", feature = "mainnet")))]
+compile_error!("select a cluster with the `devnet` or `mainnet` feature");
+
+#[cfg(feature = "devnet")]
+mod settings {
+    use super::*;
+    
+    declare_id!("6sDwzatESkmF5T3K3rfNta4DCRgH8z9ZdYoPXeMtKRmP");
+    
+    /// Price oracle written into a new Config (default = unset, the admin assigns one)
+    pub const DEFAULT_PRICE_ORACLE: Pubkey = Pubkey::new_from_array([0; 32]);
+    
+    /// Keeper rewards written into a new Config
+    pub const DEFAULT_SETTLE_REWARD_LAMPORTS: u64 = crate::SETTLEMENT_DEPOSIT_LAMPORTS;
+    pub const DEFAULT_EXPIRE_REWARD_LAMPORTS: u64 = crate::SETTLEMENT_DEPOSIT_LAMPORTS;
+}
+
+#[cfg(all(feature = "mainnet", not(feature = "devnet")))]
+mod settings {
+    compile_error!("no mainnet program id has been assigned yet; add it to cluster.rs before building for mainnet");
+}
+
+pub use settings::*;
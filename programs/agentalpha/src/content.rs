@@ -0,0 +1,76 @@
+//! Chunked content commitments for large signal attachments
+//!
+//! A provider splits a research artifact into CONTENT_CHUNK_SIZE byte chunks and commits
+//! the Merkle root of their leaf hashes on the SignalCommit (set_content_root). Buyers
+//! downloading from the endpoint verify each chunk against that root with its proof, so a
+//! partial download can be validated without fetching the whole artifact.
+//!
+//! Leaves are SHA256(0x00 || chunk) and inner nodes use crate::merkle_root's pairing, where
+//! an odd node is paired with itself. Its proof then holds the node itself as the sibling.
+
+use sha2::{Digest, Sha256};
+
+/// Chunk size providers split content into (the last chunk may be shorter)
+pub const CONTENT_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Leaf hash of one content chunk
+pub fn chunk_leaf(chunk: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update(chunk);
+    hasher.finalize().into()
+}
+
+/// Merkle root committed for a whole artifact
+pub fn content_root(content: &[u8]) -> [u8; 32] {
+    crate::merkle_root(content.chunks(CONTENT_CHUNK_SIZE).map(chunk_leaf).collect())
+}
+
+/// Number of levels above the leaves in a tree of `chunk_count` leaves (the proof length)
+pub fn proof_len(chunk_count: u32) -> usize {
+    let mut width = chunk_count.max(1) as usize;
+    let mut levels = 0;
+    while width > 1 {
+        width = width.div_ceil(2);
+        levels += 1;
+    }
+    levels
+}
+
+/// Sibling hashes from the leaf at `index` up to the root
+pub fn chunk_proof(leaves: &[[u8; 32]], index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut index = index;
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        proof.push(*level.get(sibling).unwrap_or(&level[index]));
+        level = level
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+        index /= 2;
+    }
+    proof
+}
+
+/// Whether `chunk` is chunk `index` of the `chunk_count`-chunk content committed to by `root`
+pub fn verify_chunk(root: &[u8; 32], chunk_count: u32, index: u32, chunk: &[u8], proof: &[[u8; 32]]) -> bool {
+    if index >= chunk_count || chunk.len() > CONTENT_CHUNK_SIZE || proof.len() != proof_len(chunk_count) {
+        return false;
+    }
+    let mut node = chunk_leaf(chunk);
+    let mut index = index;
+    for sibling in proof {
+        node = if index & 1 == 0 { hash_pair(&node, sibling) } else { hash_pair(sibling, &node) };
+        index /= 2;
+    }
+    node == *root
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
@@ -0,0 +1,49 @@
+//! Domain-separation prefixes for hashed and signed messages
+//!
+//! Each message a user commits to or signs starts with a tag naming the protocol, the message
+//! type and its version. A hash or signature made for one message then can't be passed off as
+//! another message of this program, or as a message of another protocol that happens to hash
+//! the same bytes. The program rebuilds tagged preimages on-chain wherever it checks a hash or
+//! signature; keepers and clients link this module to build the same bytes.
+//!
+//! A format change ships under a new tag rather than reusing an old one. Hashes of
+//! program-derived data (Merkle nodes, audit draws) are never signed and carry no tag, and
+//! neither do the untagged HASH_SCHEMA_V1 and HASH_SCHEMA_LEGACY preimages kept for older clients.
+//! Untagged relay authorizations and bid hashes are accepted until UNTAGGED_MESSAGES_CUTOFF.
+
+use sha2::{Digest, Sha256};
+
+/// Signal commitments under HASH_SCHEMA_V2: SHA256(COMMIT_V2 || V1 preimage)
+pub const COMMIT_V2: &[u8] = b"AGENTALPHA:COMMIT:V2";
+
+/// Commit authorization a provider signs for a relayer (see relayed_commit_message)
+pub const RELAY_COMMIT_V2: &[u8] = b"AGENTALPHA:RELAY_COMMIT:V2";
+
+/// Prefix relay authorizations were signed under before RELAY_COMMIT_V2
+pub const RELAY_COMMIT_V1: &[u8] = b"agentalpha:commit:";
+
+/// Sealed auction bids: SHA256(AUCTION_BID_V1 || bidder || amount LE || salt)
+pub const AUCTION_BID_V1: &[u8] = b"AGENTALPHA:AUCTION_BID:V1";
+
+/// TEE attestation hash a provider submits: SHA256(ATTESTATION_V1 || attestation report)
+/// The program never sees the report: it stores the submitted hash and checks that the
+/// attestation authority verified the same one, so this tag is only enforced by the
+/// authority and clients recomputing it
+pub const ATTESTATION_V1: &[u8] = b"AGENTALPHA:ATTESTATION:V1";
+
+/// Social-settlement observation commitments (see SocialSettlement::observation_hash)
+/// V1 committed to the closing price alone; V2 adds the window's high and low
+pub const OBSERVATION_V2: &[u8] = b"AGENTALPHA:OBSERVATION:V2";
+
+/// SHA256 of `message` under a domain tag
+pub fn tagged_hash(domain: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(domain);
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+/// Attestation hash to submit for a TEE attestation report
+pub fn attestation_hash(report: &[u8]) -> [u8; 32] {
+    tagged_hash(ATTESTATION_V1, report)
+}
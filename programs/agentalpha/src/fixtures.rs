@@ -0,0 +1,285 @@
+//! Simulated price paths for exercising settlement (`fixtures` feature)
+//!
+//! Generates deterministic OHLC paths in cents for trending, choppy and gapping markets and
+//! replays them against a revealed SignalCommit the way the oracle does, producing the
+//! arguments record_outcome expects. Keepers and client test suites use it to check TP/SL
+//! precedence, BOTH_TOUCHED resolution and ladder fills against realistic paths.
+//!
+//! The program takes oracle prices as instruction arguments rather than reading a price
+//! account, so there is no account writer here: feed the report to record_outcome or
+//! SignalCommit::resolve_settlement directly.
+
+use crate::{SignalCommit, OUTCOME_BOTH_TOUCHED};
+
+/// One candle of a simulated path, in cents of the quote currency
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Candle {
+    pub open: u64,
+    pub high: u64,
+    pub low: u64,
+    pub close: u64,
+}
+
+/// Market regime a path is drawn from
+#[derive(Clone, Copy, Debug)]
+pub enum PathShape {
+    /// Steady drift per candle (negative = downtrend)
+    Trend { drift_bps: i32 },
+    /// Mean-reverting around the start price
+    Chop,
+    /// Flat until candle `at`, which opens `gap_bps` away from the previous close
+    Gap { at: usize, gap_bps: i32 },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PathSpec {
+    pub start_cents: u64,
+    pub candles: usize,
+    pub shape: PathShape,
+    /// Largest random move per candle, also bounding the wicks
+    pub volatility_bps: u32,
+    /// Same seed, same path
+    pub seed: u64,
+}
+
+/// What the oracle would report for a signal over a path (record_outcome's arguments)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OracleReport {
+    pub outcome: u8,
+    pub final_price_cents: u64,
+    pub return_bps: i32,
+    pub adverse_extreme_cents: u64,
+    pub window_high_cents: u64,
+    pub window_low_cents: u64,
+    /// Index of the candle the signal settled in (the last candle if it expired)
+    pub settled_at: usize,
+}
+
+/// Generate the candles described by `spec`
+pub fn price_path(spec: &PathSpec) -> Vec<Candle> {
+    let mut rng = SplitMix64(spec.seed);
+    let volatility = spec.volatility_bps as i64;
+    let mut close = spec.start_cents.max(1);
+    let mut candles = Vec::with_capacity(spec.candles);
+    for i in 0..spec.candles {
+        let mut open = close;
+        let drift_bps = match spec.shape {
+            PathShape::Trend { drift_bps } => drift_bps as i64,
+            PathShape::Chop => (spec.start_cents as i64 - open as i64) * 10000 / open as i64 / 2,
+            PathShape::Gap { at, gap_bps } => {
+                if i == at {
+                    open = scale(open, gap_bps as i64);
+                }
+                0
+            }
+        };
+        close = scale(open, drift_bps + rng.within(volatility));
+        let high = scale(open.max(close), rng.within(volatility / 2).abs());
+        let low = scale(open.min(close), -rng.within(volatility / 2).abs());
+        candles.push(Candle { open, high, low, close });
+    }
+    candles
+}
+
+/// Replay a path against a revealed signal, settling in the first candle that reaches TP or SL
+/// A candle opening beyond a level settles at its open; one whose range spans both levels is
+/// reported as BOTH_TOUCHED at its close, left to the configured precedence
+pub fn oracle_report(commit: &SignalCommit, path: &[Candle]) -> OracleReport {
+    let buy = commit.direction == 0;
+    let mut report = OracleReport {
+        outcome: 3,
+        final_price_cents: commit.entry_cents,
+        return_bps: 0,
+        adverse_extreme_cents: commit.entry_cents,
+        window_high_cents: 0,
+        window_low_cents: u64::MAX,
+        settled_at: path.len().saturating_sub(1),
+    };
+    for (i, candle) in path.iter().enumerate() {
+        report.window_high_cents = report.window_high_cents.max(candle.high);
+        report.window_low_cents = report.window_low_cents.min(candle.low);
+        report.adverse_extreme_cents = if buy {
+            report.adverse_extreme_cents.min(candle.low)
+        } else {
+            report.adverse_extreme_cents.max(candle.high)
+        };
+        report.final_price_cents = candle.close;
+
+        let (favorable, adverse) = if buy { (candle.high, candle.low) } else { (candle.low, candle.high) };
+        let tp_touched = commit.outcome_at_price(favorable) == 1;
+        let sl_touched = commit.outcome_at_price(adverse) == 2;
+        let (outcome, price) = match commit.outcome_at_price(candle.open) {
+            at_open @ (1 | 2) => (at_open, candle.open),
+            _ if tp_touched && sl_touched => (OUTCOME_BOTH_TOUCHED, candle.close),
+            _ if tp_touched => (1, commit.tp_cents),
+            _ if sl_touched => (2, commit.sl_cents),
+            _ => continue,
+        };
+        report.outcome = outcome;
+        report.final_price_cents = price;
+        report.settled_at = i;
+        break;
+    }
+    if path.is_empty() {
+        report.window_low_cents = 0;
+    }
+    report.return_bps = commit.return_from_entry_bps(commit.entry_cents, report.final_price_cents);
+    report
+}
+
+fn scale(price: u64, bps: i64) -> u64 {
+    (price as i128 * (10000 + bps as i128) / 10000).max(1) as u64
+}
+
+/// SplitMix64, so paths are reproducible without a rand dependency
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [-bound, bound]
+    fn within(&mut self, bound: i64) -> i64 {
+        if bound <= 0 {
+            return 0;
+        }
+        (self.next() % (2 * bound as u64 + 1)) as i64 - bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migration::zeroed;
+    use crate::{Config, OUTCOME_NOT_FILLED, PRECEDENCE_BEST_CASE, PRECEDENCE_EXPIRE, PRECEDENCE_WORST_CASE};
+
+    fn revealed(direction: u8, entry_cents: u64, tp_cents: u64, sl_cents: u64) -> SignalCommit {
+        let mut commit = *zeroed::<SignalCommit>(SignalCommit::SIZE).unwrap();
+        commit.revealed = true;
+        commit.token = "SOL".to_string();
+        commit.direction = direction;
+        commit.entry_cents = entry_cents;
+        commit.tp_cents = tp_cents;
+        commit.sl_cents = sl_cents;
+        commit.timeframe_hours = 24;
+        commit.confidence = 70;
+        commit
+    }
+
+    fn config(precedence: u8) -> Config {
+        let mut config = *zeroed::<Config>(Config::SIZE).unwrap();
+        config.both_touched_precedence = precedence;
+        config
+    }
+
+    fn settle(commit: &SignalCommit, report: &OracleReport, precedence: u8) -> (u8, bool, i32) {
+        let preview = commit
+            .resolve_settlement(
+                report.outcome,
+                report.final_price_cents,
+                report.return_bps,
+                report.adverse_extreme_cents,
+                report.window_high_cents,
+                report.window_low_cents,
+                &config(precedence),
+            )
+            .unwrap();
+        (preview.outcome, preview.was_correct, preview.return_bps)
+    }
+
+    fn spec(shape: PathShape, volatility_bps: u32) -> PathSpec {
+        PathSpec { start_cents: 10_000, candles: 48, shape, volatility_bps, seed: 42 }
+    }
+
+    #[test]
+    fn paths_are_reproducible_from_their_seed() {
+        let trend = spec(PathShape::Trend { drift_bps: 20 }, 80);
+        assert_eq!(price_path(&trend), price_path(&trend));
+        assert_ne!(price_path(&trend), price_path(&PathSpec { seed: 43, ..trend }));
+        assert!(price_path(&trend).iter().all(|c| c.low <= c.open.min(c.close) && c.high >= c.open.max(c.close)));
+    }
+
+    #[test]
+    fn uptrend_settles_a_buy_at_take_profit() {
+        let commit = revealed(0, 10_000, 11_000, 9_500);
+        let report = oracle_report(&commit, &price_path(&spec(PathShape::Trend { drift_bps: 100 }, 50)));
+
+        assert_eq!((report.outcome, report.final_price_cents), (1, 11_000));
+        assert!(report.settled_at < 47);
+        assert_eq!(settle(&commit, &report, PRECEDENCE_WORST_CASE), (1, true, 1000));
+    }
+
+    #[test]
+    fn downtrend_settles_a_sell_at_take_profit() {
+        let commit = revealed(1, 10_000, 9_000, 10_500);
+        let report = oracle_report(&commit, &price_path(&spec(PathShape::Trend { drift_bps: -100 }, 50)));
+
+        assert_eq!((report.outcome, report.final_price_cents), (1, 9_000));
+        assert_eq!(settle(&commit, &report, PRECEDENCE_WORST_CASE), (1, true, 1000));
+    }
+
+    #[test]
+    fn gap_through_stop_loss_settles_at_the_open() {
+        let commit = revealed(0, 10_000, 11_000, 9_500);
+        let path = price_path(&spec(PathShape::Gap { at: 5, gap_bps: -1000 }, 10));
+        let report = oracle_report(&commit, &path);
+
+        assert_eq!(report.outcome, 2);
+        assert_eq!(report.settled_at, 5);
+        assert_eq!(report.final_price_cents, path[5].open);
+        assert!(report.final_price_cents < commit.sl_cents);
+        let (outcome, was_correct, return_bps) = settle(&commit, &report, PRECEDENCE_WORST_CASE);
+        assert_eq!((outcome, was_correct), (2, false));
+        assert!(return_bps < -500, "a gap fills worse than the stop");
+    }
+
+    #[test]
+    fn chop_inside_the_levels_expires_at_the_last_close() {
+        let commit = revealed(0, 10_000, 20_000, 5_000);
+        let path = price_path(&spec(PathShape::Chop, 30));
+        let report = oracle_report(&commit, &path);
+
+        assert_eq!(report.outcome, 3);
+        assert_eq!(report.settled_at, path.len() - 1);
+        assert_eq!(report.final_price_cents, path[path.len() - 1].close);
+        assert!(report.window_low_cents <= report.adverse_extreme_cents);
+        assert!(report.window_low_cents <= report.window_high_cents);
+        let (outcome, was_correct, return_bps) = settle(&commit, &report, PRECEDENCE_WORST_CASE);
+        assert_eq!(outcome, 3);
+        assert_eq!(was_correct, return_bps > 0);
+    }
+
+    #[test]
+    fn candle_spanning_both_levels_follows_the_precedence() {
+        let commit = revealed(0, 10_000, 11_000, 9_500);
+        let path = [Candle { open: 10_000, high: 11_500, low: 9_000, close: 10_100 }];
+        let report = oracle_report(&commit, &path);
+
+        assert_eq!((report.outcome, report.final_price_cents), (OUTCOME_BOTH_TOUCHED, 10_100));
+        assert_eq!(settle(&commit, &report, PRECEDENCE_WORST_CASE), (2, false, -500));
+        assert_eq!(settle(&commit, &report, PRECEDENCE_BEST_CASE), (1, true, 1000));
+        assert_eq!(settle(&commit, &report, PRECEDENCE_EXPIRE), (3, true, 100));
+    }
+
+    #[test]
+    fn window_range_agrees_with_the_replay() {
+        let commit = revealed(0, 10_000, 11_000, 9_500);
+        for shape in [PathShape::Trend { drift_bps: 100 }, PathShape::Trend { drift_bps: -100 }, PathShape::Chop] {
+            let path = price_path(&spec(shape, 30));
+            let report = oracle_report(&commit, &path);
+            let window = &path[..=report.settled_at];
+            let high = window.iter().map(|c| c.high).max().unwrap();
+            let low = window.iter().map(|c| c.low).min().unwrap();
+            assert_eq!(commit.window_outcome(high, low), report.outcome);
+        }
+
+        // A window that never trades at the entry leaves the signal unfilled
+        assert_eq!(commit.window_outcome(10_600, 10_200), OUTCOME_NOT_FILLED);
+        assert_eq!(commit.window_outcome(11_500, 9_000), OUTCOME_BOTH_TOUCHED);
+    }
+}
@@ -0,0 +1,339 @@
+//! State invariants checked at the end of instructions (`debug-invariants` feature)
+//!
+//! Test builds enable the feature so every instruction that moves escrowed lamports or
+//! settles a signal re-checks the accounts it touched through `check_invariants!`: program
+//! accounts hold at least the lamports their ledgers say they hold, ledger totals never fall
+//! below what is still unclaimed, and no signal is settled without having been revealed.
+//! Counters that only ever grow are compared against a ProviderCounters snapshot taken at
+//! the start of the instruction.
+//!
+//! A violation logs the invariant that failed and aborts the instruction with
+//! InvariantViolated. Release builds compile none of this.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    AgentAlphaError, Credits, Integrator, Provider, SignalAccess, SignalCommit, Subscription,
+    MAX_SUBSCRIPTION_SEATS, OUTCOME_NOT_FILLED, OUTCOME_VOIDED,
+};
+
+/// An account whose state must stay internally consistent
+pub trait Invariant {
+    fn check_invariants(&self) -> Result<()>;
+}
+
+fn ensure(holds: bool, invariant: &str) -> Result<()> {
+    if !holds {
+        msg!("Invariant violated: {}", invariant);
+        return err!(AgentAlphaError::InvariantViolated);
+    }
+    Ok(())
+}
+
+/// Lamports above the account's rent-exempt minimum
+fn spendable(info: &AccountInfo) -> Result<u64> {
+    Ok(info.lamports().saturating_sub(Rent::get()?.minimum_balance(info.data_len())))
+}
+
+impl Invariant for Account<'_, SignalCommit> {
+    fn check_invariants(&self) -> Result<()> {
+        ensure(
+            !self.outcome_recorded || self.revealed || self.outcome == OUTCOME_VOIDED,
+            "settled signal was revealed",
+        )?;
+        ensure(!self.outcome_recorded || (1..=OUTCOME_NOT_FILLED).contains(&self.outcome), "outcome is known")?;
+        ensure(!(self.expired && self.revealed), "expired signal is unrevealed")?;
+        ensure(self.max_buyers == 0 || self.buyer_count <= self.max_buyers, "buyers within capacity")?;
+        ensure(
+            spendable(&self.to_account_info())? >= self.settlement_deposit + self.stake_lamports,
+            "signal holds its settlement deposit and stake",
+        )
+    }
+}
+
+impl Invariant for Account<'_, SignalAccess> {
+    fn check_invariants(&self) -> Result<()> {
+        ensure(!self.refunded || self.escrow_lamports == 0, "refunded purchase holds no escrow")?;
+        ensure(self.refund_bps <= 10000, "refund share within 10000 bps")?;
+        ensure(
+            self.escrow_lamports + self.refund_lamports + self.escrow_reclaimed <= self.price_paid
+                || self.payment_mint != Pubkey::default(),
+            "escrow within the price paid",
+        )?;
+        ensure(
+            spendable(&self.to_account_info())? >= self.escrow_lamports + self.refund_lamports,
+            "access record holds its escrow",
+        )
+    }
+}
+
+impl Invariant for Account<'_, Provider> {
+    fn check_invariants(&self) -> Result<()> {
+        ensure(self.vesting_released_lamports <= self.vesting_held_lamports, "vesting released within held")?;
+        ensure(
+            self.citation_royalties_unclaimed + self.pending_royalties() <= self.citation_royalties_earned,
+            "royalties unclaimed within earned",
+        )?;
+        ensure(self.correct_signals <= self.total_signals, "correct signals within total")?;
+        ensure(
+            spendable(&self.to_account_info())?
+                >= self.vesting_held_lamports - self.vesting_released_lamports
+                    + self.citation_royalties_unclaimed
+                    + self.pending_royalties(),
+            "provider holds its vesting and royalties",
+        )
+    }
+}
+
+impl Invariant for Account<'_, Integrator> {
+    fn check_invariants(&self) -> Result<()> {
+        ensure(self.fees_unclaimed <= self.fees_earned, "integrator fees unclaimed within earned")?;
+        ensure(spendable(&self.to_account_info())? >= self.fees_unclaimed, "integrator holds its fees")
+    }
+}
+
+impl Invariant for Account<'_, Subscription> {
+    fn check_invariants(&self) -> Result<()> {
+        ensure(self.expires_at >= self.started_at, "subscription expires after it starts")?;
+        ensure(self.seats.len() <= MAX_SUBSCRIPTION_SEATS, "seats within MAX_SUBSCRIPTION_SEATS")?;
+        ensure(
+            spendable(&self.to_account_info())? >= self.escrow_lamports,
+            "subscription holds its escrow",
+        )
+    }
+}
+
+impl Invariant for Account<'_, Credits> {
+    fn check_invariants(&self) -> Result<()> {
+        ensure(self.remaining <= self.total_purchased, "credits remaining within purchased")
+    }
+}
+
+/// Provider counters that only ever grow, captured at the start of an instruction
+pub struct ProviderCounters {
+    total_signals: u64,
+    correct_signals: u64,
+    expired_signals: u64,
+    executions: u64,
+    sla_epochs: u32,
+    self_voided_signals: u32,
+    neutral_signals: u32,
+}
+
+impl ProviderCounters {
+    pub fn of(provider: &Provider) -> Self {
+        Self {
+            total_signals: provider.total_signals,
+            correct_signals: provider.correct_signals,
+            expired_signals: provider.expired_signals,
+            executions: provider.executions,
+            sla_epochs: provider.sla_met_epochs + provider.sla_missed_epochs,
+            self_voided_signals: provider.self_voided_signals,
+            neutral_signals: provider.break_even_signals + provider.not_filled_signals,
+        }
+    }
+
+    /// Check that none of the counters went backwards
+    pub fn check_monotonic(&self, provider: &Provider) -> Result<()> {
+        let now = Self::of(provider);
+        ensure(now.total_signals >= self.total_signals, "total_signals is monotonic")?;
+        ensure(now.correct_signals >= self.correct_signals, "correct_signals is monotonic")?;
+        ensure(now.expired_signals >= self.expired_signals, "expired_signals is monotonic")?;
+        ensure(now.executions >= self.executions, "executions is monotonic")?;
+        ensure(now.sla_epochs >= self.sla_epochs, "SLA epoch counts are monotonic")?;
+        ensure(now.self_voided_signals >= self.self_voided_signals, "self_voided_signals is monotonic")?;
+        ensure(now.neutral_signals >= self.neutral_signals, "neutral outcome counts are monotonic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::entrypoint::SUCCESS;
+    use anchor_lang::{AccountDeserialize, AccountSerialize, Owner};
+    use solana_sysvar::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use std::sync::Once;
+
+    use crate::fixtures::{oracle_report, price_path, PathShape, PathSpec};
+    use crate::migration::zeroed;
+    use crate::{Config, PRECEDENCE_WORST_CASE, SLA_EPOCH_SECS};
+
+    /// Serves Rent::get off-chain, which spendable needs
+    struct RentStubs;
+
+    impl SyscallStubs for RentStubs {
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            SUCCESS
+        }
+    }
+
+    fn with_account<T, R>(state: &T, size: usize, spendable: u64, check: impl FnOnce(&Account<T>) -> R) -> R
+    where
+        T: AccountSerialize + AccountDeserialize + Owner + Clone,
+    {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(RentStubs));
+        });
+        let key = Pubkey::new_unique();
+        let mut data = vec![0u8; size];
+        state.try_serialize(&mut &mut data[..]).unwrap();
+        let mut lamports = Rent::default().minimum_balance(size) + spendable;
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        let account = Account::try_from(&info).unwrap();
+        check(&account)
+    }
+
+    /// A BUY settled by the oracle over a simulated uptrend
+    fn settled_signal() -> SignalCommit {
+        let mut commit = *zeroed::<SignalCommit>(SignalCommit::SIZE).unwrap();
+        commit.revealed = true;
+        commit.direction = 0;
+        commit.entry_cents = 10_000;
+        commit.tp_cents = 11_000;
+        commit.sl_cents = 9_500;
+        commit.confidence = 70;
+        commit.settlement_deposit = 5_000;
+        let path = price_path(&PathSpec {
+            start_cents: 10_000,
+            candles: 48,
+            shape: PathShape::Trend { drift_bps: 100 },
+            volatility_bps: 50,
+            seed: 7,
+        });
+        let report = oracle_report(&commit, &path);
+        let mut config = *zeroed::<Config>(Config::SIZE).unwrap();
+        config.both_touched_precedence = PRECEDENCE_WORST_CASE;
+        let preview = commit
+            .resolve_settlement(
+                report.outcome,
+                report.final_price_cents,
+                report.return_bps,
+                report.adverse_extreme_cents,
+                report.window_high_cents,
+                report.window_low_cents,
+                &config,
+            )
+            .unwrap();
+        commit.outcome_recorded = true;
+        commit.outcome = preview.outcome;
+        commit.was_correct = preview.was_correct;
+        commit.return_bps = preview.return_bps;
+        commit.final_price_cents = report.final_price_cents;
+        commit
+    }
+
+    #[test]
+    fn settled_signal_holding_its_deposit_holds() {
+        let commit = settled_signal();
+        assert!(with_account(&commit, SignalCommit::SIZE, 5_000, |a| a.check_invariants()).is_ok());
+    }
+
+    #[test]
+    fn signal_settled_without_reveal_is_caught() {
+        let mut commit = settled_signal();
+        commit.revealed = false;
+        assert!(with_account(&commit, SignalCommit::SIZE, 5_000, |a| a.check_invariants()).is_err());
+    }
+
+    #[test]
+    fn signal_short_of_its_deposit_is_caught() {
+        let commit = settled_signal();
+        assert!(with_account(&commit, SignalCommit::SIZE, 4_999, |a| a.check_invariants()).is_err());
+    }
+
+    #[test]
+    fn provider_must_hold_unreleased_vesting() {
+        let mut provider = *zeroed::<Provider>(Provider::SIZE).unwrap();
+        provider.vesting_held_lamports = 3_000;
+        provider.vesting_released_lamports = 1_000;
+        assert!(with_account(&provider, Provider::SIZE, 2_000, |a| a.check_invariants()).is_ok());
+        assert!(with_account(&provider, Provider::SIZE, 1_999, |a| a.check_invariants()).is_err());
+    }
+
+    #[test]
+    fn subscription_escrow_is_earned_pro_rata_and_held() {
+        let mut subscription = *zeroed::<Subscription>(Subscription::SIZE).unwrap();
+        subscription.started_at = 1_000;
+        subscription.escrow_from = 1_000;
+        subscription.expires_at = 5_000;
+        subscription.escrow_lamports = 4_000;
+        assert_eq!(subscription.earned_escrow(1_000), 0);
+        assert_eq!(subscription.take_earned(2_000), 1_000);
+        // The rest is earned from where the last release stopped
+        assert_eq!(subscription.earned_escrow(3_500), 1_500);
+        assert_eq!(subscription.earned_escrow(9_000), 3_000);
+
+        assert!(with_account(&subscription, Subscription::SIZE, 3_000, |a| a.check_invariants()).is_ok());
+        assert!(with_account(&subscription, Subscription::SIZE, 2_999, |a| a.check_invariants()).is_err());
+    }
+
+    #[test]
+    fn missed_sla_epoch_extends_subscriptions_that_ran_through_it() {
+        let mut provider = *zeroed::<Provider>(Provider::SIZE).unwrap();
+        provider.sla_min_signals = 4;
+        provider.sla_epoch_signals = 1;
+        provider.sla_epoch = 10;
+        let epoch_end = 11 * SLA_EPOCH_SECS;
+        let mut active = *zeroed::<Subscription>(Subscription::SIZE).unwrap();
+        active.expires_at = epoch_end + 86400;
+        let mut lapsed = active.clone();
+        lapsed.expires_at = epoch_end - 86400;
+
+        provider.roll_sla_epoch(Pubkey::default(), epoch_end + 60);
+        assert_eq!(provider.subscriber_credit(), (SLA_EPOCH_SECS as u64 * 3 / 4, epoch_end));
+
+        active.apply_credit(&provider, epoch_end + 60);
+        assert_eq!(active.expires_at, epoch_end + 86400 + SLA_EPOCH_SECS * 3 / 4);
+        lapsed.apply_credit(&provider, epoch_end + 60);
+        assert_eq!(lapsed.expires_at, epoch_end - 86400);
+        // Credit is applied once
+        active.apply_credit(&provider, epoch_end + 60);
+        assert_eq!(active.expires_at, epoch_end + 86400 + SLA_EPOCH_SECS * 3 / 4);
+    }
+
+    #[test]
+    fn paused_sales_stop_the_subscription_clock() {
+        let day = |n: i64| 1_700_000_000 + n * 86400;
+        let mut provider = *zeroed::<Provider>(Provider::SIZE).unwrap();
+        provider.accepting_purchases = true;
+        provider.close_pause(day(0));
+        let mut running = *zeroed::<Subscription>(Subscription::SIZE).unwrap();
+        running.apply_credit(&provider, day(0));
+        running.expires_at = day(30);
+        let mut lapsed = running.clone();
+        lapsed.expires_at = day(5);
+
+        // A 10-day window from day 10, then an indefinite pause from day 25 to day 27
+        provider.pause_start = day(10);
+        provider.pause_end = day(20);
+        assert_eq!(running.credited_expires_at(&provider, day(15)), day(35));
+        provider.close_pause(day(25));
+        provider.accepting_purchases = false;
+        provider.close_pause(day(27));
+        provider.accepting_purchases = true;
+
+        assert_eq!(running.credited_expires_at(&provider, day(28)), day(42));
+        // Lapsed before the pauses, it stays lapsed
+        assert!(lapsed.credited_expires_at(&provider, day(28)) < day(25));
+        running.apply_credit(&provider, day(28));
+        assert_eq!(running.credited_expires_at(&provider, day(28)), day(42));
+    }
+
+    #[test]
+    fn settlement_keeps_provider_counters_monotonic() {
+        let commit = settled_signal();
+        let mut provider = *zeroed::<Provider>(Provider::SIZE).unwrap();
+        let before = ProviderCounters::of(&provider);
+
+        assert!(provider.record_settlement(&commit, 0, 1_700_000_000));
+        assert_eq!((provider.total_signals, provider.correct_signals), (1, 1));
+        assert!(before.check_monotonic(&provider).is_ok());
+        assert!(with_account(&provider, Provider::SIZE, 0, |a| a.check_invariants()).is_ok());
+
+        let settled = ProviderCounters::of(&provider);
+        provider.correct_signals -= 1;
+        assert!(settled.check_monotonic(&provider).is_err());
+    }
+}
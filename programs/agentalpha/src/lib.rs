@@ -1,8 +1,35 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use sha2::{Sha256, Digest};
+use migration::{OriginalProvider, OriginalSignalCommit};
+use price_display::{format_price_cents, PriceDisplay};
 
-// Deployed program ID
-declare_id!("6sDwzatESkmF5T3K3rfNta4DCRgH8z9ZdYoPXeMtKRmP");
+pub mod canonical_json;
+pub mod cluster;
+pub mod content;
+pub mod domain;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+#[cfg(any(test, feature = "debug-invariants"))]
+pub mod invariants;
+pub mod lookup_table;
+pub mod migration;
+pub mod price_display;
+
+// Deployed program ID, selected by the cluster feature
+pub use cluster::{check_id, id, ID};
+
+/// Check the state invariants of accounts an instruction touched (`debug-invariants` builds only)
+macro_rules! check_invariants {
+    ($($account:expr),+) => {
+        #[cfg(feature = "debug-invariants")]
+        {
+            use crate::invariants::Invariant;
+            $($account.check_invariants()?;)+
+        }
+    };
+}
 
 #[program]
 pub mod agentalpha {
@@ -16,31 +43,76 @@ pub mod agentalpha {
         categories: Vec<u8>,
         price_lamports: u64,
     ) -> Result<()> {
-        let provider = &mut ctx.accounts.provider;
+        init_provider(
+            &mut ctx.accounts.provider,
+            ctx.accounts.authority.key(),
+            name,
+            endpoint,
+            categories,
+            price_lamports,
+            ctx.bumps.provider,
+            Clock::get()?.unix_timestamp,
+        )
+    }
+
+    /// Register several providers in one instruction on behalf of an onboarding platform, which pays
+    /// their rent and is recorded on each as its attribution key with a cut of their SOL sales
+    /// remaining_accounts holds a (Provider PDA, authority signer) pair per entry, in order
+    pub fn register_providers_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RegisterProvidersBatch<'info>>,
+        entries: Vec<ProviderOnboarding>,
+        platform_fee_bps: u16,   // Platform's cut of each SOL sale
+    ) -> Result<()> {
+        let platform = ctx.accounts.platform.key();
         let clock = Clock::get()?;
         
-        require!(name.len() <= 64, AgentAlphaError::NameTooLong);
-        require!(endpoint.len() <= 256, AgentAlphaError::EndpointTooLong);
-        require!(categories.len() <= 8, AgentAlphaError::TooManyCategories);
-        
-        provider.authority = ctx.accounts.authority.key();
-        provider.name = name;
-        provider.endpoint = endpoint;
-        provider.categories = categories;
-        provider.price_lamports = price_lamports;
-        provider.total_signals = 0;
-        provider.correct_signals = 0;
-        provider.total_return_bps = 0;
-        provider.created_at = clock.unix_timestamp;
-        provider.updated_at = clock.unix_timestamp;
-        provider.bump = ctx.bumps.provider;
+        require!(
+            !entries.is_empty() && entries.len() <= MAX_BATCH_REGISTRATIONS,
+            AgentAlphaError::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() == entries.len() * 2,
+            AgentAlphaError::InvalidBatchSize
+        );
+        require!(platform_fee_bps <= MAX_PLATFORM_FEE_BPS, AgentAlphaError::InvalidPlatformFee);
         
-        emit!(ProviderRegistered {
-            provider: provider.key(),
-            authority: provider.authority,
-            name: provider.name.clone(),
-            endpoint: provider.endpoint.clone(),
-        });
+        for (entry, accounts) in entries.into_iter().zip(ctx.remaining_accounts.chunks(2)) {
+            let (provider_info, authority) = (&accounts[0], &accounts[1]);
+            // Each hosted agent's key signs, so a platform cannot take another key's provider address
+            require!(authority.is_signer, AgentAlphaError::Unauthorized);
+            let (provider_key, bump) =
+                Pubkey::find_program_address(&[b"provider", authority.key.as_ref()], &crate::ID);
+            require_keys_eq!(provider_info.key(), provider_key, AgentAlphaError::InvalidProviderAccount);
+            
+            create_pda_account(
+                &ctx.accounts.platform.to_account_info(),
+                provider_info,
+                &ctx.accounts.system_program.to_account_info(),
+                Provider::SIZE,
+                &[b"provider", authority.key.as_ref(), &[bump]],
+            )?;
+            let mut provider = Box::new(Account::<Provider>::try_from_unchecked(provider_info)?);
+            init_provider(
+                &mut provider,
+                authority.key(),
+                entry.name,
+                entry.endpoint,
+                entry.categories,
+                entry.price_lamports,
+                bump,
+                clock.unix_timestamp,
+            )?;
+            provider.platform = platform;
+            provider.platform_fee_bps = platform_fee_bps;
+            provider.exit(&crate::ID)?;
+            
+            emit!(ProviderOnboarded {
+                event_seq: event_seq(),
+                provider: provider_key,
+                platform,
+                platform_fee_bps,
+            });
+        }
         
         Ok(())
     }
@@ -51,6 +123,7 @@ pub mod agentalpha {
         name: Option<String>,
         endpoint: Option<String>,
         price_lamports: Option<u64>,
+        max_open_signals: Option<u16>,  // 0 = unlimited
     ) -> Result<()> {
         let provider = &mut ctx.accounts.provider;
         let clock = Clock::get()?;
@@ -60,44 +133,481 @@ pub mod agentalpha {
             provider.name = n;
         }
         if let Some(e) = endpoint {
+            // Buyers are routed to the endpoint, so a change only activates after the notice period
             require!(e.len() <= 256, AgentAlphaError::EndpointTooLong);
-            provider.endpoint = e;
+            provider.endpoint_effective_at = clock.unix_timestamp + ENDPOINT_CHANGE_DELAY_SECS;
+            
+            emit!(EndpointChangeProposed {
+                event_seq: event_seq(),
+                provider: provider.key(),
+                current_endpoint: provider.endpoint.clone(),
+                new_endpoint: e.clone(),
+                effective_at: provider.endpoint_effective_at,
+            });
+            provider.pending_endpoint = e;
         }
         if let Some(p) = price_lamports {
-            provider.price_lamports = p;
+            // Decreases apply immediately; increases only after the notice period
+            provider.apply_scheduled_price(clock.unix_timestamp);
+            if p <= provider.price_lamports {
+                provider.price_lamports = p;
+                provider.scheduled_price_lamports = 0;
+                provider.price_effective_at = 0;
+            } else {
+                provider.scheduled_price_lamports = p;
+                provider.price_effective_at = clock.unix_timestamp + PRICE_NOTICE_SECS;
+                
+                emit!(PriceChangeScheduled {
+                    event_seq: event_seq(),
+                    provider: provider.key(),
+                    current_price_lamports: provider.price_lamports,
+                    new_price_lamports: p,
+                    effective_at: provider.price_effective_at,
+                });
+            }
+        }
+        if let Some(m) = max_open_signals {
+            provider.max_open_signals = m;
+        }
+        
+        provider.updated_at = clock.unix_timestamp;
+        Ok(())
+    }
+
+    /// Switch to the pending endpoint once its notice period has passed (anyone can crank)
+    pub fn activate_endpoint(ctx: Context<ActivateEndpoint>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(provider.endpoint_effective_at > 0, AgentAlphaError::NoPendingEndpoint);
+        require!(
+            clock.unix_timestamp >= provider.endpoint_effective_at,
+            AgentAlphaError::EndpointNoticeActive
+        );
+        
+        let new_endpoint = std::mem::take(&mut provider.pending_endpoint);
+        let previous_endpoint = std::mem::replace(&mut provider.endpoint, new_endpoint);
+        provider.endpoint_effective_at = 0;
+        provider.health_probes = [EndpointProbe::default(); MAX_HEALTH_PROBES];
+        provider.health_probe_head = 0;
+        provider.updated_at = clock.unix_timestamp;
+        
+        emit!(EndpointChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            previous_endpoint,
+            new_endpoint: provider.endpoint.clone(),
+        });
+        
+        Ok(())
+    }
+
+    /// Drop a pending endpoint change (authority or recovery key)
+    pub fn cancel_endpoint_change(ctx: Context<CancelEndpointChange>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        
+        require!(provider.endpoint_effective_at > 0, AgentAlphaError::NoPendingEndpoint);
+        let cancelled_endpoint = std::mem::take(&mut provider.pending_endpoint);
+        provider.endpoint_effective_at = 0;
+        
+        emit!(EndpointChangeCancelled {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            cancelled_endpoint,
+            cancelled_by: ctx.accounts.signer.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Announce a payload schema / minimum client version change
+    pub fn set_provider_schema(
+        ctx: Context<UpdateProvider>,
+        min_client_version: u32,
+        payload_schema: u8,      // 0=JSON_V1, 1=BINARY_V2, 2=ENCRYPTED
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(payload_schema <= SCHEMA_ENCRYPTED, AgentAlphaError::InvalidPayloadSchema);
+        
+        let previous_schema = provider.payload_schema;
+        let previous_min_client_version = provider.min_client_version;
+        provider.min_client_version = min_client_version;
+        provider.payload_schema = payload_schema;
+        provider.updated_at = clock.unix_timestamp;
+        
+        emit!(ProviderSchemaChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            previous_schema,
+            payload_schema,
+            previous_min_client_version,
+            min_client_version,
+        });
+        
+        Ok(())
+    }
+
+    /// Submit a TEE attestation hash for verification (domain::attestation_hash of the report)
+    /// Any previous verification is cleared until the new hash is verified
+    pub fn submit_attestation(
+        ctx: Context<UpdateProvider>,
+        attestation_hash: [u8; 32],
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        provider.attestation_hash = attestation_hash;
+        provider.tee_verified = false;
+        provider.updated_at = clock.unix_timestamp;
+        
+        emit!(AttestationUpdated {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            attestation_hash,
+            tee_verified: false,
+            verifier: Pubkey::default(),
+        });
+        
+        Ok(())
+    }
+
+    /// Verify a provider's submitted attestation (attestation authority only)
+    /// `attestation_hash` must match what the provider submitted
+    pub fn verify_attestation(
+        ctx: Context<VerifyAttestation>,
+        attestation_hash: [u8; 32],
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        
+        require!(
+            provider.attestation_hash == attestation_hash && attestation_hash != [0u8; 32],
+            AgentAlphaError::AttestationMismatch
+        );
+        provider.tee_verified = true;
+        
+        emit!(AttestationUpdated {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            attestation_hash,
+            tee_verified: true,
+            verifier: ctx.accounts.attestation_authority.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Register a recovery key (only if none is set; afterwards only the recovery key can change it)
+    pub fn set_recovery_key(
+        ctx: Context<UpdateProvider>,
+        recovery_key: Pubkey,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        
+        require!(provider.recovery_key == Pubkey::default(), AgentAlphaError::Unauthorized);
+        provider.recovery_key = recovery_key;
+        
+        emit!(RecoveryKeyChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            recovery_key,
+        });
+        
+        Ok(())
+    }
+
+    /// Replace the recovery key (recovery key only)
+    pub fn change_recovery_key(
+        ctx: Context<RecoverProvider>,
+        recovery_key: Pubkey,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        provider.recovery_key = recovery_key;
+        
+        emit!(RecoveryKeyChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            recovery_key,
+        });
+        
+        Ok(())
+    }
+
+    /// Freeze or unfreeze the provider (recovery key only)
+    /// A frozen provider cannot be updated or commit signals, and a pending rotation is cancelled on unfreeze
+    pub fn set_provider_frozen(
+        ctx: Context<RecoverProvider>,
+        frozen: bool,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        
+        provider.frozen = frozen;
+        if frozen {
+            provider.pending_endpoint = String::new();
+            provider.endpoint_effective_at = 0;
+        } else {
+            provider.pending_authority = Pubkey::default();
+            provider.rotation_at = 0;
         }
         
+        emit!(ProviderFrozenChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            frozen,
+        });
+        
+        Ok(())
+    }
+
+    /// Start rotating a frozen provider to a new authority (recovery key only)
+    pub fn begin_authority_rotation(
+        ctx: Context<RecoverProvider>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(provider.frozen, AgentAlphaError::ProviderNotFrozen);
+        provider.pending_authority = new_authority;
+        provider.rotation_at = clock.unix_timestamp + RECOVERY_DELAY_SECS;
+        
+        emit!(AuthorityRotationScheduled {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            current_authority: provider.authority,
+            new_authority,
+            rotation_at: provider.rotation_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Apply a scheduled rotation once the delay has passed and unfreeze (recovery key only)
+    pub fn complete_authority_rotation(ctx: Context<RecoverProvider>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(provider.frozen, AgentAlphaError::ProviderNotFrozen);
+        require!(
+            provider.pending_authority != Pubkey::default(),
+            AgentAlphaError::NoPendingRotation
+        );
+        require!(
+            clock.unix_timestamp >= provider.rotation_at,
+            AgentAlphaError::RotationDelayActive
+        );
+        
+        let previous_authority = provider.authority;
+        provider.authority = provider.pending_authority;
+        provider.pending_authority = Pubkey::default();
+        provider.rotation_at = 0;
+        provider.frozen = false;
+        provider.updated_at = clock.unix_timestamp;
+        
+        emit!(AuthorityRotated {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            previous_authority,
+            new_authority: provider.authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Pause or resume sales (vacation mode)
+    /// An optional window pauses sales between pause_start and pause_end
+    /// Subscriptions don't run while sales are paused: the pause time is added to their expiry
+    pub fn set_provider_pause(
+        ctx: Context<UpdateProvider>,
+        accepting_purchases: bool,
+        pause_start: i64,        // Unix timestamp, 0 = no window
+        pause_end: i64,          // Unix timestamp, 0 = no window
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(pause_start <= pause_end, AgentAlphaError::InvalidPauseWindow);
+        
+        provider.close_pause(clock.unix_timestamp);
+        provider.accepting_purchases = accepting_purchases;
+        provider.pause_start = pause_start;
+        provider.pause_end = pause_end;
         provider.updated_at = clock.unix_timestamp;
+        
+        emit!(ProviderPauseChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            accepting_purchases,
+            pause_start,
+            pause_end,
+        });
+        
+        Ok(())
+    }
+
+    /// Issue a discount coupon; `code_hash` is Coupon::code_hash of the coupon's own address and
+    /// the code, so a code's hash differs between coupons and can't be looked up across providers
+    pub fn create_coupon(
+        ctx: Context<CreateCoupon>,
+        coupon_id: u64,
+        code_hash: [u8; 32],
+        percent_off: u8,         // 1-100
+        max_uses: u32,
+        expires_at: i64,
+    ) -> Result<()> {
+        let coupon = &mut ctx.accounts.coupon;
+        
+        require!((1..=100).contains(&percent_off), AgentAlphaError::InvalidCoupon);
+        require!(max_uses > 0, AgentAlphaError::InvalidCoupon);
+        
+        coupon.coupon_id = coupon_id;
+        coupon.provider = ctx.accounts.provider.key();
+        coupon.code_hash = code_hash;
+        coupon.percent_off = percent_off;
+        coupon.max_uses = max_uses;
+        coupon.uses = 0;
+        coupon.expires_at = expires_at;
+        coupon.bump = ctx.bumps.coupon;
+        
+        emit!(CouponCreated {
+            event_seq: event_seq(),
+            provider: coupon.provider,
+            coupon: coupon.key(),
+            percent_off,
+            max_uses,
+            expires_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Create the provider's equity-curve checkpoint buffer
+    pub fn init_provider_curve(ctx: Context<InitProviderCurve>) -> Result<()> {
+        let curve = &mut ctx.accounts.provider_curve;
+        
+        curve.provider = ctx.accounts.provider.key();
+        curve.head = 0;
+        curve.len = 0;
+        curve.points = [CurvePoint::default(); CURVE_POINTS];
+        curve.bump = ctx.bumps.provider_curve;
+        
         Ok(())
     }
 
     /// Commit a signal hash (before revealing details)
+    /// Pass a parent commit to make this signal an amendment of it
+    /// Experimental (paper) signals settle into a separate stats block
+    /// Exclusivity caps how many buyers may purchase the signal
     pub fn commit_signal(
         ctx: Context<CommitSignal>,
         signal_hash: [u8; 32],
+        experimental: bool,
+        exclusivity: u8,         // 0=UNLIMITED, 1=LIMITED, 2=EXCLUSIVE (1-of-1)
+        max_buyers: u16,         // Buyer cap for LIMITED, ignored otherwise
+        category: u8,            // One of the provider's categories (any, if it declared none)
     ) -> Result<()> {
-        let commit = &mut ctx.accounts.signal_commit;
         let clock = Clock::get()?;
         
-        commit.provider = ctx.accounts.provider.key();
-        commit.signal_hash = signal_hash;
-        commit.committed_at = clock.unix_timestamp;
-        commit.revealed = false;
-        commit.outcome_recorded = false;
-        commit.bump = ctx.bumps.signal_commit;
+        require!(ctx.accounts.config.accepts_new_commits(), AgentAlphaError::HashSchemaDisabled);
         
-        emit!(SignalCommitted {
-            provider: commit.provider,
+        // Prefund whoever later settles or expires this signal
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.signal_commit.to_account_info(),
+                },
+            ),
+            SETTLEMENT_DEPOSIT_LAMPORTS,
+        )?;
+        
+        if let Some(parent) = ctx.accounts.parent_commit.as_ref() {
+            require!(!parent.outcome_recorded, AgentAlphaError::ParentAlreadySettled);
+        }
+        
+        open_commit(
+            &mut ctx.accounts.signal_commit,
+            &mut ctx.accounts.provider,
+            ctx.accounts.parent_commit.as_ref().map(|p| p.key()),
             signal_hash,
-            committed_at: commit.committed_at,
-        });
+            experimental,
+            exclusivity,
+            max_buyers,
+            category,
+            ctx.bumps.signal_commit,
+            clock.unix_timestamp,
+        )
+    }
+
+    /// Commit a signal on the provider's behalf: a relayer submits and pays for a commit message
+    /// the provider authority signed off-chain (see relayed_commit_message)
+    /// The preceding instruction must be an ed25519 program verification of that signature,
+    /// and `nonce` must match the provider's relay nonce, which then advances
+    /// Until UNTAGGED_MESSAGES_CUTOFF the message may also be signed under domain::RELAY_COMMIT_V1
+    pub fn relayed_commit_signal(
+        ctx: Context<RelayedCommitSignal>,
+        signal_hash: [u8; 32],
+        experimental: bool,
+        exclusivity: u8,
+        max_buyers: u16,
+        category: u8,
+        nonce: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let provider = &ctx.accounts.provider;
         
-        Ok(())
+        require!(ctx.accounts.config.accepts_new_commits(), AgentAlphaError::HashSchemaDisabled);
+        require!(nonce == provider.relay_nonce, AgentAlphaError::InvalidRelayNonce);
+        let message = |prefix| {
+            relayed_commit_message(prefix, &provider.key(), &signal_hash, experimental, exclusivity, max_buyers, category, nonce)
+        };
+        let sysvar = &ctx.accounts.instructions_sysvar;
+        verify_preceding_ed25519(sysvar, &provider.authority, &message(domain::RELAY_COMMIT_V2)).or_else(|err| {
+            if clock.unix_timestamp < UNTAGGED_MESSAGES_CUTOFF {
+                verify_preceding_ed25519(sysvar, &provider.authority, &message(domain::RELAY_COMMIT_V1))
+            } else {
+                Err(err)
+            }
+        })?;
+        
+        // The relayer prefunds settlement as well as paying fees and rent
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.relayer.to_account_info(),
+                    to: ctx.accounts.signal_commit.to_account_info(),
+                },
+            ),
+            SETTLEMENT_DEPOSIT_LAMPORTS,
+        )?;
+        
+        ctx.accounts.provider.relay_nonce += 1;
+        open_commit(
+            &mut ctx.accounts.signal_commit,
+            &mut ctx.accounts.provider,
+            None,
+            signal_hash,
+            experimental,
+            exclusivity,
+            max_buyers,
+            category,
+            ctx.bumps.signal_commit,
+            clock.unix_timestamp,
+        )
     }
 
     /// Reveal a signal with full TP/SL data
     /// Hash format: "{token}:{direction}:{entry}:{tp}:{sl}:{timeframe}:{confidence}"
-    /// Where prices are in cents (e.g., $100.50 = 10050)
+    /// Where prices are in cents of the quote currency (e.g., $100.50 = 10050)
+    /// Non-USD quotes append ":{quote_currency}" (and ":{quote_mint}" for SPL quotes)
+    /// Laddered entries append ":{price}@{weight}" per level
+    /// The commit is SHA256 of that string, or of domain::COMMIT_V2 followed by it (HASH_SCHEMA_V2)
+    /// Legacy clients may instead commit "{token}:{direction}:{confidence}:{committed_at}" until
+    /// LEGACY_HASH_SCHEMA_CUTOFF (see HASH_SCHEMA_LEGACY)
+    #[allow(clippy::too_many_arguments)]
     pub fn reveal_signal(
         ctx: Context<RevealSignal>,
         token: String,
@@ -107,306 +617,12667 @@ pub mod agentalpha {
         sl_cents: u64,           // Stop loss in cents
         timeframe_hours: u8,     // Evaluation window (1-72)
         confidence: u8,          // 0-100
+        quote_currency: u8,      // 0=USD, 1=SOL, 2=BTC, 3=ETH, 4=SPL mint
+        quote_mint: Option<Pubkey>, // Required when quote_currency=4
+        min_notional_cents: Option<u64>, // Recommended minimum position size (not hashed)
+        max_notional_cents: Option<u64>, // Recommended maximum position size (not hashed)
+        benchmark: u8,           // 0=NONE, 1=SOL, 2=BTC, 3=ETH (not hashed, measured from reveal)
+        entry_ladder: Vec<EntryLevel>, // Laddered entries (empty = single entry at entry_cents)
     ) -> Result<()> {
         let commit = &mut ctx.accounts.signal_commit;
         let clock = Clock::get()?;
         
-        require!(!commit.revealed, AgentAlphaError::AlreadyRevealed);
-        require!(token.len() <= 16, AgentAlphaError::TokenTooLong);
-        require!(direction <= 1, AgentAlphaError::InvalidDirection);
-        require!(timeframe_hours >= 1 && timeframe_hours <= 72, AgentAlphaError::InvalidTimeframe);
-        require!(confidence <= 100, AgentAlphaError::InvalidConfidence);
+        commit.reveal(
+            SignalPayload {
+                token,
+                direction,
+                entry_cents,
+                tp_cents,
+                sl_cents,
+                timeframe_hours,
+                confidence,
+                quote_currency,
+                quote_mint,
+                min_notional_cents,
+                max_notional_cents,
+                benchmark,
+                entry_ladder,
+            },
+            &ctx.accounts.token_registry,
+            &ctx.accounts.config,
+            clock.unix_timestamp,
+        )?;
+        let provider_key = ctx.accounts.provider.key();
+        ctx.accounts.provider.record_reveal_delay(provider_key, commit.revealed_at - commit.committed_at, clock.unix_timestamp);
         
-        // Verify hash matches the revealed data
-        // Format: "{token}:{direction}:{entry}:{tp}:{sl}:{timeframe}:{confidence}"
-        let data_to_hash = format!(
-            "{}:{}:{}:{}:{}:{}:{}",
-            token, direction, entry_cents, tp_cents, sl_cents, timeframe_hours, confidence
+        // Stake on the revealed confidence, if the provider opted in
+        let stake_lamports = commit.confidence as u64 * ctx.accounts.provider.stake_per_confidence_lamports;
+        if stake_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: ctx.accounts.signal_commit.to_account_info(),
+                    },
+                ),
+                stake_lamports,
+            )?;
+        }
+        let commit = &mut ctx.accounts.signal_commit;
+        commit.stake_lamports = stake_lamports;
+        if let Some(queue) = ctx.accounts.task_queue.as_mut() {
+            queue.enqueue(commit.key(), commit.revealed_at + commit.timeframe_hours as i64 * 3600);
+        }
+        // An amendment restates its parent's position, which the token stats and consensus already count
+        let is_amendment = commit.parent_signal.is_some();
+        if let Some(stats) = ctx.accounts.token_stats.as_mut().filter(|_| !is_amendment) {
+            stats.record_reveal(commit, clock.unix_timestamp);
+            emit!(TokenStatsUpdated {
+                event_seq: event_seq(),
+                token: stats.token.clone(),
+                active_signals: stats.active_signals,
+                direction_skew_bps: stats.direction_skew_bps(),
+                avg_confidence: stats.avg_confidence(),
+            });
+        }
+        if let Some(consensus) = ctx.accounts.token_consensus.as_mut().filter(|_| !is_amendment) {
+            let score_bps = ctx.accounts.provider.reputation_score_bps();
+            consensus.record_reveal(commit, score_bps, clock.unix_timestamp);
+            emit!(TokenConsensusUpdated {
+                event_seq: event_seq(),
+                token: consensus.token.clone(),
+                open_signals: consensus.open_signals,
+                consensus_bps: consensus.consensus_bps(),
+            });
+        }
+        
+        // An amendment revealed while its parent is still open supersedes the parent's SL, as long
+        // as a fresh mark shows the new SL hasn't already been crossed
+        if let Some(parent_key) = commit.parent_signal {
+            let parent = ctx
+                .accounts
+                .parent_commit
+                .as_mut()
+                .ok_or(AgentAlphaError::MissingParentSignal)?;
+            require_keys_eq!(parent.key(), parent_key, AgentAlphaError::MissingParentSignal);
+            require!(
+                parent.revealed && parent.token == commit.token && parent.direction == commit.direction,
+                AgentAlphaError::AmendmentMismatch
+            );
+            
+            let parent_expires_at = parent.revealed_at + parent.timeframe_hours as i64 * 3600;
+            if !parent.outcome_recorded
+                && clock.unix_timestamp <= parent_expires_at
+                && parent.accepts_amended_sl(commit.sl_cents, clock.unix_timestamp)
+            {
+                let previous_sl_cents = parent.effective_sl_cents();
+                parent.amended_sl_cents = Some(commit.sl_cents);
+                
+                emit!(SignalAmended {
+                    event_seq: event_seq(),
+                    parent: parent_key,
+                    amendment: commit.key(),
+                    previous_sl_cents,
+                    new_sl_cents: commit.sl_cents,
+                });
+            } else {
+                // The amendment still reveals, it just no longer affects the parent
+                let code = if parent.outcome_recorded || clock.unix_timestamp > parent_expires_at {
+                    WARN_AMENDMENT_TOO_LATE
+                } else {
+                    WARN_AMENDMENT_SL_CROSSED
+                };
+                emit!(BusinessWarning {
+                    event_seq: event_seq(),
+                    code,
+                    subject: parent_key,
+                    actor: ctx.accounts.authority.key(),
+                });
+            }
+        }
+        
+        Ok(())
+    }
+
+    /// Reveal several of the provider's signals in one instruction
+    /// remaining_accounts holds a (SignalCommit, TokenRegistry PDA) pair per payload, in order
+    /// Amendments must be revealed individually with reveal_signal
+    pub fn reveal_signals_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevealSignalsBatch<'info>>,
+        payloads: Vec<SignalPayload>,
+    ) -> Result<()> {
+        let provider_key = ctx.accounts.provider.key();
+        let clock = Clock::get()?;
+        
+        require!(
+            !payloads.is_empty() && payloads.len() <= MAX_BATCH_REVEALS,
+            AgentAlphaError::InvalidBatchSize
         );
-        let mut hasher = Sha256::new();
-        hasher.update(data_to_hash.as_bytes());
-        let computed_hash: [u8; 32] = hasher.finalize().into();
+        require!(
+            ctx.remaining_accounts.len() == payloads.len() * 2,
+            AgentAlphaError::InvalidBatchSize
+        );
+        
+        for (payload, accounts) in payloads.into_iter().zip(ctx.remaining_accounts.chunks(2)) {
+            let mut commit = Account::<SignalCommit>::try_from(&accounts[0])?;
+            require_keys_eq!(commit.provider, provider_key, AgentAlphaError::SignalProviderMismatch);
+            require!(commit.parent_signal.is_none(), AgentAlphaError::AmendmentInBatch);
+            
+            let (registry_key, _) = Pubkey::find_program_address(&[b"token", payload.token.as_bytes()], &crate::ID);
+            require_keys_eq!(accounts[1].key(), registry_key, AgentAlphaError::InvalidTokenRegistry);
+            
+            commit.reveal(payload, &accounts[1], &ctx.accounts.config, clock.unix_timestamp)?;
+            
+            let stake_lamports = commit.confidence as u64 * ctx.accounts.provider.stake_per_confidence_lamports;
+            if stake_lamports > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: ctx.accounts.authority.to_account_info(),
+                            to: accounts[0].clone(),
+                        },
+                    ),
+                    stake_lamports,
+                )?;
+            }
+            commit.stake_lamports = stake_lamports;
+            commit.exit(&crate::ID)?;
+            if let Some(queue) = ctx.accounts.task_queue.as_mut() {
+                queue.enqueue(commit.key(), commit.revealed_at + commit.timeframe_hours as i64 * 3600);
+            }
+            ctx.accounts.provider.record_reveal_delay(provider_key, commit.revealed_at - commit.committed_at, clock.unix_timestamp);
+        }
+        
+        Ok(())
+    }
+
+    /// Buy access to a signal; a SOL payment is escrowed for the provider until delivery is acked
+    /// A wrapped SOL price is topped up from the buyer's lamports, so it can be paid in plain SOL
+    /// A coupon code (the preimage of a provider coupon's hash) applies its discount
+    /// Retrying with the intent id of a completed purchase succeeds without charging again, with a
+    /// WARN_DUPLICATE_PURCHASE warning
+    /// Performance-fee signals take a notional instead of a price: the buyer escrows the fee on a
+    /// 100% return of it, settled against the realized return by settle_performance_fee
+    pub fn purchase_signal(
+        ctx: Context<PurchaseSignal>,
+        coupon_code: Option<String>,
+        intent_id: Option<u64>,
+        notional_lamports: Option<u64>,
+    ) -> Result<()> {
+        let provider = &ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        // The buyer signs, or a session key of theirs acts within the session's limits
+        let session_key = match (ctx.accounts.buyer_session.as_ref(), ctx.accounts.session_key.as_ref()) {
+            (Some(session), Some(key)) => {
+                require_keys_eq!(session.session_key, key.key(), AgentAlphaError::Unauthorized);
+                key.key()
+            }
+            (None, None) => {
+                require!(ctx.accounts.buyer.is_signer, AgentAlphaError::Unauthorized);
+                Pubkey::default()
+            }
+            _ => return err!(AgentAlphaError::Unauthorized),
+        };
+        
+        let existing = &ctx.accounts.signal_access;
+        if existing.buyer != Pubkey::default() {
+            require!(
+                intent_id.is_some_and(|id| id != 0 && id == existing.intent_id),
+                AgentAlphaError::AlreadyPurchased
+            );
+            emit!(BusinessWarning {
+                event_seq: event_seq(),
+                code: WARN_DUPLICATE_PURCHASE,
+                subject: existing.key(),
+                actor: ctx.accounts.buyer.key(),
+            });
+            return Ok(());
+        }
         
         require!(
-            computed_hash == commit.signal_hash,
-            AgentAlphaError::HashMismatch
+            provider.is_accepting_purchases(clock.unix_timestamp),
+            AgentAlphaError::ProviderNotAcceptingPurchases
+        );
+        let commit = &ctx.accounts.signal_commit;
+        require!(!commit.outcome_recorded && !commit.expired, AgentAlphaError::SignalClosed);
+        require!(!commit.auctioned, AgentAlphaError::SignalAuctioned);
+        require!(
+            commit.max_buyers == 0 || commit.buyer_count < commit.max_buyers,
+            AgentAlphaError::SignalSoldOut
         );
         
-        // Store revealed data
-        commit.revealed = true;
-        commit.token = token;
-        commit.direction = direction;
-        commit.entry_cents = entry_cents;
-        commit.tp_cents = tp_cents;
-        commit.sl_cents = sl_cents;
-        commit.timeframe_hours = timeframe_hours;
-        commit.confidence = confidence;
-        commit.revealed_at = clock.unix_timestamp;
+        // Embargoed category/buyer-class combinations
+        if provider.purchase_policy != Pubkey::default() {
+            let policy = ctx.accounts.purchase_policy.as_ref().ok_or(AgentAlphaError::MissingPurchasePolicy)?;
+            require_keys_eq!(policy.key(), provider.purchase_policy, AgentAlphaError::MissingPurchasePolicy);
+            let buyer_class = ctx.accounts.buyer_class.as_ref().ok_or(AgentAlphaError::MissingPurchasePolicy)?;
+            let (class_key, _) = Pubkey::find_program_address(
+                &[b"buyer_class", policy.key().as_ref(), ctx.accounts.buyer.key().as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(buyer_class.key(), class_key, AgentAlphaError::MissingPurchasePolicy);
+            let classes = if buyer_class.data_is_empty() {
+                0
+            } else {
+                BuyerClass::try_deserialize(&mut &buyer_class.try_borrow_data()?[..])?.classes
+            };
+            require!(!policy.blocks(commit.category, classes), AgentAlphaError::SignalEmbargoed);
+        }
         
-        emit!(SignalRevealed {
+        // Token-gated feeds: the buyer must hold enough of the gate mint
+        if provider.gate_mint != Pubkey::default() {
+            let holding = ctx.accounts.gate_token_account.as_ref().ok_or(AgentAlphaError::AccessGateNotMet)?;
+            require!(
+                holding.owner == ctx.accounts.buyer.key()
+                    && holding.mint == provider.gate_mint
+                    && holding.amount >= provider.gate_min_amount,
+                AgentAlphaError::AccessGateNotMet
+            );
+        }
+        
+        // Subscribers take the signal without paying
+        let subscribed = match ctx.accounts.subscription.as_ref() {
+            Some(subscription) => {
+                require!(
+                    subscription.covers(provider, commit.exclusivity, clock.unix_timestamp),
+                    AgentAlphaError::SubscriptionNotActive
+                );
+                true
+            }
+            None => false,
+        };
+        // As do buyers holding a replacement credit from a signal the provider withdrew
+        let credited = match ctx.accounts.replacement_credit.as_mut().filter(|_| !subscribed) {
+            Some(credit) => {
+                require!(
+                    credit.buyer == ctx.accounts.buyer.key() && credit.credit_provider == provider.key(),
+                    AgentAlphaError::NoReplacementCredit
+                );
+                credit.credit_provider = Pubkey::default();
+                true
+            }
+            None => false,
+        };
+        // And buyers spending a prepaid bundle credit on a flat-priced TIER_UNLIMITED signal
+        // Credits are bought at the base price, so they don't cover categories priced above it
+        let from_bundle = match ctx.accounts.credits.as_mut().filter(|_| !subscribed && !credited) {
+            Some(credits) => {
+                require!(
+                    commit.exclusivity == TIER_UNLIMITED
+                        && commit.performance_fee_bps() == 0
+                        && provider.price_for_category(commit.category, clock.unix_timestamp)
+                            <= provider.effective_price_lamports(clock.unix_timestamp),
+                    AgentAlphaError::BundleCreditNotApplicable
+                );
+                require!(credits.remaining > 0, AgentAlphaError::NoBundleCredits);
+                credits.remaining -= 1;
+                true
+            }
+            None => false,
+        };
+        let subscribed = subscribed || credited || from_bundle;
+        
+        let perf_fee_bps = commit.performance_fee_bps();
+        let notional_lamports = if subscribed || perf_fee_bps == 0 {
+            0
+        } else {
+            notional_lamports.filter(|n| *n > 0).ok_or(AgentAlphaError::MissingNotional)?
+        };
+        
+        let mut price_lamports = if subscribed {
+            0
+        } else if notional_lamports > 0 {
+            (notional_lamports as u128 * perf_fee_bps as u128 / 10000) as u64
+        } else {
+            commit.tier_price(provider.price_for_category(commit.category, clock.unix_timestamp))
+        };
+        let mut percent_off = 0;
+        if let Some(code) = coupon_code.filter(|_| !subscribed && notional_lamports == 0) {
+            let coupon = ctx.accounts.coupon.as_mut().ok_or(AgentAlphaError::InvalidCoupon)?;
+            let coupon_key = coupon.key();
+            coupon.redeem(&coupon_key, &code, clock.unix_timestamp)?;
+            percent_off = coupon.percent_off as u64;
+            price_lamports -= price_lamports * percent_off / 100;
+            
+            emit!(CouponRedeemed {
+                event_seq: event_seq(),
+                coupon: coupon.key(),
+                buyer: ctx.accounts.buyer.key(),
+                uses: coupon.uses,
+            });
+        }
+        if let Some(session) = ctx.accounts.buyer_session.as_mut() {
+            require!(
+                ctx.accounts.payment_registry.is_none() && ctx.accounts.price_vault.is_none(),
+                AgentAlphaError::SessionPaysSolOnly
+            );
+            session.authorize_spend(&provider.key(), price_lamports, clock.unix_timestamp)?;
+            let info = session.to_account_info();
+            let spendable = info.lamports().saturating_sub(Rent::get()?.minimum_balance(info.data_len()));
+            require!(price_lamports <= spendable, AgentAlphaError::InsufficientSessionFunds);
+        }
+        
+        // Curator shares, royalties, platform and integrator cuts and the purchase escrow are SOL-only
+        let mut escrow_lamports = 0;
+        let (payment_mint, price_paid) = if subscribed {
+            (Pubkey::default(), 0)
+        } else if notional_lamports > 0 {
+            // The most the fee can come to is escrowed whole; the split is made at settlement
+            let session = ctx.accounts.buyer_session.as_ref().map(|s| s.to_account_info());
+            pay_as_buyer(
+                session.as_ref(),
+                &ctx.accounts.buyer,
+                &ctx.accounts.system_program,
+                &ctx.accounts.signal_access.to_account_info(),
+                price_lamports,
+            )?;
+            escrow_lamports = price_lamports;
+            (Pubkey::default(), price_lamports)
+        } else if let Some(vault) = ctx.accounts.price_vault.as_ref() {
+            // Token payments go into the provider's vault for the mint, less the protocol fee and
+            // a new provider's holdback
+            let (Some(buyer_token), Some(token_program)) =
+                (ctx.accounts.buyer_token_account.as_ref(), ctx.accounts.token_program.as_ref())
+            else {
+                return err!(AgentAlphaError::MissingPaymentAccounts);
+            };
+            require!(buyer_token.mint == vault.mint, AgentAlphaError::InvalidPaymentAccount);
+            let token_amount = if let Some(payment) = ctx.accounts.payment_registry.as_ref() {
+                // The provider's creator token, converted from the SOL price at the oracle's USD rates
+                let sol = ctx.accounts.sol_registry.as_ref().ok_or(AgentAlphaError::MissingPaymentAccounts)?;
+                require!(
+                    payment.mint != Pubkey::default() && payment.mint == provider.payment_mint,
+                    AgentAlphaError::PaymentMintNotAccepted
+                );
+                require!(vault.mint == payment.mint, AgentAlphaError::InvalidPaymentAccount);
+                require!(
+                    payment.rate_is_fresh(clock.unix_timestamp) && sol.rate_is_fresh(clock.unix_timestamp),
+                    AgentAlphaError::StaleTokenRate
+                );
+                payment
+                    .token_amount_for_lamports(price_lamports, sol.usd_rate_micros)
+                    .ok_or(AgentAlphaError::StaleTokenRate)?
+            } else {
+                // Priced in the provider's price mint
+                require!(
+                    vault.mint == provider.price_mint && provider.price_mint != Pubkey::default(),
+                    AgentAlphaError::InvalidPaymentAccount
+                );
+                let base_token_amount = provider
+                    .token_price_for_category(commit.category, clock.unix_timestamp)
+                    .ok_or(AgentAlphaError::CategoryTokenPriceUnavailable)?;
+                let mut token_amount = commit.tier_price(base_token_amount);
+                token_amount -= token_amount * percent_off / 100;
+                price_lamports = 0;
+                token_amount
+            };
+            if vault.mint == token::spl_token::native_mint::ID {
+                let shortfall = token_amount.saturating_sub(buyer_token.amount);
+                if shortfall > 0 {
+                    wrap_sol(
+                        &ctx.accounts.buyer,
+                        &buyer_token.to_account_info(),
+                        &ctx.accounts.system_program,
+                        &token_program.to_account_info(),
+                        shortfall,
+                    )?;
+                }
+            }
+            let protocol_fee = ctx.accounts.config.protocol_fee(token_amount);
+            let treasury = if protocol_fee > 0 {
+                ctx.accounts
+                    .config
+                    .treasury_token_account(ctx.accounts.treasury_token_account.as_deref(), vault.mint)?
+            } else {
+                vault.to_account_info()
+            };
+            let held = ctx.accounts.config.vesting_holdback(provider.created_at, token_amount - protocol_fee, clock.unix_timestamp);
+            let holdback = if held > 0 {
+                ctx.accounts
+                    .token_holdback
+                    .as_ref()
+                    .filter(|h| h.mint == vault.mint)
+                    .ok_or(AgentAlphaError::MissingPaymentAccounts)?
+                    .to_account_info()
+            } else {
+                vault.to_account_info()
+            };
+            let payouts = [
+                (treasury, protocol_fee),
+                (holdback, held),
+                (vault.to_account_info(), token_amount - protocol_fee - held),
+            ];
+            for (to, amount) in payouts {
+                if amount == 0 {
+                    continue;
+                }
+                token::transfer(
+                    CpiContext::new(
+                        token_program.to_account_info(),
+                        token::Transfer {
+                            from: buyer_token.to_account_info(),
+                            to,
+                            authority: ctx.accounts.buyer.to_account_info(),
+                        },
+                    ),
+                    amount,
+                )?;
+            }
+            (vault.mint, token_amount)
+        } else {
+            // Bonded curators take their share; it stays in the provider account until claimed
+            let curator_cut = if provider.total_bonded > 0 {
+                price_lamports * provider.curator_share_bps as u64 / 10000
+            } else {
+                0
+            };
+            let session = ctx.accounts.buyer_session.as_ref().map(|s| s.to_account_info());
+            if curator_cut > 0 {
+                pay_as_buyer(
+                    session.as_ref(),
+                    &ctx.accounts.buyer,
+                    &ctx.accounts.system_program,
+                    &ctx.accounts.provider.to_account_info(),
+                    curator_cut,
+                )?;
+                let provider = &mut ctx.accounts.provider;
+                provider.curator_reward_index += curator_cut as u128 * REWARD_INDEX_SCALE / provider.total_bonded as u128;
+            }
+            // Cited upstream providers accrue their royalty in their provider account until claimed
+            let royalty = price_lamports * CITATION_ROYALTY_BPS / 10000;
+            let mut royalties = 0;
+            let citations = ctx.accounts.signal_commit.citations;
+            let sources = [&mut ctx.accounts.cited_provider, &mut ctx.accounts.second_cited_provider];
+            for (cited, source) in citations.iter().zip(sources) {
+                if *cited == Pubkey::default() || royalty == 0 {
+                    continue;
+                }
+                let source = source
+                    .as_mut()
+                    .filter(|s| s.key() == *cited)
+                    .ok_or(AgentAlphaError::MissingCitedProvider)?;
+                pay_as_buyer(
+                    session.as_ref(),
+                    &ctx.accounts.buyer,
+                    &ctx.accounts.system_program,
+                    &source.to_account_info(),
+                    royalty,
+                )?;
+                source.citation_royalties_unclaimed += royalty;
+                source.citation_royalties_earned += royalty;
+                royalties += royalty;
+            }
+            // The protocol fee goes to the treasury
+            let protocol_fee = ctx.accounts.config.protocol_fee(price_lamports);
+            if protocol_fee > 0 {
+                let treasury = ctx.accounts.config.treasury_account(ctx.accounts.treasury.as_ref())?;
+                pay_as_buyer(
+                    session.as_ref(),
+                    &ctx.accounts.buyer,
+                    &ctx.accounts.system_program,
+                    &treasury,
+                    protocol_fee,
+                )?;
+            }
+            // The onboarding platform takes its cut directly
+            let platform_cut = price_lamports * ctx.accounts.provider.platform_fee_bps as u64 / 10000;
+            if platform_cut > 0 {
+                let platform = ctx
+                    .accounts
+                    .platform
+                    .as_ref()
+                    .filter(|p| p.key() == ctx.accounts.provider.platform)
+                    .ok_or(AgentAlphaError::MissingPlatformAccount)?;
+                pay_as_buyer(
+                    session.as_ref(),
+                    &ctx.accounts.buyer,
+                    &ctx.accounts.system_program,
+                    &platform.to_account_info(),
+                    platform_cut,
+                )?;
+            }
+            // A registered integrator that routed the purchase accrues its fee share in its account until claimed
+            let mut integrator_cut = 0;
+            if let Some(integrator) = ctx.accounts.integrator.as_mut() {
+                integrator_cut = price_lamports * integrator.fee_bps as u64 / 10000;
+                if integrator_cut > 0 {
+                    pay_as_buyer(
+                        session.as_ref(),
+                        &ctx.accounts.buyer,
+                        &ctx.accounts.system_program,
+                        &integrator.to_account_info(),
+                        integrator_cut,
+                    )?;
+                }
+                integrator.purchases_routed += 1;
+                integrator.fees_earned += integrator_cut;
+                integrator.fees_unclaimed += integrator_cut;
+            }
+            // New providers have part of their proceeds held back in the provider account to vest
+            let proceeds = price_lamports - curator_cut - royalties - protocol_fee - platform_cut - integrator_cut;
+            let held = ctx.accounts.config.vesting_holdback(ctx.accounts.provider.created_at, proceeds, clock.unix_timestamp);
+            if held > 0 {
+                pay_as_buyer(
+                    session.as_ref(),
+                    &ctx.accounts.buyer,
+                    &ctx.accounts.system_program,
+                    &ctx.accounts.provider.to_account_info(),
+                    held,
+                )?;
+                ctx.accounts.provider.vesting_held_lamports += held;
+            }
+            // The rest is escrowed in the access record until delivery is acked or the escrow window ends
+            escrow_lamports = proceeds - held;
+            pay_as_buyer(
+                session.as_ref(),
+                &ctx.accounts.buyer,
+                &ctx.accounts.system_program,
+                &ctx.accounts.signal_access.to_account_info(),
+                escrow_lamports,
+            )?;
+            (Pubkey::default(), price_lamports)
+        };
+        
+        let commit = &mut ctx.accounts.signal_commit;
+        commit.buyer_count += 1;
+        
+        let access = &mut ctx.accounts.signal_access;
+        access.buyer = ctx.accounts.buyer.key();
+        access.signal = commit.key();
+        access.payment_mint = payment_mint;
+        access.price_paid = price_paid;
+        access.purchased_at = clock.unix_timestamp;
+        access.delivered_at = 0;
+        access.intent_id = intent_id.unwrap_or(0);
+        access.spend_reported = false;
+        access.escrow_lamports = escrow_lamports;
+        access.bump = ctx.bumps.signal_access;
+        access.refund_bps = if escrow_lamports > 0 && notional_lamports == 0 {
+            ctx.accounts.provider.guarantee_refund_bps()
+        } else {
+            0
+        };
+        access.refund_lamports = 0;
+        access.notional_lamports = notional_lamports;
+        
+        if let Some(report) = ctx.accounts.spend_report.as_mut() {
+            report.record_purchase(commit.provider, price_lamports);
+        }
+        
+        emit!(SignalPurchased {
+            event_seq: event_seq(),
             provider: commit.provider,
             signal_hash: commit.signal_hash,
-            token: commit.token.clone(),
-            direction: commit.direction,
-            entry_cents: commit.entry_cents,
-            tp_cents: commit.tp_cents,
-            sl_cents: commit.sl_cents,
-            timeframe_hours: commit.timeframe_hours,
-            confidence: commit.confidence,
+            buyer: access.buyer,
+            price_lamports,
+            payment_mint,
+            price_paid,
+            exclusivity: commit.exclusivity,
+            buyer_count: commit.buyer_count,
+            session_key,
+            subscribed,
         });
         
+        check_invariants!(ctx.accounts.signal_access, ctx.accounts.signal_commit, ctx.accounts.provider);
+        
         Ok(())
     }
 
-    /// Record signal outcome (called by oracle)
+    /// Record signal outcome (Config.settlement_oracle only)
     /// Determines if TP hit, SL hit, or expired
+    /// The price feed, publish slot/time and raw update hash are stored as a
+    /// settlement proof so the outcome can be audited (and disputed) later
+    #[allow(clippy::too_many_arguments)]
     pub fn record_outcome(
         ctx: Context<RecordOutcome>,
-        outcome: u8,             // 1=TP_HIT, 2=SL_HIT, 3=EXPIRED
+        outcome: u8,             // 1=TP_HIT, 2=SL_HIT, 3=EXPIRED, 4=BOTH_TOUCHED (TP and SL inside one candle), 7=NOT_FILLED
         final_price_cents: u64,  // Price at evaluation
         return_bps: i32,         // Actual return in basis points
+        price_feed: Pubkey,      // Price feed account the oracle read
+        publish_slot: u64,       // Slot the price update was published at
+        publish_time: i64,       // Unix timestamp of the price update
+        update_hash: [u8; 32],   // SHA256 of the raw price update
+        benchmark_return_bps: i32, // Benchmark return over the window (0 if no benchmark)
+        adverse_extreme_cents: u64, // Lowest (BUY) / highest (SELL) price in the window (laddered signals, else 0)
+        window_high_cents: u64,  // Oracle high over the signal's window (0 if unavailable)
+        window_low_cents: u64,   // Oracle low over the signal's window (0 if unavailable)
     ) -> Result<()> {
+        #[cfg(feature = "debug-invariants")]
+        let counters = invariants::ProviderCounters::of(&ctx.accounts.provider);
         let commit = &mut ctx.accounts.signal_commit;
         let provider = &mut ctx.accounts.provider;
         let clock = Clock::get()?;
         
         require!(commit.revealed, AgentAlphaError::NotRevealed);
         require!(!commit.outcome_recorded, AgentAlphaError::OutcomeAlreadyRecorded);
-        require!(outcome >= 1 && outcome <= 3, AgentAlphaError::InvalidOutcome);
+        require!(
+            publish_slot <= clock.slot && publish_time <= clock.unix_timestamp,
+            AgentAlphaError::InvalidSettlementProof
+        );
+        require!(
+            commit.benchmark != BENCH_NONE || benchmark_return_bps == 0,
+            AgentAlphaError::InvalidBenchmark
+        );
         
-        // Determine if correct based on outcome
-        // TP_HIT (1) = correct, SL_HIT (2) = wrong, EXPIRED (3) = based on return
-        let was_correct = match outcome {
-            1 => true,   // TP hit = correct
-            2 => false,  // SL hit = wrong
-            3 => return_bps > 0,  // Expired = correct if profitable
-            _ => false,
-        };
+        let settlement = commit.resolve_settlement(
+            outcome,
+            final_price_cents,
+            return_bps,
+            adverse_extreme_cents,
+            window_high_cents,
+            window_low_cents,
+            &ctx.accounts.config,
+        )?;
+        let (outcome, was_correct, return_bps, both_touched) =
+            (settlement.outcome, settlement.was_correct, settlement.return_bps, settlement.both_touched);
         
-        commit.outcome_recorded = true;
-        commit.outcome = outcome;
-        commit.final_price_cents = final_price_cents;
-        commit.was_correct = was_correct;
-        commit.return_bps = return_bps;
-        commit.evaluated_at = clock.unix_timestamp;
+        commit.apply_settlement(&settlement, final_price_cents, benchmark_return_bps, clock.unix_timestamp);
+        commit.settled_by = ctx.accounts.oracle.key();
+        commit.price_feed = price_feed;
+        commit.publish_slot = publish_slot;
+        commit.publish_time = publish_time;
+        commit.update_hash = update_hash;
         
-        // Update provider reputation
-        provider.total_signals += 1;
-        if was_correct {
-            provider.correct_signals += 1;
+        // A tripped circuit breaker holds reputation updates back until a guardian resets it
+        let config = &mut ctx.accounts.config;
+        if config.observe_settlement(commit.return_bps, clock.unix_timestamp) {
+            emit!(CircuitBreakerTripped {
+                event_seq: event_seq(),
+                window_settlements: config.breaker_window_settlements,
+                window_losses: config.breaker_window_losses,
+                tripped_at: config.breaker_tripped_at,
+            });
+        }
+        if config.breaker_tripped_at != 0 {
+            commit.reputation_pending = true;
+        } else {
+            apply_reputation(
+                provider,
+                ctx.accounts.provider_curve.as_mut(),
+                commit,
+                config.min_reputation_confidence,
+                clock.unix_timestamp,
+            );
+        }
+        
+        if let Some(stats) = ctx.accounts.token_stats.as_mut() {
+            if stats.record_settlement(commit, clock.unix_timestamp) {
+                emit!(TokenStatsUpdated {
+                    event_seq: event_seq(),
+                    token: stats.token.clone(),
+                    active_signals: stats.active_signals,
+                    direction_skew_bps: stats.direction_skew_bps(),
+                    avg_confidence: stats.avg_confidence(),
+                });
+            }
+        }
+        if let Some(consensus) = ctx.accounts.token_consensus.as_mut() {
+            if consensus.record_settlement(commit, clock.unix_timestamp) {
+                emit!(TokenConsensusUpdated {
+                    event_seq: event_seq(),
+                    token: consensus.token.clone(),
+                    open_signals: consensus.open_signals,
+                    consensus_bps: consensus.consensus_bps(),
+                });
+            }
         }
-        provider.total_return_bps += return_bps as i64;
-        provider.updated_at = clock.unix_timestamp;
+        
+        let deposit = std::mem::take(&mut commit.settlement_deposit);
+        let settlement_reward = pay_keeper_reward(
+            &commit.to_account_info(),
+            &ctx.accounts.config.to_account_info(),
+            &ctx.accounts.oracle.to_account_info(),
+            deposit,
+            ctx.accounts.config.settle_reward_lamports,
+            true,
+        )?;
         
         emit!(OutcomeRecorded {
+            event_seq: event_seq(),
             provider: provider.key(),
             signal_hash: commit.signal_hash,
             outcome,
             was_correct,
             return_bps,
+            tp_progress_bps: commit.tp_progress_bps,
+            benchmark_return_bps,
+            both_touched,
+            vol_regime: commit.vol_regime,
+            experimental: commit.experimental,
             total_signals: provider.total_signals,
             correct_signals: provider.correct_signals,
+            settled_by: commit.settled_by,
+            settlement_reward,
+            price_feed,
+            publish_slot,
+            publish_time,
+            update_hash,
+        });
+        
+        #[cfg(feature = "debug-invariants")]
+        counters.check_monotonic(&ctx.accounts.provider)?;
+        check_invariants!(ctx.accounts.signal_commit, ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Post an interim mark-to-market checkpoint for an open signal (Config.settlement_oracle only)
+    /// Marks must move forward in time; the unrealized return is measured from the stated entry
+    pub fn post_mark(
+        ctx: Context<PostMark>,
+        mark_price_cents: u64,
+        publish_time: i64,       // Unix timestamp of the price update
+    ) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        let clock = Clock::get()?;
+        
+        require!(commit.revealed, AgentAlphaError::NotRevealed);
+        require!(!commit.outcome_recorded, AgentAlphaError::OutcomeAlreadyRecorded);
+        require!(mark_price_cents > 0, AgentAlphaError::InvalidMark);
+        require!(
+            publish_time > commit.last_mark_at && publish_time <= clock.unix_timestamp,
+            AgentAlphaError::InvalidMark
+        );
+        
+        let unrealized_return_bps = commit.return_from_entry_bps(commit.entry_cents, mark_price_cents);
+        commit.last_mark_cents = mark_price_cents;
+        commit.last_mark_return_bps = unrealized_return_bps;
+        commit.last_mark_at = publish_time;
+        commit.mark_count = commit.mark_count.saturating_add(1);
+        
+        emit!(MarkPosted {
+            event_seq: event_seq(),
+            provider: commit.provider,
+            signal_hash: commit.signal_hash,
+            mark_price_cents,
+            unrealized_return_bps,
+            publish_time,
+            posted_by: ctx.accounts.oracle.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Commit the Merkle root of a signal's chunked content attachment (once; see content.rs)
+    pub fn set_content_root(
+        ctx: Context<SetContentRoot>,
+        content_root: [u8; 32],
+        chunk_count: u32,
+    ) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        
+        require!(commit.content_chunks == 0, AgentAlphaError::ContentAlreadyCommitted);
+        require!(chunk_count > 0, AgentAlphaError::InvalidContentCommitment);
+        commit.content_root = content_root;
+        commit.content_chunks = chunk_count;
+        
+        emit!(ContentCommitted {
+            event_seq: event_seq(),
+            provider: commit.provider,
+            signal_hash: commit.signal_hash,
+            content_root,
+            chunk_count,
+        });
+        
+        Ok(())
+    }
+
+    /// Report the buyer's realized fill price for a revealed signal it bought (once per purchase)
+    /// Slippage against the stated entry is aggregated on the provider
+    pub fn report_fill(
+        ctx: Context<ReportFill>,
+        fill_price_cents: u64,
+    ) -> Result<()> {
+        let commit = &ctx.accounts.signal_commit;
+        let provider = &mut ctx.accounts.provider;
+        let report = &mut ctx.accounts.fill_report;
+        let clock = Clock::get()?;
+        
+        require!(commit.revealed, AgentAlphaError::NotRevealed);
+        require!(commit.entry_cents > 0 && fill_price_cents > 0, AgentAlphaError::InvalidFillPrice);
+        
+        let slippage_bps = commit.slippage_bps(fill_price_cents);
+        
+        report.signal = commit.key();
+        report.reporter = ctx.accounts.reporter.key();
+        report.fill_price_cents = fill_price_cents;
+        report.slippage_bps = slippage_bps;
+        report.reported_at = clock.unix_timestamp;
+        report.bump = ctx.bumps.fill_report;
+        
+        // An execution record already counted this reporter's entry slippage
+        if ctx.accounts.execution_record.data_is_empty() {
+            provider.fill_reports += 1;
+            provider.total_slippage_bps += slippage_bps as i64;
+        }
+        
+        emit!(FillReported {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            signal_hash: commit.signal_hash,
+            reporter: report.reporter,
+            fill_price_cents,
+            slippage_bps,
+        });
+        
+        Ok(())
+    }
+
+    /// Create a buyer's notice mailbox
+    pub fn init_mailbox(ctx: Context<InitMailbox>) -> Result<()> {
+        let mailbox = &mut ctx.accounts.mailbox;
+        
+        mailbox.owner = ctx.accounts.owner.key();
+        mailbox.next_seq = 0;
+        mailbox.acked_seq = 0;
+        mailbox.dropped = 0;
+        mailbox.notices = [Notice::default(); MAILBOX_SLOTS];
+        mailbox.bump = ctx.bumps.mailbox;
+        
+        Ok(())
+    }
+
+    /// Push a notice about one of the provider's signals into a buyer's mailbox
+    /// Only for buyers who bought the signal or hold an active subscription to the provider, so
+    /// providers can't flood other mailboxes and evict the notices buyers want
+    pub fn post_notice(
+        ctx: Context<PostNotice>,
+        kind: u8,                // 1=NEW_SIGNAL, 2=OUTCOME
+    ) -> Result<()> {
+        let commit = &ctx.accounts.signal_commit;
+        let mailbox = &mut ctx.accounts.mailbox;
+        let clock = Clock::get()?;
+        
+        let subscribed = ctx
+            .accounts
+            .subscription
+            .as_ref()
+            .is_some_and(|s| s.covers(&ctx.accounts.provider, TIER_UNLIMITED, clock.unix_timestamp));
+        require!(
+            ctx.accounts.signal_access.is_some() || subscribed,
+            AgentAlphaError::NoticeRecipientNotBuyer
+        );
+        
+        match kind {
+            NOTICE_NEW_SIGNAL => {}
+            NOTICE_OUTCOME => require!(commit.outcome_recorded, AgentAlphaError::OutcomeNotRecorded),
+            _ => return err!(AgentAlphaError::InvalidNoticeKind),
+        }
+        
+        let seq = mailbox.push(Notice {
+            kind,
+            signal: commit.key(),
+            posted_at: clock.unix_timestamp,
+        });
+        
+        emit!(NoticePosted {
+            event_seq: event_seq(),
+            mailbox: mailbox.key(),
+            seq,
+            kind,
+            signal: commit.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Acknowledge drained notices up to (excluding) `up_to_seq`
+    pub fn ack_notices(
+        ctx: Context<AckNotices>,
+        up_to_seq: u64,
+    ) -> Result<()> {
+        let mailbox = &mut ctx.accounts.mailbox;
+        
+        require!(
+            up_to_seq >= mailbox.acked_seq && up_to_seq <= mailbox.next_seq,
+            AgentAlphaError::InvalidAckSeq
+        );
+        mailbox.acked_seq = up_to_seq;
+        
+        Ok(())
+    }
+
+    /// Expire a commit that was never revealed (permissionless)
+    /// Frees the provider's open-signal slot once the reveal window has passed
+    pub fn expire_signal(ctx: Context<ExpireSignal>) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(!commit.revealed, AgentAlphaError::AlreadyRevealed);
+        require!(!commit.expired, AgentAlphaError::SignalExpired);
+        require!(
+            clock.unix_timestamp > commit.committed_at + MAX_TIMEFRAME_SECS,
+            AgentAlphaError::RevealWindowOpen
+        );
+        
+        commit.expired = true;
+        provider.open_signals = provider.open_signals.saturating_sub(1);
+        
+        let deposit = std::mem::take(&mut commit.settlement_deposit);
+        let settlement_reward = pay_keeper_reward(
+            &commit.to_account_info(),
+            &ctx.accounts.config.to_account_info(),
+            &ctx.accounts.keeper.to_account_info(),
+            deposit,
+            ctx.accounts.config.expire_reward_lamports,
+            ctx.accounts.config.is_trusted_keeper(&ctx.accounts.keeper.key()),
+        )?;
+        
+        emit!(SignalExpired {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            signal_hash: commit.signal_hash,
+            expired_by: ctx.accounts.keeper.key(),
+            settlement_reward,
+        });
+        
+        check_invariants!(ctx.accounts.signal_commit, ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Create the protocol config (only the program's upgrade authority can do this)
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        
+        config.admin = ctx.accounts.admin.key();
+        config.attestation_authority = Pubkey::default();
+        config.price_oracle = cluster::DEFAULT_PRICE_ORACLE;
+        config.settlement_oracle = cluster::DEFAULT_PRICE_ORACLE;
+        config.keeper = Pubkey::default();
+        config.settle_reward_lamports = cluster::DEFAULT_SETTLE_REWARD_LAMPORTS;
+        config.expire_reward_lamports = cluster::DEFAULT_EXPIRE_REWARD_LAMPORTS;
+        config.both_touched_precedence = PRECEDENCE_WORST_CASE;
+        config.hash_schema_disabled_at = [0; MAX_HASH_SCHEMAS];
+        config.hash_schema_disabled_at[(HASH_SCHEMA_LEGACY - 1) as usize] = LEGACY_HASH_SCHEMA_CUTOFF;
+        config.min_reputation_confidence = 0;
+        config.vesting_bps = 0;
+        config.vesting_period_secs = 0;
+        config.guardian = config.admin;
+        config.breaker_max_settlements = 0;
+        config.breaker_max_loss_bps = 0;
+        config.breaker_window_start = 0;
+        config.breaker_window_settlements = 0;
+        config.breaker_window_losses = 0;
+        config.breaker_tripped_at = 0;
+        config.audit_oracle = Pubkey::default();
+        config.audit_rate_bps = 0;
+        config.audit_epoch = 0;
+        config.audit_seed = [0u8; 32];
+        config.baseline_provider = Pubkey::default();
+        config.bump = ctx.bumps.config;
+        
+        Ok(())
+    }
+
+    /// Designate who may verify provider TEE attestations (admin only)
+    pub fn set_attestation_authority(
+        ctx: Context<AdminOnly>,
+        attestation_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.attestation_authority = attestation_authority;
+        Ok(())
+    }
+
+    /// Set the rewards paid to whoever settles or expires a signal (admin only)
+    pub fn set_keeper_rewards(
+        ctx: Context<AdminOnly>,
+        settle_reward_lamports: u64,
+        expire_reward_lamports: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.settle_reward_lamports = settle_reward_lamports;
+        config.expire_reward_lamports = expire_reward_lamports;
+        
+        emit!(KeeperRewardsChanged {
+            event_seq: event_seq(),
+            settle_reward_lamports,
+            expire_reward_lamports,
+        });
+        
+        Ok(())
+    }
+
+    /// Designate who may post token USD rates (admin only)
+    pub fn set_price_oracle(
+        ctx: Context<AdminOnly>,
+        price_oracle: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.price_oracle = price_oracle;
+        Ok(())
+    }
+
+    /// Designate who may record outcomes and post marks (admin only)
+    /// Guarantee refunds and performance fees are released by the outcome it records
+    pub fn set_settlement_oracle(
+        ctx: Context<AdminOnly>,
+        settlement_oracle: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.settlement_oracle = settlement_oracle;
+        Ok(())
+    }
+
+    /// Designate the keeper whose rewards may be topped up from the config reserve (admin only)
+    /// Other keepers are paid from the signal's settlement deposit alone
+    pub fn set_keeper(
+        ctx: Context<AdminOnly>,
+        keeper: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.keeper = keeper;
+        Ok(())
+    }
+
+    /// Choose how a settlement with TP and SL inside one candle resolves (admin only)
+    pub fn set_both_touched_precedence(
+        ctx: Context<AdminOnly>,
+        precedence: u8,          // 0=WORST_CASE (SL), 1=BEST_CASE (TP), 2=EXPIRE
+    ) -> Result<()> {
+        require!(precedence <= PRECEDENCE_EXPIRE, AgentAlphaError::InvalidPrecedence);
+        ctx.accounts.config.both_touched_precedence = precedence;
+        Ok(())
+    }
+
+    /// Set the confidence a signal needs to count toward headline reputation (admin only)
+    pub fn set_min_reputation_confidence(
+        ctx: Context<AdminOnly>,
+        min_confidence: u8,      // 0-100, 0 = every signal counts
+    ) -> Result<()> {
+        require!(min_confidence <= 100, AgentAlphaError::InvalidConfidence);
+        ctx.accounts.config.min_reputation_confidence = min_confidence;
+        Ok(())
+    }
+
+    /// Hold back part of new providers' SOL proceeds to vest (admin only)
+    /// A provider is new for `period_days` after registering; what is held back vests linearly over the next `period_days`
+    pub fn set_payout_vesting(
+        ctx: Context<AdminOnly>,
+        vesting_bps: u16,        // 0 = off
+        period_days: u16,
+    ) -> Result<()> {
+        require!(vesting_bps <= 10000, AgentAlphaError::InvalidVesting);
+        let config = &mut ctx.accounts.config;
+        config.vesting_bps = vesting_bps;
+        config.vesting_period_secs = period_days as i64 * 86400;
+        Ok(())
+    }
+
+    /// Set the protocol fee taken from every payment and the treasury it is paid to (admin only)
+    /// Covers purchases, auction settlements and subscriptions; token purchases pay it into the
+    /// treasury's token account for the mint
+    pub fn update_config(
+        ctx: Context<AdminOnly>,
+        protocol_fee_bps: u16,   // 0 = off
+        treasury: Pubkey,
+    ) -> Result<()> {
+        require!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, AgentAlphaError::InvalidProtocolFee);
+        require!(
+            protocol_fee_bps == 0 || treasury != Pubkey::default(),
+            AgentAlphaError::MissingTreasuryAccount
+        );
+        let config = &mut ctx.accounts.config;
+        config.protocol_fee_bps = protocol_fee_bps;
+        config.treasury = treasury;
+        
+        emit!(ProtocolFeeChanged {
+            event_seq: event_seq(),
+            protocol_fee_bps,
+            treasury,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the band around entry within which an EXPIRED signal settles as BREAK_EVEN (admin only)
+    pub fn set_break_even_band(
+        ctx: Context<AdminOnly>,
+        break_even_bps: u16,     // 0 = off
+    ) -> Result<()> {
+        require!(break_even_bps <= MAX_BREAK_EVEN_BPS, AgentAlphaError::InvalidBreakEvenBand);
+        ctx.accounts.config.break_even_bps = break_even_bps;
+        Ok(())
+    }
+
+    /// Stop or resume new commits under a hash schema version (admin only)
+    /// Signals already committed under a disabled schema can still be revealed and settled
+    /// LEGACY can be disabled earlier than LEGACY_HASH_SCHEMA_CUTOFF but not re-enabled
+    pub fn set_hash_schema_enabled(
+        ctx: Context<AdminOnly>,
+        schema: u8,
+        enabled: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let clock = Clock::get()?;
+        
+        require!(
+            (1..=MAX_HASH_SCHEMAS as u8).contains(&schema),
+            AgentAlphaError::InvalidHashSchema
+        );
+        require!(!(enabled && schema == HASH_SCHEMA_LEGACY), AgentAlphaError::InvalidHashSchema);
+        let slot = &mut config.hash_schema_disabled_at[(schema - 1) as usize];
+        if !enabled && *slot == 0 {
+            *slot = clock.unix_timestamp;
+        } else if enabled {
+            *slot = 0;
+        }
+        
+        emit!(HashSchemaToggled {
+            event_seq: event_seq(),
+            schema,
+            enabled,
+            disabled_at: *slot,
+        });
+        
+        Ok(())
+    }
+
+    /// Suspend or reinstate a provider (admin only)
+    /// Suspended providers cannot commit signals or take purchases, and their subscribers can take
+    /// back the unearned part of their subscriptions with refund_subscription
+    pub fn set_provider_suspended(
+        ctx: Context<SuspendProvider>,
+        suspended: bool,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        provider.suspended = suspended;
+        provider.suspended_at = if suspended { clock.unix_timestamp } else { 0 };
+        
+        emit!(ProviderSuspensionChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            suspended,
+            at: clock.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Register a token with its price tick size (admin only)
+    pub fn register_token(
+        ctx: Context<RegisterToken>,
+        symbol: String,
+        tick_size_cents: u64,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.token_registry;
+        
+        require!(symbol.len() <= 16, AgentAlphaError::TokenTooLong);
+        require!(tick_size_cents > 0, AgentAlphaError::InvalidTickSize);
+        
+        entry.symbol = symbol;
+        entry.tick_size_cents = tick_size_cents;
+        entry.mint = Pubkey::default();
+        entry.decimals = 0;
+        entry.usd_rate_micros = 0;
+        entry.rate_updated_at = 0;
+        entry.halted_at = 0;
+        entry.bump = ctx.bumps.token_registry;
+        entry.display_decimals = PriceDisplay::DEFAULT.decimals;
+        entry.display_exponent = PriceDisplay::DEFAULT.exponent;
+        entry.display_updated_at = 0;
+        entry.social_settlement = false;
+        
+        emit!(TokenRegistered {
+            event_seq: event_seq(),
+            symbol: entry.symbol.clone(),
+            tick_size_cents,
+        });
+        
+        Ok(())
+    }
+
+    /// Change a registered token's tick size (admin only)
+    pub fn update_token(
+        ctx: Context<UpdateToken>,
+        tick_size_cents: u64,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.token_registry;
+        
+        require!(tick_size_cents > 0, AgentAlphaError::InvalidTickSize);
+        entry.tick_size_cents = tick_size_cents;
+        
+        emit!(TokenRegistered {
+            event_seq: event_seq(),
+            symbol: entry.symbol.clone(),
+            tick_size_cents,
+        });
+        
+        Ok(())
+    }
+
+    /// Set how clients render a registered token's prices (admin only)
+    /// Prices show with `decimals` fractional digits at 10^`exponent`; see price_display
+    pub fn set_token_display(
+        ctx: Context<UpdateToken>,
+        decimals: u8,
+        exponent: i8,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.token_registry;
+        let display = PriceDisplay { decimals, exponent };
+        
+        require!(display.is_valid(), AgentAlphaError::InvalidPriceDisplay);
+        entry.display_decimals = decimals;
+        entry.display_exponent = exponent;
+        entry.display_updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(TokenDisplayUpdated {
+            event_seq: event_seq(),
+            symbol: entry.symbol.clone(),
+            decimals,
+            exponent,
+        });
+        
+        Ok(())
+    }
+
+    /// Mark a registered token as lacking oracle coverage, letting its signals settle by reporter
+    /// panel (admin only)
+    pub fn set_token_social_settlement(
+        ctx: Context<UpdateToken>,
+        enabled: bool,
+    ) -> Result<()> {
+        ctx.accounts.token_registry.social_settlement = enabled;
+        Ok(())
+    }
+
+    /// Escrow a reward for a call on a token within a window
+    pub fn create_bounty(
+        ctx: Context<CreateBounty>,
+        bounty_id: u64,
+        token: String,
+        reward_lamports: u64,
+        duration_secs: i64,      // Window in which entries must be revealed
+        min_confidence: u8,      // Minimum stated confidence for entries
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        
+        require!(token.len() <= 16, AgentAlphaError::TokenTooLong);
+        require!(reward_lamports > 0, AgentAlphaError::InvalidBountyReward);
+        require!(duration_secs > 0, AgentAlphaError::InvalidBountyWindow);
+        require!(min_confidence <= 100, AgentAlphaError::InvalidConfidence);
+        
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.bounty.to_account_info(),
+                },
+            ),
+            reward_lamports,
+        )?;
+        
+        let bounty = &mut ctx.accounts.bounty;
+        bounty.creator = ctx.accounts.creator.key();
+        bounty.bounty_id = bounty_id;
+        bounty.token = token;
+        bounty.reward_lamports = reward_lamports;
+        bounty.min_confidence = min_confidence;
+        bounty.created_at = clock.unix_timestamp;
+        bounty.deadline = clock.unix_timestamp + duration_secs;
+        bounty.entries = 0;
+        bounty.best_signal = None;
+        bounty.best_return_bps = 0;
+        bounty.bump = ctx.bumps.bounty;
+        
+        emit!(BountyCreated {
+            event_seq: event_seq(),
+            bounty: bounty.key(),
+            creator: bounty.creator,
+            token: bounty.token.clone(),
+            reward_lamports,
+            deadline: bounty.deadline,
+        });
+        
+        Ok(())
+    }
+
+    /// Enter a revealed, unsettled signal into a bounty, before its deadline
+    /// The signal must have been committed while the bounty was open, so providers can't pick
+    /// which of their older calls to enter after seeing how they went
+    pub fn enter_bounty(ctx: Context<EnterBounty>) -> Result<()> {
+        let bounty = &mut ctx.accounts.bounty;
+        let commit = &mut ctx.accounts.signal_commit;
+        let clock = Clock::get()?;
+        
+        require!(clock.unix_timestamp <= bounty.deadline, AgentAlphaError::BountyClosed);
+        require!(commit.revealed, AgentAlphaError::NotRevealed);
+        require!(!commit.outcome_recorded, AgentAlphaError::OutcomeAlreadyRecorded);
+        require!(commit.bounty.is_none(), AgentAlphaError::AlreadyInBounty);
+        require!(
+            commit.token == bounty.token
+                && commit.confidence >= bounty.min_confidence
+                && commit.committed_at >= bounty.created_at
+                && commit.committed_at < bounty.deadline
+                && commit.revealed_at <= bounty.deadline,
+            AgentAlphaError::BountyEntryMismatch
+        );
+        
+        commit.bounty = Some(bounty.key());
+        bounty.entries += 1;
+        
+        emit!(BountyEntered {
+            event_seq: event_seq(),
+            bounty: bounty.key(),
+            provider: commit.provider,
+            signal_hash: commit.signal_hash,
         });
         
         Ok(())
     }
+
+    /// Score a settled bounty entry (permissionless), until the bounty's scoring window ends
+    /// The best correct return becomes the bounty's winner
+    pub fn score_bounty_entry(ctx: Context<ScoreBountyEntry>) -> Result<()> {
+        let bounty = &mut ctx.accounts.bounty;
+        let commit = &ctx.accounts.signal_commit;
+        let clock = Clock::get()?;
+        
+        require!(clock.unix_timestamp <= bounty.claimable_at(), AgentAlphaError::BountyScoringClosed);
+        require!(commit.bounty == Some(bounty.key()), AgentAlphaError::BountyEntryMismatch);
+        require!(commit.outcome_recorded, AgentAlphaError::OutcomeNotRecorded);
+        
+        if commit.was_correct && (bounty.best_signal.is_none() || commit.return_bps > bounty.best_return_bps) {
+            bounty.best_signal = Some(commit.key());
+            bounty.best_return_bps = commit.return_bps;
+        } else {
+            emit!(BusinessWarning {
+                event_seq: event_seq(),
+                code: WARN_BOUNTY_ENTRY_NOT_BEST,
+                subject: bounty.key(),
+                actor: commit.key(),
+            });
+        }
+        
+        Ok(())
+    }
+
+    /// Winning provider claims the reward once all entries could have settled
+    pub fn claim_bounty(ctx: Context<ClaimBounty>) -> Result<()> {
+        let bounty = &ctx.accounts.bounty;
+        let clock = Clock::get()?;
+        
+        require!(
+            clock.unix_timestamp > bounty.claimable_at(),
+            AgentAlphaError::BountyStillOpen
+        );
+        require!(
+            bounty.best_signal == Some(ctx.accounts.signal_commit.key()),
+            AgentAlphaError::NotBountyWinner
+        );
+        
+        let reward = bounty.reward_lamports;
+        **ctx.accounts.bounty.to_account_info().try_borrow_mut_lamports()? -= reward;
+        **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += reward;
+        
+        emit!(BountyClaimed {
+            event_seq: event_seq(),
+            bounty: ctx.accounts.bounty.key(),
+            winner: ctx.accounts.provider.key(),
+            reward_lamports: reward,
+        });
+        
+        Ok(())
+    }
+
+    /// Creator reclaims a bounty that ended without a winner
+    pub fn close_bounty(ctx: Context<CloseBounty>) -> Result<()> {
+        let bounty = &ctx.accounts.bounty;
+        let clock = Clock::get()?;
+        
+        require!(
+            clock.unix_timestamp > bounty.claimable_at(),
+            AgentAlphaError::BountyStillOpen
+        );
+        require!(bounty.best_signal.is_none(), AgentAlphaError::BountyHasWinner);
+        
+        Ok(())
+    }
+
+    /// Create a curated bundle over several providers
+    /// Each member's split_bps is its share of bundle revenue; the rest goes to the curator
+    pub fn create_bundle(
+        ctx: Context<CreateBundle>,
+        bundle_id: u64,
+        name: String,
+        price_lamports: u64,
+        members: Vec<BundleMember>,
+    ) -> Result<()> {
+        let bundle = &mut ctx.accounts.bundle;
+        let clock = Clock::get()?;
+        
+        require!(name.len() <= 64, AgentAlphaError::NameTooLong);
+        require!(
+            !members.is_empty() && members.len() <= MAX_BUNDLE_MEMBERS,
+            AgentAlphaError::InvalidBundleMembers
+        );
+        let total_split: u32 = members.iter().map(|m| m.split_bps as u32).sum();
+        require!(total_split <= 10000, AgentAlphaError::InvalidBundleMembers);
+        
+        bundle.curator = ctx.accounts.curator.key();
+        bundle.bundle_id = bundle_id;
+        bundle.name = name;
+        bundle.price_lamports = price_lamports;
+        bundle.members = members;
+        bundle.total_signals = 0;
+        bundle.correct_signals = 0;
+        bundle.total_return_bps = 0;
+        bundle.created_at = clock.unix_timestamp;
+        bundle.bump = ctx.bumps.bundle;
+        
+        emit!(BundleCreated {
+            event_seq: event_seq(),
+            bundle: bundle.key(),
+            curator: bundle.curator,
+            name: bundle.name.clone(),
+            price_lamports,
+            member_count: bundle.members.len() as u8,
+        });
+        
+        Ok(())
+    }
+
+    /// Count a member's settled signal toward the bundle's reputation (permissionless)
+    /// Only signals committed after the bundle was created count, and each only once
+    pub fn tally_bundle_outcome(ctx: Context<TallyBundleOutcome>) -> Result<()> {
+        let bundle = &mut ctx.accounts.bundle;
+        let commit = &ctx.accounts.signal_commit;
+        let tally = &mut ctx.accounts.tally;
+        
+        require!(
+            bundle.members.iter().any(|m| m.provider == commit.provider),
+            AgentAlphaError::NotBundleMember
+        );
+        require!(commit.outcome_recorded, AgentAlphaError::OutcomeNotRecorded);
+        require!(
+            !commit.experimental
+                && commit.outcome != OUTCOME_VOIDED
+                && !commit.has_neutral_outcome()
+                && commit.committed_at >= bundle.created_at,
+            AgentAlphaError::BundleSignalIneligible
+        );
+        
+        tally.bump = ctx.bumps.tally;
+        bundle.total_signals += 1;
+        if commit.was_correct {
+            bundle.correct_signals += 1;
+        }
+        bundle.total_return_bps += commit.return_bps as i64;
+        
+        emit!(BundleOutcomeTallied {
+            event_seq: event_seq(),
+            bundle: bundle.key(),
+            provider: commit.provider,
+            signal_hash: commit.signal_hash,
+            was_correct: commit.was_correct,
+            return_bps: commit.return_bps,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the share of each sale paid to bonded curators
+    pub fn set_curator_share(
+        ctx: Context<UpdateProvider>,
+        curator_share_bps: u16,
+    ) -> Result<()> {
+        require!(curator_share_bps <= MAX_CURATOR_SHARE_BPS, AgentAlphaError::InvalidCuratorShare);
+        
+        let provider = &mut ctx.accounts.provider;
+        provider.curator_share_bps = curator_share_bps;
+        provider.updated_at = Clock::get()?.unix_timestamp;
+        
+        Ok(())
+    }
+
+    /// Bond lamports behind a provider to vouch for it and earn a share of its sales
+    pub fn bond_provider(
+        ctx: Context<BondProvider>,
+        amount: u64,
+    ) -> Result<()> {
+        let provider = &ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(amount > 0, AgentAlphaError::InvalidBondAmount);
+        require!(!provider.fraud_proven && !provider.suspended, AgentAlphaError::ProviderSuspended);
+        
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.curator.to_account_info(),
+                    to: ctx.accounts.curator_bond.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let provider = &mut ctx.accounts.provider;
+        provider.total_bonded += amount;
+        
+        let bond = &mut ctx.accounts.curator_bond;
+        bond.curator = ctx.accounts.curator.key();
+        bond.provider = provider.key();
+        bond.amount = amount;
+        bond.reward_index = provider.curator_reward_index;
+        bond.unbond_at = 0;
+        bond.bonded_at = clock.unix_timestamp;
+        bond.bump = ctx.bumps.curator_bond;
+        
+        emit!(CuratorBonded {
+            event_seq: event_seq(),
+            provider: bond.provider,
+            curator: bond.curator,
+            amount,
+            total_bonded: provider.total_bonded,
+        });
+        
+        Ok(())
+    }
+
+    /// Pay a curator the sale share accrued on their bond
+    pub fn claim_curator_rewards(ctx: Context<CuratorBondAction>) -> Result<()> {
+        let provider = &ctx.accounts.provider;
+        let bond = &mut ctx.accounts.curator_bond;
+        
+        let rewards = bond.pending_rewards(provider.curator_reward_index);
+        bond.reward_index = provider.curator_reward_index;
+        if rewards > 0 {
+            transfer_from_program_account(
+                &ctx.accounts.provider.to_account_info(),
+                &ctx.accounts.curator.to_account_info(),
+                rewards,
+            )?;
+        }
+        
+        emit!(CuratorRewardsClaimed {
+            event_seq: event_seq(),
+            provider: bond.provider,
+            curator: bond.curator,
+            amount: rewards,
+        });
+        
+        Ok(())
+    }
+
+    /// Stop earning and start the unbonding delay; the bond stays slashable until withdrawn
+    pub fn begin_unbond(ctx: Context<CuratorBondAction>) -> Result<()> {
+        let clock = Clock::get()?;
+        let provider = &mut ctx.accounts.provider;
+        let bond = &mut ctx.accounts.curator_bond;
+        
+        require!(bond.unbond_at == 0, AgentAlphaError::AlreadyUnbonding);
+        
+        let rewards = bond.pending_rewards(provider.curator_reward_index);
+        bond.reward_index = provider.curator_reward_index;
+        bond.unbond_at = clock.unix_timestamp + UNBOND_DELAY_SECS;
+        provider.total_bonded -= bond.amount;
+        if rewards > 0 {
+            transfer_from_program_account(
+                &ctx.accounts.provider.to_account_info(),
+                &ctx.accounts.curator.to_account_info(),
+                rewards,
+            )?;
+        }
+        
+        emit!(CuratorUnbondStarted {
+            event_seq: event_seq(),
+            provider: bond.provider,
+            curator: bond.curator,
+            amount: bond.amount,
+            unbond_at: bond.unbond_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw an unbonded stake after the delay, closing the bond
+    pub fn withdraw_bond(ctx: Context<WithdrawBond>) -> Result<()> {
+        let clock = Clock::get()?;
+        let bond = &ctx.accounts.curator_bond;
+        
+        require!(
+            bond.unbond_at != 0 && clock.unix_timestamp >= bond.unbond_at,
+            AgentAlphaError::UnbondNotReady
+        );
+        require!(!ctx.accounts.provider.fraud_proven, AgentAlphaError::ProviderFraudProven);
+        
+        Ok(())
+    }
+
+    /// Mark a provider as proven fraudulent, suspending it and exposing its curators' bonds (admin only)
+    pub fn mark_provider_fraudulent(ctx: Context<SuspendProvider>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        provider.fraud_proven = true;
+        provider.suspended = true;
+        provider.suspended_at = clock.unix_timestamp;
+        
+        emit!(ProviderSuspensionChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            suspended: true,
+            at: clock.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Slash a curator's bond behind a fraudulent provider into the protocol config account (admin only)
+    pub fn slash_bond(ctx: Context<SlashBond>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let bond = &ctx.accounts.curator_bond;
+        
+        require!(provider.fraud_proven, AgentAlphaError::ProviderNotFraudulent);
+        if bond.unbond_at == 0 {
+            provider.total_bonded -= bond.amount;
+        }
+        
+        emit!(CuratorSlashed {
+            event_seq: event_seq(),
+            provider: bond.provider,
+            curator: bond.curator,
+            amount: bond.amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Propose a change to a governed Config parameter, backed by one of the proposer's curator bonds
+    /// Voting runs GOVERNANCE_VOTING_SECS; `value` carries numeric parameters, `key_value` oracle keys
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        id: u64,
+        param: u8,
+        value: u64,
+        key_value: Pubkey,
+    ) -> Result<()> {
+        let bond = &ctx.accounts.curator_bond;
+        let clock = Clock::get()?;
+        
+        require!(
+            bond.voting_weight(&ctx.accounts.provider) >= GOVERNANCE_MIN_PROPOSAL_LAMPORTS,
+            AgentAlphaError::InsufficientProposalWeight
+        );
+        
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.proposer = ctx.accounts.curator.key();
+        proposal.id = id;
+        proposal.param = param;
+        proposal.value = value;
+        proposal.key_value = key_value;
+        proposal.created_at = clock.unix_timestamp;
+        proposal.voting_ends_at = clock.unix_timestamp + GOVERNANCE_VOTING_SECS;
+        proposal.yes_weight = 0;
+        proposal.no_weight = 0;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+        proposal.validate(&ctx.accounts.config)?;
+        
+        emit!(ProposalCreated {
+            event_seq: event_seq(),
+            proposal: proposal.key(),
+            proposer: proposal.proposer,
+            param,
+            value,
+            key_value,
+            voting_ends_at: proposal.voting_ends_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Vote on an open proposal with a curator bond, weighted by its lamports
+    /// Only bonds made before the proposal and not unbonding count, and each bond votes once;
+    /// the unbonding delay outlasts the vote, so counted lamports stay locked through it
+    pub fn cast_vote(
+        ctx: Context<CastVote>,
+        approve: bool,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let bond = &ctx.accounts.curator_bond;
+        let clock = Clock::get()?;
+        
+        require!(clock.unix_timestamp < proposal.voting_ends_at, AgentAlphaError::VotingClosed);
+        require!(bond.bonded_at < proposal.created_at, AgentAlphaError::BondNotEligibleToVote);
+        let weight = bond.voting_weight(&ctx.accounts.provider);
+        require!(weight > 0, AgentAlphaError::BondNotEligibleToVote);
+        
+        if approve {
+            proposal.yes_weight += weight;
+        } else {
+            proposal.no_weight += weight;
+        }
+        let vote = &mut ctx.accounts.vote;
+        vote.proposal = proposal.key();
+        vote.curator_bond = bond.key();
+        vote.weight = weight;
+        vote.approve = approve;
+        vote.bump = ctx.bumps.vote;
+        
+        emit!(VoteCast {
+            event_seq: event_seq(),
+            proposal: proposal.key(),
+            curator: bond.curator,
+            curator_bond: bond.key(),
+            approve,
+            weight,
+            yes_weight: proposal.yes_weight,
+            no_weight: proposal.no_weight,
+        });
+        
+        Ok(())
+    }
+
+    /// Apply a passed proposal to Config once its timelock has run (permissionless)
+    /// A proposal passes with more yes than no weight and at least GOVERNANCE_QUORUM_LAMPORTS cast
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let config = &mut ctx.accounts.config;
+        let clock = Clock::get()?;
+        
+        require!(!proposal.executed, AgentAlphaError::ProposalAlreadyExecuted);
+        require!(clock.unix_timestamp >= proposal.voting_ends_at, AgentAlphaError::VotingOpen);
+        require!(proposal.passed(), AgentAlphaError::ProposalNotPassed);
+        require!(
+            clock.unix_timestamp >= proposal.voting_ends_at + GOVERNANCE_TIMELOCK_SECS,
+            AgentAlphaError::ProposalTimelocked
+        );
+        
+        proposal.validate(config)?;
+        proposal.apply(config);
+        proposal.executed = true;
+        
+        emit!(ProposalExecuted {
+            event_seq: event_seq(),
+            proposal: proposal.key(),
+            param: proposal.param,
+            value: proposal.value,
+            key_value: proposal.key_value,
+            yes_weight: proposal.yes_weight,
+            no_weight: proposal.no_weight,
+        });
+        
+        Ok(())
+    }
+
+    /// Record when the buyer actually received a purchased signal's content off-chain
+    /// Latency from purchase feeds the provider's delivery-latency histogram
+    pub fn ack_delivery(
+        ctx: Context<AckDelivery>,
+        received_at: i64,
+    ) -> Result<()> {
+        let access = &mut ctx.accounts.signal_access;
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(access.delivered_at == 0, AgentAlphaError::DeliveryAlreadyAcked);
+        require!(
+            received_at >= access.purchased_at && received_at <= clock.unix_timestamp,
+            AgentAlphaError::InvalidDeliveryTime
+        );
+        
+        let latency_secs = received_at - access.purchased_at;
+        access.delivered_at = received_at;
+        provider.record_delivery_latency(latency_secs);
+        
+        emit!(DeliveryAcked {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            signal: access.signal,
+            buyer: access.buyer,
+            latency_secs,
+        });
+        
+        // Delivery confirmed: the escrowed payment goes to the provider, unless it is guaranteed
+        // against or priced by the outcome (claim_payout or settle_performance_fee releases it then)
+        if access.escrow_held_for_outcome() {
+            return Ok(());
+        }
+        release_escrow(access, &ctx.accounts.provider_authority)?;
+        check_invariants!(ctx.accounts.signal_access);
+        
+        Ok(())
+    }
+
+    /// Pay a purchase's escrow to the provider once PURCHASE_ESCROW_SECS have passed without
+    /// an ack (anyone can crank; voided signals are refunded instead)
+    pub fn release_purchase_escrow(ctx: Context<ReleasePurchaseEscrow>) -> Result<()> {
+        let access = &mut ctx.accounts.signal_access;
+        let clock = Clock::get()?;
+        
+        require!(access.escrow_lamports > 0, AgentAlphaError::NothingEscrowed);
+        require!(
+            clock.unix_timestamp >= access.purchased_at + PURCHASE_ESCROW_SECS,
+            AgentAlphaError::EscrowWindowActive
+        );
+        require!(ctx.accounts.signal_commit.outcome != OUTCOME_VOIDED, AgentAlphaError::SignalVoided);
+        require!(!access.escrow_held_for_outcome(), AgentAlphaError::EscrowHeldForOutcome);
+        
+        release_escrow(access, &ctx.accounts.provider_authority)?;
+        check_invariants!(ctx.accounts.signal_access);
+        
+        Ok(())
+    }
+
+    /// Pay the provider its share of a guaranteed purchase once the signal has an outcome
+    /// (anyone can crank; the buyer's refund share stays in the access record for claim_refund)
+    pub fn claim_payout(ctx: Context<ReleasePurchaseEscrow>) -> Result<()> {
+        let access = &mut ctx.accounts.signal_access;
+        
+        require!(access.notional_lamports == 0, AgentAlphaError::PerformanceFeePurchase);
+        access.split_guaranteed_escrow(&ctx.accounts.signal_commit)?;
+        require!(access.escrow_lamports > 0, AgentAlphaError::NothingEscrowed);
+        
+        release_escrow(access, &ctx.accounts.provider_authority)?;
+        check_invariants!(ctx.accounts.signal_access);
+        
+        Ok(())
+    }
+
+    /// Claim the money-back share of a guaranteed purchase whose signal hit SL (buyer)
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        let access = &mut ctx.accounts.signal_access;
+        
+        access.split_guaranteed_escrow(&ctx.accounts.signal_commit)?;
+        let lamports = std::mem::take(&mut access.refund_lamports);
+        require!(lamports > 0, AgentAlphaError::NoRefundDue);
+        transfer_from_program_account(&access.to_account_info(), &ctx.accounts.buyer.to_account_info(), lamports)?;
+        
+        emit!(GuaranteeRefunded {
+            event_seq: event_seq(),
+            signal: access.signal,
+            buyer: access.buyer,
+            lamports,
+        });
+        
+        check_invariants!(ctx.accounts.signal_access);
+        
+        Ok(())
+    }
+
+    /// Back new SOL purchases with a money-back guarantee: their escrow is held until the
+    /// outcome, and `refund_bps` of it goes back to the buyer if the signal hits SL (0 = off)
+    pub fn set_money_back_guarantee(
+        ctx: Context<UpdateProvider>,
+        refund_bps: u16,
+    ) -> Result<()> {
+        require!(refund_bps <= 10000, AgentAlphaError::InvalidRefundBps);
+        let provider = &mut ctx.accounts.provider;
+        provider.guarantee_refund_bps = refund_bps;
+        provider.layout_version = PROVIDER_LAYOUT_VERSION;
+        provider.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Restrict purchases to holders of an SPL token or NFT mint (default mint = open to everyone)
+    /// For an NFT, gate on its mint with a minimum of 1
+    pub fn set_access_gate(
+        ctx: Context<UpdateProvider>,
+        gate_mint: Pubkey,
+        min_amount: u64,
+    ) -> Result<()> {
+        require!(
+            gate_mint == Pubkey::default() || min_amount > 0,
+            AgentAlphaError::InvalidAccessGate
+        );
+        let provider = &mut ctx.accounts.provider;
+        provider.gate_mint = gate_mint;
+        provider.gate_min_amount = min_amount;
+        provider.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Accept a creator token as payment, priced at its oracle USD rate (`accept` false = SOL only)
+    /// Creates the provider's vault for the mint on first use; token payments are paid into it
+    pub fn set_payment_mint(
+        ctx: Context<SetProviderMint>,
+        accept: bool,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        provider.payment_mint = if accept { ctx.accounts.mint.key() } else { Pubkey::default() };
+        provider.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Price signals in an SPL mint such as USDC, in its base units (authority)
+    /// Creates the provider's vault for the mint on first use; buyers then pay the token price
+    /// into it through purchase_signal, and the SOL price stays available as a fallback.
+    /// A price of 0 returns the provider to SOL-only pricing.
+    pub fn set_price_mint(
+        ctx: Context<SetProviderMint>,
+        price_token_amount: u64,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        provider.price_mint = if price_token_amount > 0 { ctx.accounts.mint.key() } else { Pubkey::default() };
+        provider.price_token_amount = price_token_amount;
+        provider.updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(PriceMintChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            price_mint: provider.price_mint,
+            price_token_amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw token sale proceeds from one of the provider's price vaults (authority)
+    /// The destination's owner must pass the provider's withdrawal allowlist; with a withdrawal
+    /// delay set the amount is only scheduled, to be paid by complete_token_withdrawal
+    /// Wrapped SOL paid straight into the authority's own account is unwrapped to their wallet
+    /// (the account is closed); a scheduled payout arrives wrapped, as the owner isn't signing then
+    pub fn withdraw_token_earnings(
+        ctx: Context<WithdrawTokenEarnings>,
+        amount: u64,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let destination = &ctx.accounts.destination;
+        
+        require!(!provider.fraud_proven, AgentAlphaError::ProviderFraudProven);
+        require!(
+            provider.allows_withdrawal_to(&destination.owner),
+            AgentAlphaError::WithdrawalDestinationNotAllowed
+        );
+        require!(provider.withdrawal_unlocks_at == 0, AgentAlphaError::WithdrawalPending);
+        require!(amount > 0 && amount <= ctx.accounts.price_vault.amount, AgentAlphaError::InsufficientVaultBalance);
+        
+        if provider.withdrawal_delay_secs > 0 {
+            let provider_key = provider.key();
+            provider.schedule_withdrawal(provider_key, WITHDRAWAL_TOKEN, destination.key(), amount, Clock::get()?.unix_timestamp);
+            return Ok(());
+        }
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.price_vault.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: provider.to_account_info(),
+                },
+                &[&[b"provider", provider.seed_authority.as_ref(), &[provider.bump]]],
+            ),
+            amount,
+        )?;
+        if ctx.accounts.price_vault.mint == token::spl_token::native_mint::ID
+            && destination.owner == ctx.accounts.authority.key()
+        {
+            token::close_account(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::CloseAccount {
+                    account: destination.to_account_info(),
+                    destination: ctx.accounts.authority.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ))?;
+        }
+        
+        emit!(TokenEarningsWithdrawn {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            mint: ctx.accounts.price_vault.mint,
+            destination: destination.key(),
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Link a registered token to its SPL mint (admin only)
+    pub fn set_token_mint(
+        ctx: Context<UpdateToken>,
+        mint: Pubkey,
+        decimals: u8,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.token_registry;
+        entry.mint = mint;
+        entry.decimals = decimals;
+        Ok(())
+    }
+
+    /// Post a token's USD rate (price oracle only)
+    pub fn update_token_rate(
+        ctx: Context<UpdateTokenRate>,
+        usd_rate_micros: u64,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.token_registry;
+        let clock = Clock::get()?;
+        
+        require!(usd_rate_micros > 0, AgentAlphaError::StaleTokenRate);
+        entry.usd_rate_micros = usd_rate_micros;
+        entry.rate_updated_at = clock.unix_timestamp;
+        
+        emit!(TokenRateUpdated {
+            event_seq: event_seq(),
+            symbol: entry.symbol.clone(),
+            usd_rate_micros,
+            updated_at: entry.rate_updated_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Correct a fat-fingered reveal once, within CORRECTION_WINDOW_SECS of revealing
+    /// Only prices can change, and only before anyone has bought or the signal has settled
+    pub fn correct_reveal(
+        ctx: Context<CorrectReveal>,
+        entry_cents: u64,
+        tp_cents: u64,
+        sl_cents: u64,
+    ) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        let clock = Clock::get()?;
+        
+        require!(commit.revealed, AgentAlphaError::NotRevealed);
+        require!(commit.corrected_at == 0, AgentAlphaError::CorrectionNotAllowed);
+        require!(
+            clock.unix_timestamp <= commit.revealed_at + CORRECTION_WINDOW_SECS,
+            AgentAlphaError::CorrectionNotAllowed
+        );
+        require!(
+            commit.buyer_count == 0 && !commit.outcome_recorded && commit.parent_signal.is_none(),
+            AgentAlphaError::CorrectionNotAllowed
+        );
+        require!(
+            commit.entry_ladder.is_empty() || entry_cents == commit.entry_cents,
+            AgentAlphaError::CorrectionNotAllowed
+        );
+        
+        let token_registry = &ctx.accounts.token_registry;
+        if !token_registry.data_is_empty() {
+            let entry = TokenRegistry::try_deserialize(&mut &token_registry.try_borrow_data()?[..])?;
+            require!(
+                entry.is_on_tick(entry_cents) && entry.is_on_tick(tp_cents) && entry.is_on_tick(sl_cents),
+                AgentAlphaError::PriceOffTick
+            );
+        }
+        
+        commit.original_entry_cents = commit.entry_cents;
+        commit.original_tp_cents = commit.tp_cents;
+        commit.original_sl_cents = commit.sl_cents;
+        commit.entry_cents = entry_cents;
+        commit.tp_cents = tp_cents;
+        commit.sl_cents = sl_cents;
+        commit.corrected_at = clock.unix_timestamp;
+        
+        emit!(RevealCorrected {
+            event_seq: event_seq(),
+            provider: commit.provider,
+            signal_hash: commit.signal_hash,
+            original_entry_cents: commit.original_entry_cents,
+            original_tp_cents: commit.original_tp_cents,
+            original_sl_cents: commit.original_sl_cents,
+            entry_cents,
+            tp_cents,
+            sl_cents,
+            corrected_at: commit.corrected_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Declare how much capital and how many followers the strategy can take (0 = undeclared)
+    pub fn set_capacity(
+        ctx: Context<UpdateProvider>,
+        capacity_usd: u64,
+        max_followers: u32,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        provider.capacity_usd = capacity_usd;
+        provider.max_followers = max_followers;
+        provider.updated_at = clock.unix_timestamp;
+        
+        emit!(ProviderCapacityChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            capacity_usd,
+            max_followers,
+            follower_count: provider.follower_count,
+        });
+        
+        Ok(())
+    }
+
+    /// Follow a provider, counting towards its actual follower count
+    pub fn follow_provider(ctx: Context<FollowProvider>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let follow = &mut ctx.accounts.follow;
+        
+        follow.provider = provider.key();
+        follow.follower = ctx.accounts.follower.key();
+        follow.followed_at = Clock::get()?.unix_timestamp;
+        follow.bump = ctx.bumps.follow;
+        provider.follower_count += 1;
+        
+        emit!(FollowerChanged {
+            event_seq: event_seq(),
+            provider: follow.provider,
+            follower: follow.follower,
+            following: true,
+            follower_count: provider.follower_count,
+            max_followers: provider.max_followers,
+        });
+        
+        Ok(())
+    }
+
+    /// Stop following a provider, closing the follow record
+    pub fn unfollow_provider(ctx: Context<UnfollowProvider>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        provider.follower_count = provider.follower_count.saturating_sub(1);
+        
+        emit!(FollowerChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            follower: ctx.accounts.follower.key(),
+            following: false,
+            follower_count: provider.follower_count,
+            max_followers: provider.max_followers,
+        });
+        
+        Ok(())
+    }
+
+    /// Declare a service-level commitment: minimum signals per epoch and maximum commit-to-reveal delay
+    /// The current epoch is closed out under the previous terms first
+    pub fn set_sla(
+        ctx: Context<UpdateProvider>,
+        min_signals: u16,
+        max_reveal_delay_secs: u32,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        let provider_key = provider.key();
+        provider.roll_sla_epoch(provider_key, clock.unix_timestamp);
+        provider.sla_min_signals = min_signals;
+        provider.sla_max_reveal_delay_secs = max_reveal_delay_secs;
+        provider.updated_at = clock.unix_timestamp;
+        
+        emit!(SlaChanged {
+            event_seq: event_seq(),
+            provider: provider_key,
+            min_signals,
+            max_reveal_delay_secs,
+        });
+        
+        Ok(())
+    }
+
+    /// Close out a provider's ended SLA epoch (permissionless, for providers that went quiet)
+    pub fn evaluate_sla(ctx: Context<EvaluateSla>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let provider_key = provider.key();
+        provider.roll_sla_epoch(provider_key, Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+
+    /// Log a buyer's execution of a purchased signal for per-provider performance attribution
+    /// The entry also counts as a fill report unless the buyer already filed one
+    pub fn record_execution(
+        ctx: Context<RecordExecution>,
+        entry_price_cents: u64,
+        exit_price_cents: u64,
+        size_cents: u64,          // Position notional in quote cents
+        pnl_hash: [u8; 32],       // SHA256 of the buyer's off-chain PnL record
+    ) -> Result<()> {
+        let commit = &ctx.accounts.signal_commit;
+        let provider = &mut ctx.accounts.provider;
+        let record = &mut ctx.accounts.execution_record;
+        let clock = Clock::get()?;
+        
+        require!(commit.revealed, AgentAlphaError::NotRevealed);
+        require!(
+            commit.entry_cents > 0 && entry_price_cents > 0 && exit_price_cents > 0 && size_cents > 0,
+            AgentAlphaError::InvalidExecution
+        );
+        
+        let realized_return_bps = commit.return_from_entry_bps(entry_price_cents, exit_price_cents);
+        let realized_pnl_cents = commit.pnl_from_entry_cents(entry_price_cents, exit_price_cents, size_cents);
+        let slippage_bps = commit.slippage_bps(entry_price_cents);
+        
+        record.buyer = ctx.accounts.buyer.key();
+        record.signal = commit.key();
+        record.provider = provider.key();
+        record.entry_price_cents = entry_price_cents;
+        record.exit_price_cents = exit_price_cents;
+        record.size_cents = size_cents;
+        record.realized_return_bps = realized_return_bps;
+        record.realized_pnl_cents = realized_pnl_cents;
+        record.slippage_bps = slippage_bps;
+        record.pnl_hash = pnl_hash;
+        record.recorded_at = clock.unix_timestamp;
+        record.bump = ctx.bumps.execution_record;
+        
+        provider.executions += 1;
+        provider.total_realized_return_bps += realized_return_bps as i64;
+        provider.total_realized_pnl_cents = provider.total_realized_pnl_cents.saturating_add(realized_pnl_cents);
+        if ctx.accounts.fill_report.data_is_empty() {
+            provider.fill_reports += 1;
+            provider.total_slippage_bps += slippage_bps as i64;
+        }
+        
+        emit!(ExecutionRecorded {
+            event_seq: event_seq(),
+            provider: record.provider,
+            signal_hash: commit.signal_hash,
+            buyer: record.buyer,
+            entry_price_cents,
+            exit_price_cents,
+            size_cents,
+            realized_return_bps,
+            realized_pnl_cents,
+            slippage_bps,
+            pnl_hash,
+        });
+        
+        Ok(())
+    }
+
+    /// Opt in to staking on each signal at reveal: confidence x lamports per point (0 = off)
+    pub fn set_confidence_stake(
+        ctx: Context<UpdateProvider>,
+        stake_per_confidence_lamports: u64,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        provider.stake_per_confidence_lamports = stake_per_confidence_lamports;
+        provider.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Create the pool that collects forfeited stakes and pays winning-stake bonuses (admin only)
+    pub fn init_stake_pool(ctx: Context<InitStakePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.stake_pool;
+        pool.total_forfeited = 0;
+        pool.total_bonuses = 0;
+        pool.bump = ctx.bumps.stake_pool;
+        Ok(())
+    }
+
+    /// Release a settled signal's confidence stake (permissionless)
+    /// Winners get the stake back plus a bonus from the pool; losers forfeit STAKE_FORFEIT_BPS of it
+    /// Voided signals get the stake back untouched
+    pub fn settle_stake(ctx: Context<SettleStake>) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        let pool = &mut ctx.accounts.stake_pool;
+        
+        require!(commit.outcome_recorded, AgentAlphaError::OutcomeNotRecorded);
+        require!(commit.stake_lamports > 0, AgentAlphaError::NoStake);
+        
+        let stake = std::mem::take(&mut commit.stake_lamports);
+        let pool_info = pool.to_account_info();
+        let authority_info = ctx.accounts.provider_authority.to_account_info();
+        let (returned, bonus, forfeited) = if commit.outcome == OUTCOME_VOIDED || commit.has_neutral_outcome() {
+            (stake, 0, 0)
+        } else if commit.was_correct {
+            let reserve = pool_info.lamports().saturating_sub(Rent::get()?.minimum_balance(pool_info.data_len()));
+            let bonus = (stake * STAKE_BONUS_BPS / 10000).min(reserve);
+            transfer_from_program_account(&pool_info, &authority_info, bonus)?;
+            (stake, bonus, 0)
+        } else {
+            let forfeited = stake * STAKE_FORFEIT_BPS / 10000;
+            transfer_from_program_account(&commit.to_account_info(), &pool_info, forfeited)?;
+            (stake - forfeited, 0, forfeited)
+        };
+        transfer_from_program_account(&commit.to_account_info(), &authority_info, returned)?;
+        pool.total_forfeited += forfeited;
+        pool.total_bonuses += bonus;
+        
+        emit!(StakeSettled {
+            event_seq: event_seq(),
+            provider: commit.provider,
+            signal_hash: commit.signal_hash,
+            stake,
+            returned,
+            bonus,
+            forfeited,
+        });
+        
+        Ok(())
+    }
+
+    /// Create the oracle work queue (admin only)
+    pub fn init_task_queue(ctx: Context<InitTaskQueue>) -> Result<()> {
+        // Slots start zeroed, i.e. EMPTY
+        ctx.accounts.task_queue.bump = ctx.bumps.task_queue;
+        Ok(())
+    }
+
+    /// Claim a queued settlement task whose signal window has ended (Config.settlement_oracle only)
+    pub fn claim_task(
+        ctx: Context<ClaimTask>,
+        index: u8,
+    ) -> Result<()> {
+        let queue = &mut ctx.accounts.task_queue;
+        let clock = Clock::get()?;
+        
+        let task = queue.tasks.get_mut(index as usize).ok_or(AgentAlphaError::InvalidTask)?;
+        require!(task.state == TASK_PENDING, AgentAlphaError::InvalidTask);
+        require!(clock.unix_timestamp >= task.due_at, AgentAlphaError::TaskNotDue);
+        
+        task.state = TASK_CLAIMED;
+        task.claimed_by = ctx.accounts.oracle.key();
+        task.claimed_at = clock.unix_timestamp;
+        
+        emit!(OracleTaskChanged {
+            event_seq: event_seq(),
+            index,
+            signal: task.signal,
+            state: TASK_CLAIMED,
+            oracle: task.claimed_by,
+        });
+        
+        Ok(())
+    }
+
+    /// Remove a task once its signal has settled (permissionless)
+    pub fn complete_task(
+        ctx: Context<CompleteTask>,
+        index: u8,
+    ) -> Result<()> {
+        let queue = &mut ctx.accounts.task_queue;
+        let commit = &ctx.accounts.signal_commit;
+        
+        let task = queue.tasks.get_mut(index as usize).ok_or(AgentAlphaError::InvalidTask)?;
+        require!(task.state != TASK_EMPTY && task.signal == commit.key(), AgentAlphaError::InvalidTask);
+        require!(commit.outcome_recorded, AgentAlphaError::OutcomeNotRecorded);
+        
+        let oracle = task.claimed_by;
+        *task = OracleTask::default();
+        
+        emit!(OracleTaskChanged {
+            event_seq: event_seq(),
+            index,
+            signal: commit.key(),
+            state: TASK_EMPTY,
+            oracle,
+        });
+        
+        Ok(())
+    }
+
+    /// Return claims older than TASK_CLAIM_TIMEOUT_SECS to the pending state (permissionless)
+    pub fn requeue_stale_tasks(ctx: Context<RequeueStaleTasks>) -> Result<()> {
+        let queue = &mut ctx.accounts.task_queue;
+        let now = Clock::get()?.unix_timestamp;
+        
+        for (index, task) in queue.tasks.iter_mut().enumerate() {
+            if task.state == TASK_CLAIMED && now > task.claimed_at + TASK_CLAIM_TIMEOUT_SECS {
+                let oracle = task.claimed_by;
+                task.state = TASK_PENDING;
+                task.claimed_by = Pubkey::default();
+                task.claimed_at = 0;
+                
+                emit!(OracleTaskChanged {
+                    event_seq: event_seq(),
+                    index: index as u8,
+                    signal: task.signal,
+                    state: TASK_PENDING,
+                    oracle,
+                });
+            }
+        }
+        
+        Ok(())
+    }
+
+    /// Read a provider's reputation as a stable, versioned struct (returned via return data)
+    /// Intended for CPI by third-party programs; see ReputationV1 for the semantics
+    pub fn get_reputation(ctx: Context<GetReputation>) -> Result<ReputationV1> {
+        let provider = &ctx.accounts.provider;
+        
+        let mut flags = 0u8;
+        if provider.suspended { flags |= REPUTATION_FLAG_SUSPENDED; }
+        if provider.fraud_proven { flags |= REPUTATION_FLAG_FRAUD_PROVEN; }
+        if provider.tee_verified { flags |= REPUTATION_FLAG_TEE_VERIFIED; }
+        
+        Ok(ReputationV1 {
+            version: 1,
+            score_bps: provider.reputation_score_bps(),
+            sample_size: provider.total_signals,
+            hit_rate_bps: provider.hit_rate_bps() as u16,
+            avg_return_bps: provider.avg_return_bps(),
+            last_updated: provider.last_settled_at,
+            flags,
+        })
+    }
+
+    /// A provider's week-by-week activity over the last year (returned via return data)
+    /// Tells steadily active providers apart from ones with a good record over a few bursts
+    pub fn get_activity(ctx: Context<GetReputation>) -> Result<ActivityConsistency> {
+        let provider = &ctx.accounts.provider;
+        let now = Clock::get()?.unix_timestamp;
+        
+        let weekly_bits = provider.activity_bits((now / ACTIVITY_WEEK_SECS) as u32);
+        Ok(ActivityConsistency {
+            weekly_bits,
+            active_weeks: weekly_bits.count_ones() as u8,
+            weeks_tracked: provider.weeks_tracked(now) as u8,
+            consistency_bps: provider.consistency_score_bps(now),
+        })
+    }
+
+    /// Dry-run record_outcome: return what would be recorded for these oracle inputs, without writing
+    /// Lets keepers and providers check their inputs (and feed configuration) before settling
+    pub fn preview_settlement(
+        ctx: Context<PreviewSettlement>,
+        outcome: u8,             // As for record_outcome
+        final_price_cents: u64,
+        return_bps: i32,
+        adverse_extreme_cents: u64,
+        window_high_cents: u64,
+        window_low_cents: u64,
+    ) -> Result<SettlementPreview> {
+        let commit = &ctx.accounts.signal_commit;
+        
+        require!(commit.revealed, AgentAlphaError::NotRevealed);
+        require!(!commit.outcome_recorded, AgentAlphaError::OutcomeAlreadyRecorded);
+        
+        commit.resolve_settlement(
+            outcome,
+            final_price_cents,
+            return_bps,
+            adverse_extreme_cents,
+            window_high_cents,
+            window_low_cents,
+            &ctx.accounts.config,
+        )
+    }
+
+    /// Emit a provider's history digest in one event so indexers can bootstrap without a full replay
+    /// When the provider's curve is supplied, its points (oldest first, each leaf
+    /// SHA256(timestamp LE || cumulative_return_bps LE)) are committed to as a Merkle root
+    pub fn export_provider_digest(ctx: Context<ExportProviderDigest>) -> Result<()> {
+        let provider = &ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        let (curve_root, curve_points) = match ctx.accounts.provider_curve.as_ref() {
+            Some(curve) => {
+                let leaves = curve
+                    .chronological()
+                    .map(|p| {
+                        let mut hasher = Sha256::new();
+                        hasher.update(p.timestamp.to_le_bytes());
+                        hasher.update(p.cumulative_return_bps.to_le_bytes());
+                        hasher.finalize().into()
+                    })
+                    .collect();
+                (merkle_root(leaves), curve.len)
+            }
+            None => ([0u8; 32], 0),
+        };
+        
+        emit!(ProviderDigest {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            total_signals: provider.total_signals,
+            correct_signals: provider.correct_signals,
+            total_return_bps: provider.total_return_bps,
+            expired_signals: provider.expired_signals,
+            total_tp_progress_bps: provider.total_tp_progress_bps,
+            benchmarked_signals: provider.benchmarked_signals,
+            total_alpha_bps: provider.total_alpha_bps,
+            exp_total_signals: provider.exp_total_signals,
+            exp_correct_signals: provider.exp_correct_signals,
+            low_conf_signals: provider.low_conf_signals,
+            low_conf_correct_signals: provider.low_conf_correct_signals,
+            fill_reports: provider.fill_reports,
+            total_slippage_bps: provider.total_slippage_bps,
+            executions: provider.executions,
+            total_realized_pnl_cents: provider.total_realized_pnl_cents,
+            open_signals: provider.open_signals,
+            last_settled_at: provider.last_settled_at,
+            curve_root,
+            curve_points,
+            exported_at: clock.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Create the provider's purchase embargo policy and attach it to the provider
+    pub fn create_purchase_policy(
+        ctx: Context<CreatePurchasePolicy>,
+        classifier: Pubkey,              // Who may assign buyer classes under this policy
+        rules: Vec<EmbargoRule>,
+    ) -> Result<()> {
+        require!(rules.len() <= MAX_EMBARGO_RULES, AgentAlphaError::TooManyEmbargoRules);
+        
+        let policy = &mut ctx.accounts.purchase_policy;
+        policy.provider = ctx.accounts.provider.key();
+        policy.classifier = classifier;
+        policy.rules = rules;
+        policy.bump = ctx.bumps.purchase_policy;
+        
+        ctx.accounts.provider.purchase_policy = policy.key();
+        
+        emit!(PurchasePolicyChanged {
+            event_seq: event_seq(),
+            provider: policy.provider,
+            policy: policy.key(),
+            classifier,
+            rules: policy.rules.clone(),
+        });
+        
+        Ok(())
+    }
+
+    /// Replace the policy's classifier and rules (an empty rule list embargoes nothing)
+    pub fn update_purchase_policy(
+        ctx: Context<UpdatePurchasePolicy>,
+        classifier: Pubkey,
+        rules: Vec<EmbargoRule>,
+    ) -> Result<()> {
+        require!(rules.len() <= MAX_EMBARGO_RULES, AgentAlphaError::TooManyEmbargoRules);
+        
+        let policy = &mut ctx.accounts.purchase_policy;
+        policy.classifier = classifier;
+        policy.rules = rules;
+        
+        emit!(PurchasePolicyChanged {
+            event_seq: event_seq(),
+            provider: policy.provider,
+            policy: policy.key(),
+            classifier,
+            rules: policy.rules.clone(),
+        });
+        
+        Ok(())
+    }
+
+    /// Assign a buyer's classes under a policy (policy classifier only)
+    pub fn set_buyer_class(
+        ctx: Context<SetBuyerClass>,
+        classes: u8,                     // Bitmask of buyer classes
+    ) -> Result<()> {
+        let buyer_class = &mut ctx.accounts.buyer_class;
+        
+        buyer_class.policy = ctx.accounts.purchase_policy.key();
+        buyer_class.buyer = ctx.accounts.buyer.key();
+        buyer_class.classes = classes;
+        buyer_class.updated_at = Clock::get()?.unix_timestamp;
+        buyer_class.bump = ctx.bumps.buyer_class;
+        
+        Ok(())
+    }
+
+    /// Create the set of active reporters social-settlement panels are drawn from (admin only)
+    pub fn init_reporter_set(ctx: Context<InitReporterSet>) -> Result<()> {
+        ctx.accounts.reporter_set.members = Vec::new();
+        ctx.accounts.reporter_set.bump = ctx.bumps.reporter_set;
+        Ok(())
+    }
+
+    /// Stake lamports to join the set social-settlement panels are drawn from
+    pub fn register_reporter(
+        ctx: Context<RegisterReporter>,
+        stake_lamports: u64,
+    ) -> Result<()> {
+        require!(stake_lamports >= MIN_REPORTER_STAKE_LAMPORTS, AgentAlphaError::InsufficientReporterStake);
+        
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.reporter.to_account_info(),
+                },
+            ),
+            stake_lamports,
+        )?;
+        
+        let reporter_set = &mut ctx.accounts.reporter_set;
+        require!(reporter_set.members.len() < MAX_PANEL_REPORTERS, AgentAlphaError::ReporterSetFull);
+        reporter_set.members.push(ctx.accounts.authority.key());
+        
+        let reporter = &mut ctx.accounts.reporter;
+        reporter.authority = ctx.accounts.authority.key();
+        reporter.stake_lamports = stake_lamports;
+        reporter.observations = 0;
+        reporter.outliers = 0;
+        reporter.slashed_lamports = 0;
+        reporter.unbond_at = 0;
+        reporter.bump = ctx.bumps.reporter;
+        
+        emit!(ReporterRegistered {
+            event_seq: event_seq(),
+            reporter: reporter.authority,
+            stake_lamports,
+        });
+        
+        Ok(())
+    }
+
+    /// Leave the reporter set; the stake stays slashable until the delay passes, and panels
+    /// the reporter was already drawn for still expect its observation
+    pub fn begin_reporter_unbond(ctx: Context<ReporterAction>) -> Result<()> {
+        let reporter = &mut ctx.accounts.reporter;
+        let clock = Clock::get()?;
+        
+        require!(reporter.unbond_at == 0, AgentAlphaError::AlreadyUnbonding);
+        reporter.unbond_at = clock.unix_timestamp + UNBOND_DELAY_SECS;
+        ctx.accounts.reporter_set.members.retain(|m| *m != reporter.authority);
+        
+        Ok(())
+    }
+
+    /// Withdraw a reporter's remaining stake after the unbonding delay, closing the account
+    pub fn withdraw_reporter_stake(ctx: Context<WithdrawReporterStake>) -> Result<()> {
+        let reporter = &ctx.accounts.reporter;
+        let clock = Clock::get()?;
+        
+        require!(
+            reporter.unbond_at != 0 && clock.unix_timestamp >= reporter.unbond_at,
+            AgentAlphaError::UnbondNotReady
+        );
+        
+        Ok(())
+    }
+
+    /// Open a reporter-panel settlement for a signal the oracle has not settled
+    /// Only for tokens the admin marked as lacking oracle coverage, once the signal's window plus
+    /// SOCIAL_SETTLEMENT_GRACE_SECS has passed. The panel is drawn from the reporter set with the
+    /// latest slot hash, so reporters can't choose to sit on the panels they want.
+    pub fn open_social_settlement(ctx: Context<OpenSocialSettlement>) -> Result<()> {
+        let commit = &ctx.accounts.signal_commit;
+        let clock = Clock::get()?;
+        
+        require!(commit.revealed, AgentAlphaError::NotRevealed);
+        require!(!commit.outcome_recorded, AgentAlphaError::OutcomeAlreadyRecorded);
+        let window_end = commit.revealed_at + commit.timeframe_hours as i64 * 3600;
+        require!(
+            clock.unix_timestamp >= window_end + SOCIAL_SETTLEMENT_GRACE_SECS,
+            AgentAlphaError::OracleGraceActive
+        );
+        
+        let mut hasher = Sha256::new();
+        hasher.update(&ctx.accounts.slot_hashes.try_borrow_data()?[16..48]);
+        hasher.update(commit.key().as_ref());
+        let seed: [u8; 32] = hasher.finalize().into();
+        let panel = draw_panel(&ctx.accounts.reporter_set.members, seed).ok_or(AgentAlphaError::PanelIncomplete)?;
+        
+        let social = &mut ctx.accounts.social_settlement;
+        social.signal = commit.key();
+        social.opened_by = ctx.accounts.payer.key();
+        social.opened_at = clock.unix_timestamp;
+        social.observations = Vec::new();
+        social.median_price_cents = 0;
+        social.finalized = false;
+        social.bump = ctx.bumps.social_settlement;
+        social.panel = panel;
+        social.commitments = [[0u8; 32]; SOCIAL_PANEL_SIZE];
+        social.commit_deadline = clock.unix_timestamp + SOCIAL_COMMIT_SECS;
+        social.reveal_deadline = social.commit_deadline + SOCIAL_REVEAL_SECS;
+        social.no_shows_slashed = 0;
+        
+        emit!(SocialSettlementOpened {
+            event_seq: event_seq(),
+            signal: social.signal,
+            window_end,
+            panel,
+            commit_deadline: social.commit_deadline,
+            reveal_deadline: social.reveal_deadline,
+        });
+        
+        Ok(())
+    }
+
+    /// Commit a panel member's observation of the signal's window: its closing price, high and low
+    /// `commitment` is SocialSettlement::observation_hash of the observation and a secret salt, so
+    /// members can't copy each other before reveal_observation
+    pub fn submit_observation(
+        ctx: Context<SubmitObservation>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        let social = &mut ctx.accounts.social_settlement;
+        let reporter = &ctx.accounts.reporter;
+        let clock = Clock::get()?;
+        
+        require!(!social.finalized, AgentAlphaError::SocialSettlementFinalized);
+        require!(reporter.stake_lamports >= MIN_REPORTER_STAKE_LAMPORTS, AgentAlphaError::InsufficientReporterStake);
+        require!(clock.unix_timestamp < social.commit_deadline, AgentAlphaError::ObservationWindowClosed);
+        require!(commitment != [0u8; 32], AgentAlphaError::InvalidObservation);
+        let seat = social.seat(&reporter.authority).ok_or(AgentAlphaError::NotOnPanel)?;
+        require!(social.commitments[seat] == [0u8; 32], AgentAlphaError::DuplicateObservation);
+        social.commitments[seat] = commitment;
+        
+        emit!(ObservationCommitted {
+            event_seq: event_seq(),
+            signal: social.signal,
+            reporter: reporter.authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Reveal a committed observation, once every member has committed or the commit window closed
+    pub fn reveal_observation(
+        ctx: Context<SubmitObservation>,
+        price_cents: u64,        // Price at the end of the window
+        high_cents: u64,         // Highest price over the window
+        low_cents: u64,          // Lowest price over the window
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let social = &mut ctx.accounts.social_settlement;
+        let reporter = &mut ctx.accounts.reporter;
+        let clock = Clock::get()?;
+        
+        require!(!social.finalized, AgentAlphaError::SocialSettlementFinalized);
+        require!(
+            clock.unix_timestamp >= social.commit_deadline || social.commitments.iter().all(|c| *c != [0u8; 32]),
+            AgentAlphaError::ObservationWindowOpen
+        );
+        require!(clock.unix_timestamp < social.reveal_deadline, AgentAlphaError::ObservationWindowClosed);
+        require!(
+            low_cents > 0 && low_cents <= price_cents && price_cents <= high_cents,
+            AgentAlphaError::InvalidObservation
+        );
+        let seat = social.seat(&reporter.authority).ok_or(AgentAlphaError::NotOnPanel)?;
+        require!(
+            !social.observations.iter().any(|o| o.reporter == reporter.authority),
+            AgentAlphaError::DuplicateObservation
+        );
+        require!(
+            SocialSettlement::observation_hash(
+                &social.signal,
+                &reporter.authority,
+                price_cents,
+                high_cents,
+                low_cents,
+                &salt
+            ) == social.commitments[seat],
+            AgentAlphaError::ObservationMismatch
+        );
+        
+        social.observations.push(PriceObservation {
+            reporter: reporter.authority,
+            price_cents,
+            high_cents,
+            low_cents,
+            outlier: false,
+            slashed: false,
+        });
+        reporter.observations += 1;
+        
+        emit!(ObservationSubmitted {
+            event_seq: event_seq(),
+            signal: social.signal,
+            reporter: reporter.authority,
+            price_cents,
+            high_cents,
+            low_cents,
+        });
+        
+        Ok(())
+    }
+
+    /// Settle a signal from its panel's revealed observations: all of them, or at least SOCIAL_QUORUM
+    /// once the reveal window has closed. Observations far from the panel median in close, high or
+    /// low are flagged as outliers; the medians of the rest settle the signal as the oracle would
+    /// (see SignalCommit::window_outcome and resolve_settlement). Members who never revealed can
+    /// be slashed like outliers.
+    pub fn finalize_social_settlement(ctx: Context<FinalizeSocialSettlement>) -> Result<()> {
+        let social = &mut ctx.accounts.social_settlement;
+        let commit = &mut ctx.accounts.signal_commit;
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(!social.finalized, AgentAlphaError::SocialSettlementFinalized);
+        require!(!commit.outcome_recorded, AgentAlphaError::OutcomeAlreadyRecorded);
+        require!(
+            social.observations.len() == SOCIAL_PANEL_SIZE
+                || (clock.unix_timestamp >= social.reveal_deadline && social.observations.len() >= SOCIAL_QUORUM),
+            AgentAlphaError::PanelIncomplete
+        );
+        
+        let (panel_close, panel_high, panel_low) = social.median_observation();
+        let deviates = |cents: u64, median: u64| {
+            cents.abs_diff(median) as u128 * 10000 / median as u128 > SOCIAL_OUTLIER_BPS as u128
+        };
+        for o in social.observations.iter_mut() {
+            o.outlier = deviates(o.price_cents, panel_close)
+                || deviates(o.high_cents, panel_high)
+                || deviates(o.low_cents, panel_low);
+        }
+        let (close_cents, high_cents, low_cents) = social.median_observation();
+        social.median_price_cents = close_cents;
+        social.finalized = true;
+        
+        // TP and SL settle at their level, like an oracle report; otherwise at the window's close
+        let outcome = commit.window_outcome(high_cents, low_cents);
+        let final_price_cents = match outcome {
+            1 => commit.tp_cents,
+            2 => commit.effective_sl_cents(),
+            _ => close_cents,
+        };
+        let adverse_extreme_cents = if commit.direction == 0 { low_cents } else { high_cents };
+        let settlement = commit.resolve_settlement(
+            outcome,
+            final_price_cents,
+            commit.return_from_entry_bps(commit.entry_cents, final_price_cents),
+            adverse_extreme_cents,
+            high_cents,
+            low_cents,
+            &ctx.accounts.config,
+        )?;
+        // The panel observes no benchmark
+        commit.apply_settlement(&settlement, final_price_cents, 0, clock.unix_timestamp);
+        commit.settled_by = ctx.accounts.keeper.key();
+        commit.price_feed = social.key();
+        commit.publish_slot = clock.slot;
+        commit.publish_time = clock.unix_timestamp;
+        
+        // A tripped circuit breaker holds reputation updates back until a guardian resets it
+        let config = &mut ctx.accounts.config;
+        if config.observe_settlement(commit.return_bps, clock.unix_timestamp) {
+            emit!(CircuitBreakerTripped {
+                event_seq: event_seq(),
+                window_settlements: config.breaker_window_settlements,
+                window_losses: config.breaker_window_losses,
+                tripped_at: config.breaker_tripped_at,
+            });
+        }
+        if config.breaker_tripped_at != 0 {
+            commit.reputation_pending = true;
+        } else {
+            apply_reputation(
+                provider,
+                ctx.accounts.provider_curve.as_mut(),
+                commit,
+                config.min_reputation_confidence,
+                clock.unix_timestamp,
+            );
+        }
+        
+        if let Some(stats) = ctx.accounts.token_stats.as_mut() {
+            if stats.record_settlement(commit, clock.unix_timestamp) {
+                emit!(TokenStatsUpdated {
+                    event_seq: event_seq(),
+                    token: stats.token.clone(),
+                    active_signals: stats.active_signals,
+                    direction_skew_bps: stats.direction_skew_bps(),
+                    avg_confidence: stats.avg_confidence(),
+                });
+            }
+        }
+        if let Some(consensus) = ctx.accounts.token_consensus.as_mut() {
+            if consensus.record_settlement(commit, clock.unix_timestamp) {
+                emit!(TokenConsensusUpdated {
+                    event_seq: event_seq(),
+                    token: consensus.token.clone(),
+                    open_signals: consensus.open_signals,
+                    consensus_bps: consensus.consensus_bps(),
+                });
+            }
+        }
+        
+        let deposit = std::mem::take(&mut commit.settlement_deposit);
+        let settlement_reward = pay_keeper_reward(
+            &commit.to_account_info(),
+            &ctx.accounts.config.to_account_info(),
+            &ctx.accounts.keeper.to_account_info(),
+            deposit,
+            ctx.accounts.config.settle_reward_lamports,
+            ctx.accounts.config.is_trusted_keeper(&ctx.accounts.keeper.key()),
+        )?;
+        
+        emit!(SocialSettlementFinalized {
+            event_seq: event_seq(),
+            signal: social.signal,
+            median_price_cents: close_cents,
+            median_high_cents: high_cents,
+            median_low_cents: low_cents,
+            outliers: social.observations.iter().filter(|o| o.outlier).count() as u8,
+        });
+        emit!(OutcomeRecorded {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            signal_hash: commit.signal_hash,
+            outcome: commit.outcome,
+            was_correct: commit.was_correct,
+            return_bps: commit.return_bps,
+            tp_progress_bps: commit.tp_progress_bps,
+            benchmark_return_bps: commit.benchmark_return_bps,
+            both_touched: commit.both_touched,
+            vol_regime: commit.vol_regime,
+            experimental: commit.experimental,
+            total_signals: provider.total_signals,
+            correct_signals: provider.correct_signals,
+            settled_by: commit.settled_by,
+            settlement_reward,
+            price_feed: commit.price_feed,
+            publish_slot: commit.publish_slot,
+            publish_time: commit.publish_time,
+            update_hash: commit.update_hash,
+        });
+        
+        Ok(())
+    }
+
+    /// Slash a reporter whose observation was flagged as an outlier, or a panel member who never
+    /// revealed one (anyone can crank)
+    pub fn slash_outlier(ctx: Context<SlashOutlier>) -> Result<()> {
+        let social = &mut ctx.accounts.social_settlement;
+        let reporter = &mut ctx.accounts.reporter;
+        
+        let price_cents = match social.observations.iter_mut().find(|o| o.reporter == reporter.authority) {
+            Some(observation) => {
+                require!(observation.outlier, AgentAlphaError::NotAnOutlier);
+                require!(!observation.slashed, AgentAlphaError::AlreadySlashed);
+                observation.slashed = true;
+                observation.price_cents
+            }
+            None => {
+                let seat = social.seat(&reporter.authority).ok_or(AgentAlphaError::NotAnOutlier)?;
+                require!(social.no_shows_slashed & (1 << seat) == 0, AgentAlphaError::AlreadySlashed);
+                social.no_shows_slashed |= 1 << seat;
+                0
+            }
+        };
+        
+        let amount = reporter.stake_lamports * REPORTER_SLASH_BPS / 10000;
+        reporter.stake_lamports -= amount;
+        reporter.outliers += 1;
+        reporter.slashed_lamports += amount;
+        transfer_from_program_account(
+            &reporter.to_account_info(),
+            &ctx.accounts.config.to_account_info(),
+            amount,
+        )?;
+        
+        emit!(ReporterSlashed {
+            event_seq: event_seq(),
+            signal: social.signal,
+            reporter: reporter.authority,
+            price_cents,
+            median_price_cents: social.median_price_cents,
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Authorize a hot key to buy signals for the caller, within a daily budget and from approved providers
+    /// The deposit funds the session; purchases made with the key are paid from it
+    pub fn create_buyer_session(
+        ctx: Context<CreateBuyerSession>,
+        session_key: Pubkey,
+        daily_budget_lamports: u64,
+        expires_at: i64,
+        providers: Vec<Pubkey>,
+        deposit_lamports: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        
+        require!(providers.len() <= MAX_SESSION_PROVIDERS, AgentAlphaError::TooManySessionProviders);
+        require!(expires_at > clock.unix_timestamp, AgentAlphaError::SessionExpired);
+        
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.buyer_session.to_account_info(),
+                },
+            ),
+            deposit_lamports,
+        )?;
+        
+        let session = &mut ctx.accounts.buyer_session;
+        session.owner = ctx.accounts.owner.key();
+        session.session_key = session_key;
+        session.daily_budget_lamports = daily_budget_lamports;
+        session.day = 0;
+        session.spent_today = 0;
+        session.expires_at = expires_at;
+        session.providers = providers;
+        session.created_at = clock.unix_timestamp;
+        session.bump = ctx.bumps.buyer_session;
+        
+        emit!(BuyerSessionChanged {
+            event_seq: event_seq(),
+            owner: session.owner,
+            session_key,
+            daily_budget_lamports,
+            expires_at,
+            providers: session.providers.clone(),
+        });
+        
+        Ok(())
+    }
+
+    /// Change a session's budget, expiry and approved providers, optionally topping up its balance
+    pub fn update_buyer_session(
+        ctx: Context<UpdateBuyerSession>,
+        daily_budget_lamports: u64,
+        expires_at: i64,
+        providers: Vec<Pubkey>,
+        top_up_lamports: u64,
+    ) -> Result<()> {
+        require!(providers.len() <= MAX_SESSION_PROVIDERS, AgentAlphaError::TooManySessionProviders);
+        
+        if top_up_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.buyer_session.to_account_info(),
+                    },
+                ),
+                top_up_lamports,
+            )?;
+        }
+        
+        let session = &mut ctx.accounts.buyer_session;
+        session.daily_budget_lamports = daily_budget_lamports;
+        session.expires_at = expires_at;
+        session.providers = providers;
+        
+        emit!(BuyerSessionChanged {
+            event_seq: event_seq(),
+            owner: session.owner,
+            session_key: session.session_key,
+            daily_budget_lamports,
+            expires_at,
+            providers: session.providers.clone(),
+        });
+        
+        Ok(())
+    }
+
+    /// Revoke a session key, returning the session's remaining balance to the owner
+    pub fn close_buyer_session(ctx: Context<CloseBuyerSession>) -> Result<()> {
+        emit!(BuyerSessionClosed {
+            event_seq: event_seq(),
+            owner: ctx.accounts.owner.key(),
+            session_key: ctx.accounts.buyer_session.session_key,
+        });
+        
+        Ok(())
+    }
+
+    /// Create the caller's spend report; purchases made while it exists are tallied into it
+    pub fn init_spend_report(ctx: Context<InitSpendReport>) -> Result<()> {
+        let report = &mut ctx.accounts.spend_report;
+        report.buyer = ctx.accounts.buyer.key();
+        report.providers = Vec::new();
+        report.bump = ctx.bumps.spend_report;
+        Ok(())
+    }
+
+    /// Fold a settled purchased signal's outcome into the buyer's spend report (anyone can crank)
+    pub fn report_purchase_outcome(ctx: Context<ReportPurchaseOutcome>) -> Result<()> {
+        let commit = &ctx.accounts.signal_commit;
+        let access = &mut ctx.accounts.signal_access;
+        let report = &mut ctx.accounts.spend_report;
+        
+        require!(commit.outcome_recorded, AgentAlphaError::OutcomeNotRecorded);
+        require!(!access.spend_reported, AgentAlphaError::AlreadyReported);
+        require!(commit.outcome != OUTCOME_VOIDED, AgentAlphaError::SignalVoided);
+        access.spend_reported = true;
+        report.record_outcome(commit.provider, commit.was_correct, commit.return_bps);
+        
+        emit!(SpendReportUpdated {
+            event_seq: event_seq(),
+            buyer: report.buyer,
+            total_spent_lamports: report.total_spent_lamports,
+            total_settled: report.total_settled,
+            total_return_bps: report.total_return_bps,
+        });
+        
+        Ok(())
+    }
+
+    /// Create the open-interest stats account for a token (permissionless)
+    /// Reveals and settlements that pass it keep it current; batched reveals are not counted
+    pub fn init_token_stats(ctx: Context<InitTokenStats>, token: String) -> Result<()> {
+        require!(token.len() <= 16, AgentAlphaError::TokenTooLong);
+        let stats = &mut ctx.accounts.token_stats;
+        stats.token = token;
+        stats.bump = ctx.bumps.token_stats;
+        Ok(())
+    }
+
+    /// Create the reputation-weighted consensus account for a token (permissionless)
+    /// Like TokenStats it only tracks reveals and settlements that pass it
+    pub fn init_token_consensus(ctx: Context<InitTokenConsensus>, token: String) -> Result<()> {
+        require!(token.len() <= 16, AgentAlphaError::TokenTooLong);
+        let consensus = &mut ctx.accounts.token_consensus;
+        consensus.token = token;
+        consensus.bump = ctx.bumps.token_consensus;
+        Ok(())
+    }
+
+    /// Declare or lift a trading halt on a registered token (admin only)
+    /// Signals on the token revealed before the halt can then be voided with void_signal
+    pub fn set_token_halted(
+        ctx: Context<UpdateToken>,
+        halted: bool,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.token_registry;
+        entry.halted_at = if halted { Clock::get()?.unix_timestamp } else { 0 };
+        
+        emit!(TokenHaltChanged {
+            event_seq: event_seq(),
+            symbol: entry.symbol.clone(),
+            halted_at: entry.halted_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Settle an open signal on a halted token as VOIDED (permissionless once the halt is declared)
+    /// Voided signals leave the provider's reputation untouched and their confidence stake is returned in full
+    /// Buyers take their escrow back with reclaim_voided_purchase; refund_voided_purchase pays the rest
+    pub fn void_signal(ctx: Context<VoidSignal>) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        let provider = &mut ctx.accounts.provider;
+        let registry = &ctx.accounts.token_registry;
+        let clock = Clock::get()?;
+        
+        require!(commit.revealed, AgentAlphaError::NotRevealed);
+        require!(!commit.outcome_recorded, AgentAlphaError::OutcomeAlreadyRecorded);
+        require!(
+            registry.halted_at != 0 && commit.revealed_at <= registry.halted_at,
+            AgentAlphaError::TokenNotHalted
+        );
+        
+        commit.outcome_recorded = true;
+        commit.outcome = OUTCOME_VOIDED;
+        commit.was_correct = false;
+        commit.return_bps = 0;
+        commit.evaluated_at = clock.unix_timestamp;
+        commit.settled_by = ctx.accounts.keeper.key();
+        commit.price_feed = registry.key();
+        commit.publish_slot = clock.slot;
+        commit.publish_time = clock.unix_timestamp;
+        provider.open_signals = provider.open_signals.saturating_sub(1);
+        provider.updated_at = clock.unix_timestamp;
+        
+        if let Some(stats) = ctx.accounts.token_stats.as_mut() {
+            if stats.record_settlement(commit, clock.unix_timestamp) {
+                emit!(TokenStatsUpdated {
+                    event_seq: event_seq(),
+                    token: stats.token.clone(),
+                    active_signals: stats.active_signals,
+                    direction_skew_bps: stats.direction_skew_bps(),
+                    avg_confidence: stats.avg_confidence(),
+                });
+            }
+        }
+        if let Some(consensus) = ctx.accounts.token_consensus.as_mut() {
+            if consensus.record_settlement(commit, clock.unix_timestamp) {
+                emit!(TokenConsensusUpdated {
+                    event_seq: event_seq(),
+                    token: consensus.token.clone(),
+                    open_signals: consensus.open_signals,
+                    consensus_bps: consensus.consensus_bps(),
+                });
+            }
+        }
+        
+        let deposit = std::mem::take(&mut commit.settlement_deposit);
+        let settlement_reward = pay_keeper_reward(
+            &commit.to_account_info(),
+            &ctx.accounts.config.to_account_info(),
+            &ctx.accounts.keeper.to_account_info(),
+            deposit,
+            ctx.accounts.config.settle_reward_lamports,
+            ctx.accounts.config.is_trusted_keeper(&ctx.accounts.keeper.key()),
+        )?;
+        
+        emit!(SignalVoided {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            signal_hash: commit.signal_hash,
+            token: commit.token.clone(),
+            voided_by: commit.settled_by,
+            settlement_reward,
+        });
+        
+        Ok(())
+    }
+
+    /// Return a buyer's payment for a voided signal (provider authority)
+    /// For a SOL purchase whatever is still escrowed comes back from the access record, and the
+    /// rest of the price, which already reached the provider, from the authority's wallet. A token
+    /// purchase is refunded from the provider's vault for its mint.
+    pub fn refund_voided_purchase(ctx: Context<RefundVoidedPurchase>) -> Result<()> {
+        let token_refund = match (
+            ctx.accounts.price_vault.as_deref(),
+            ctx.accounts.buyer_token_account.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        ) {
+            (Some(vault), Some(buyer_token), Some(token_program)) => Some(TokenRefund {
+                provider: &ctx.accounts.provider,
+                vault,
+                buyer_token,
+                token_program,
+            }),
+            _ => None,
+        };
+        refund_purchase(
+            &mut ctx.accounts.signal_access,
+            &ctx.accounts.signal_commit,
+            &ctx.accounts.buyer,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            token_refund,
+        )?;
+        check_invariants!(ctx.accounts.signal_access);
+        
+        Ok(())
+    }
+
+    /// Take back a purchase of a voided signal without waiting on the provider (buyer)
+    /// A SOL purchase gets back what it still holds in escrow, and refund_voided_purchase can
+    /// still pay it the rest of the price; a token purchase is refunded in full from the
+    /// provider's vault for its mint
+    pub fn reclaim_voided_purchase(ctx: Context<ReclaimVoidedPurchase>) -> Result<()> {
+        let access = &mut ctx.accounts.signal_access;
+        
+        if access.payment_mint != Pubkey::default() {
+            let (Some(vault), Some(buyer_token), Some(token_program)) = (
+                ctx.accounts.price_vault.as_deref(),
+                ctx.accounts.buyer_token_account.as_deref(),
+                ctx.accounts.token_program.as_ref(),
+            ) else {
+                return err!(AgentAlphaError::MissingPaymentAccounts);
+            };
+            // No SOL is paid from the authority side of a token refund
+            let buyer = ctx.accounts.buyer.to_account_info();
+            refund_purchase(
+                access,
+                &ctx.accounts.signal_commit,
+                &buyer,
+                &buyer,
+                &ctx.accounts.system_program.to_account_info(),
+                Some(TokenRefund {
+                    provider: &ctx.accounts.provider,
+                    vault,
+                    buyer_token,
+                    token_program,
+                }),
+            )?;
+        } else {
+            require!(
+                ctx.accounts.signal_commit.outcome == OUTCOME_VOIDED,
+                AgentAlphaError::SignalNotVoided
+            );
+            require!(!access.refunded, AgentAlphaError::AlreadyRefunded);
+            let lamports = std::mem::take(&mut access.escrow_lamports);
+            require!(lamports > 0, AgentAlphaError::NothingEscrowed);
+            access.refund_bps = 0;
+            access.escrow_reclaimed += lamports;
+            transfer_from_program_account(&access.to_account_info(), &ctx.accounts.buyer.to_account_info(), lamports)?;
+            
+            emit!(PurchaseRefunded {
+                event_seq: event_seq(),
+                signal: access.signal,
+                buyer: access.buyer,
+                lamports,
+                payment_mint: Pubkey::default(),
+            });
+        }
+        
+        check_invariants!(ctx.accounts.signal_access);
+        
+        Ok(())
+    }
+
+    /// Void one of the provider's own unrevealed signals, e.g. after finding a model error (authority)
+    /// Every buyer is refunded in full by the withdrawal: each purchase is passed in remaining_accounts
+    /// as a (signal_access, buyer) pair, or (signal_access, buyer's token account) for a token
+    /// purchase, refunded from the matching vault. A signal with more than MAX_BATCH_REFUNDS buyers
+    /// can't be withdrawn. With `replacement_credit` each refunded buyer can also claim the
+    /// provider's next signal free. Counted in self_voided_signals.
+    pub fn withdraw_signal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawSignal<'info>>,
+        replacement_credit: bool,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let commit = &mut ctx.accounts.signal_commit;
+        
+        require!(!commit.revealed, AgentAlphaError::AlreadyRevealed);
+        require!(!commit.outcome_recorded && !commit.expired, AgentAlphaError::SignalClosed);
+        require!(!commit.awaits_baseline_draw(), AgentAlphaError::BaselineAwaitingDraw);
+        require!(
+            ctx.remaining_accounts.len().is_multiple_of(2) && ctx.remaining_accounts.len() <= MAX_BATCH_REFUNDS * 2,
+            AgentAlphaError::InvalidBatchSize
+        );
+        // A purchase can only be refunded once, so this many pairs covers every buyer
+        require!(
+            ctx.remaining_accounts.len() / 2 == commit.buyer_count as usize,
+            AgentAlphaError::IncompleteRefunds
+        );
+        
+        commit.outcome_recorded = true;
+        commit.outcome = OUTCOME_VOIDED;
+        commit.was_correct = false;
+        commit.evaluated_at = clock.unix_timestamp;
+        commit.settled_by = ctx.accounts.authority.key();
+        commit.replacement_credit = replacement_credit;
+        commit.layout_version = SIGNAL_COMMIT_LAYOUT_VERSION;
+        // Nobody settles it, so the settlement deposit goes back to the provider
+        let deposit = std::mem::take(&mut commit.settlement_deposit);
+        transfer_from_program_account(&commit.to_account_info(), &ctx.accounts.authority.to_account_info(), deposit)?;
+        
+        let provider = &mut ctx.accounts.provider;
+        provider.open_signals = provider.open_signals.saturating_sub(1);
+        if !provider.has_layout(5) {
+            provider.self_voided_signals = 0;
+            provider.layout_version = PROVIDER_LAYOUT_VERSION;
+        }
+        provider.self_voided_signals += 1;
+        provider.updated_at = clock.unix_timestamp;
+        
+        let commit_key = ctx.accounts.signal_commit.key();
+        for accounts in ctx.remaining_accounts.chunks(2) {
+            let mut access = Account::<SignalAccess>::try_from(&accounts[0])?;
+            require_keys_eq!(access.signal, commit_key, AgentAlphaError::InvalidRefundAccount);
+            let buyer_token;
+            let token_refund = if access.payment_mint == Pubkey::default() {
+                require_keys_eq!(accounts[1].key(), access.buyer, AgentAlphaError::InvalidRefundAccount);
+                None
+            } else {
+                let vault = [ctx.accounts.price_vault.as_deref(), ctx.accounts.payment_vault.as_deref()]
+                    .into_iter()
+                    .flatten()
+                    .find(|v| v.mint == access.payment_mint)
+                    .ok_or(AgentAlphaError::MissingPaymentAccounts)?;
+                let token_program = ctx.accounts.token_program.as_ref().ok_or(AgentAlphaError::MissingPaymentAccounts)?;
+                buyer_token = Account::<TokenAccount>::try_from(&accounts[1])?;
+                Some(TokenRefund {
+                    provider: &ctx.accounts.provider,
+                    vault,
+                    buyer_token: &buyer_token,
+                    token_program,
+                })
+            };
+            refund_purchase(
+                &mut access,
+                &ctx.accounts.signal_commit,
+                &accounts[1],
+                &ctx.accounts.authority.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                token_refund,
+            )?;
+            access.exit(&crate::ID)?;
+        }
+        
+        emit!(SignalWithdrawn {
+            event_seq: event_seq(),
+            provider: ctx.accounts.provider.key(),
+            signal_hash: ctx.accounts.signal_commit.signal_hash,
+            buyer_count: ctx.accounts.signal_commit.buyer_count,
+            refunded: (ctx.remaining_accounts.len() / 2) as u16,
+            replacement_credit,
+            self_voided_signals: ctx.accounts.provider.self_voided_signals,
+        });
+        
+        check_invariants!(ctx.accounts.signal_commit, ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Authorize an independent endpoint prober (admin only)
+    pub fn register_prober(
+        ctx: Context<RegisterProber>,
+        prober_authority: Pubkey,
+    ) -> Result<()> {
+        let prober = &mut ctx.accounts.prober;
+        prober.authority = prober_authority;
+        prober.probes = 0;
+        prober.registered_at = Clock::get()?.unix_timestamp;
+        prober.bump = ctx.bumps.prober;
+        Ok(())
+    }
+
+    /// Revoke a prober (admin only)
+    pub fn remove_prober(_ctx: Context<RemoveProber>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Attest a provider endpoint's liveness and latency (registered prober)
+    /// Observations go into the provider's ring buffer, oldest overwritten first
+    pub fn attest_endpoint_health(
+        ctx: Context<AttestEndpointHealth>,
+        live: bool,
+        latency_ms: u32,         // Round trip of the probe (0 when not live)
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let prober = &mut ctx.accounts.prober;
+        let clock = Clock::get()?;
+        
+        require!(
+            !provider.health_probes.iter().any(|p| {
+                p.prober == prober.authority && clock.unix_timestamp - p.observed_at < PROBE_MIN_INTERVAL_SECS
+            }),
+            AgentAlphaError::ProbeTooSoon
+        );
+        
+        let head = provider.health_probe_head as usize;
+        provider.health_probes[head] = EndpointProbe {
+            prober: prober.authority,
+            observed_at: clock.unix_timestamp,
+            latency_ms: if live { latency_ms } else { 0 },
+            live,
+        };
+        provider.health_probe_head = ((head + 1) % MAX_HEALTH_PROBES) as u8;
+        prober.probes += 1;
+        
+        emit!(EndpointProbed {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            prober: prober.authority,
+            live,
+            latency_ms,
+        });
+        
+        Ok(())
+    }
+
+    /// Cite the upstream providers a signal builds on (provider authority)
+    /// Each cited provider accrues CITATION_ROYALTY_BPS of every SOL sale; citations are fixed once the signal sells
+    pub fn cite_sources(ctx: Context<CiteSources>) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        
+        require!(
+            commit.buyer_count == 0 && !commit.outcome_recorded && !commit.expired,
+            AgentAlphaError::CitationsLocked
+        );
+        let mut citations = [Pubkey::default(); MAX_CITATIONS];
+        citations[0] = ctx.accounts.cited_provider.key();
+        if let Some(second) = ctx.accounts.second_cited_provider.as_ref() {
+            citations[1] = second.key();
+        }
+        require!(
+            citations.iter().all(|c| *c != commit.provider) && citations[0] != citations[1],
+            AgentAlphaError::InvalidCitation
+        );
+        commit.citations = citations;
+        
+        emit!(SourcesCited {
+            event_seq: event_seq(),
+            provider: commit.provider,
+            signal_hash: commit.signal_hash,
+            citations,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw the citation royalties accrued by the provider (authority only)
+    /// The destination must pass the provider's withdrawal allowlist; with a withdrawal delay set
+    /// the amount is only scheduled, to be paid by complete_withdrawal
+    pub fn claim_citation_royalties(ctx: Context<ClaimCitationRoyalties>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let destination = ctx.accounts.destination.key();
+        
+        require!(provider.allows_withdrawal_to(&destination), AgentAlphaError::WithdrawalDestinationNotAllowed);
+        require!(provider.withdrawal_unlocks_at == 0, AgentAlphaError::WithdrawalPending);
+        let amount = std::mem::take(&mut provider.citation_royalties_unclaimed);
+        require!(amount > 0, AgentAlphaError::NoRoyalties);
+        
+        if provider.withdrawal_delay_secs > 0 {
+            let provider_key = provider.key();
+            provider.schedule_withdrawal(provider_key, WITHDRAWAL_ROYALTIES, destination, amount, Clock::get()?.unix_timestamp);
+            check_invariants!(ctx.accounts.provider);
+            return Ok(());
+        }
+        
+        transfer_from_program_account(
+            &provider.to_account_info(),
+            &ctx.accounts.destination.to_account_info(),
+            amount,
+        )?;
+        
+        emit!(CitationRoyaltiesClaimed {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            amount,
+        });
+        
+        check_invariants!(ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Withdraw the provider's vested held-back proceeds (authority, not once fraud is proven)
+    /// The destination must pass the provider's withdrawal allowlist; with a withdrawal delay set
+    /// the amount is only scheduled, to be paid by complete_withdrawal
+    pub fn release_vested_earnings(ctx: Context<ReleaseVestedEarnings>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let destination = ctx.accounts.destination.key();
+        let clock = Clock::get()?;
+        
+        require!(!provider.fraud_proven, AgentAlphaError::ProviderFraudProven);
+        require!(provider.allows_withdrawal_to(&destination), AgentAlphaError::WithdrawalDestinationNotAllowed);
+        require!(provider.withdrawal_unlocks_at == 0, AgentAlphaError::WithdrawalPending);
+        let vested = provider.vested_lamports(ctx.accounts.config.vesting_period_secs, clock.unix_timestamp);
+        let amount = vested.saturating_sub(provider.vesting_released_lamports);
+        require!(amount > 0, AgentAlphaError::NothingVested);
+        
+        if provider.withdrawal_delay_secs > 0 {
+            let provider_key = provider.key();
+            provider.schedule_withdrawal(provider_key, WITHDRAWAL_VESTED, destination, amount, clock.unix_timestamp);
+            return Ok(());
+        }
+        
+        provider.vesting_released_lamports = vested;
+        transfer_from_program_account(
+            &provider.to_account_info(),
+            &ctx.accounts.destination.to_account_info(),
+            amount,
+        )?;
+        
+        emit!(VestedEarningsReleased {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            amount,
+            remaining: provider.vesting_held_lamports - provider.vesting_released_lamports,
+        });
+        
+        check_invariants!(ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Pay out a scheduled withdrawal once its delay has passed (anyone can crank)
+    /// Token withdrawals are paid by complete_token_withdrawal
+    pub fn complete_withdrawal(ctx: Context<CompleteWithdrawal>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(provider.withdrawal_unlocks_at > 0, AgentAlphaError::NoPendingWithdrawal);
+        require!(
+            provider.pending_withdrawal_kind() != WITHDRAWAL_TOKEN,
+            AgentAlphaError::WrongWithdrawalKind
+        );
+        require!(
+            clock.unix_timestamp >= provider.withdrawal_unlocks_at,
+            AgentAlphaError::WithdrawalDelayActive
+        );
+        require!(!provider.fraud_proven, AgentAlphaError::ProviderFraudProven);
+        
+        let (kind, _, amount) = provider.take_pending_withdrawal();
+        transfer_from_program_account(
+            &provider.to_account_info(),
+            &ctx.accounts.destination.to_account_info(),
+            amount,
+        )?;
+        
+        if kind == WITHDRAWAL_ROYALTIES {
+            emit!(CitationRoyaltiesClaimed {
+                event_seq: event_seq(),
+                provider: provider.key(),
+                amount,
+            });
+        } else {
+            provider.vesting_released_lamports += amount;
+            emit!(VestedEarningsReleased {
+                event_seq: event_seq(),
+                provider: provider.key(),
+                amount,
+                remaining: provider.vesting_held_lamports - provider.vesting_released_lamports,
+            });
+        }
+        
+        check_invariants!(ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Pay out a scheduled token withdrawal from its price vault once its delay has passed (anyone can crank)
+    pub fn complete_token_withdrawal(ctx: Context<CompleteTokenWithdrawal>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(provider.withdrawal_unlocks_at > 0, AgentAlphaError::NoPendingWithdrawal);
+        require!(
+            provider.pending_withdrawal_kind() == WITHDRAWAL_TOKEN,
+            AgentAlphaError::WrongWithdrawalKind
+        );
+        require!(
+            clock.unix_timestamp >= provider.withdrawal_unlocks_at,
+            AgentAlphaError::WithdrawalDelayActive
+        );
+        require!(!provider.fraud_proven, AgentAlphaError::ProviderFraudProven);
+        require!(
+            provider.pending_withdrawal_lamports <= ctx.accounts.price_vault.amount,
+            AgentAlphaError::InsufficientVaultBalance
+        );
+        
+        let (_, destination, amount) = provider.take_pending_withdrawal();
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.price_vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: provider.to_account_info(),
+                },
+                &[&[b"provider", provider.seed_authority.as_ref(), &[provider.bump]]],
+            ),
+            amount,
+        )?;
+        
+        emit!(TokenEarningsWithdrawn {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            mint: ctx.accounts.price_vault.mint,
+            destination,
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Drop a scheduled withdrawal, leaving the proceeds in the provider account (recovery key only)
+    pub fn cancel_withdrawal(ctx: Context<RecoverProvider>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        
+        require!(provider.withdrawal_unlocks_at > 0, AgentAlphaError::NoPendingWithdrawal);
+        let (destination, amount) = provider.drop_pending_withdrawal();
+        
+        emit!(WithdrawalCancelled {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            destination,
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the destinations earnings may be withdrawn to and the delay before they are paid (recovery key only)
+    /// An empty allowlist means the provider authority only; a delay of 0 pays immediately
+    pub fn set_withdrawal_policy(
+        ctx: Context<RecoverProvider>,
+        allowlist: Vec<Pubkey>,
+        delay_secs: u32,
+    ) -> Result<()> {
+        require!(
+            allowlist.len() <= MAX_WITHDRAWAL_DESTINATIONS && !allowlist.contains(&Pubkey::default()),
+            AgentAlphaError::InvalidWithdrawalAllowlist
+        );
+        let provider = &mut ctx.accounts.provider;
+        
+        provider.withdrawal_allowlist = [Pubkey::default(); MAX_WITHDRAWAL_DESTINATIONS];
+        provider.withdrawal_allowlist[..allowlist.len()].copy_from_slice(&allowlist);
+        provider.withdrawal_delay_secs = delay_secs;
+        
+        emit!(WithdrawalPolicyChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            allowlist,
+            delay_secs,
+        });
+        
+        Ok(())
+    }
+
+    /// Claw a fraudulent provider's unreleased held-back proceeds back into the protocol config account (admin only)
+    pub fn claw_back_vesting(ctx: Context<ClawBackVesting>) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        
+        require!(provider.fraud_proven, AgentAlphaError::ProviderNotFraudulent);
+        let amount = provider.vesting_held_lamports - provider.vesting_released_lamports;
+        require!(amount > 0, AgentAlphaError::NothingVested);
+        
+        provider.vesting_held_lamports = provider.vesting_released_lamports;
+        provider.drop_pending_withdrawal();
+        transfer_from_program_account(
+            &provider.to_account_info(),
+            &ctx.accounts.config.to_account_info(),
+            amount,
+        )?;
+        
+        emit!(VestingClawedBack {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            amount,
+        });
+        
+        check_invariants!(ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Move a provider's held-back token proceeds into its vault once its vesting period is over
+    /// (anyone can crank). Token holdbacks vest all at once, when the SOL holdback is fully vested.
+    pub fn release_token_holdback(ctx: Context<ReleaseTokenHoldback>) -> Result<()> {
+        let provider = &ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!(!provider.fraud_proven, AgentAlphaError::ProviderFraudProven);
+        require!(
+            clock.unix_timestamp >= provider.vesting_ends_at(ctx.accounts.config.vesting_period_secs),
+            AgentAlphaError::NothingVested
+        );
+        let amount = ctx.accounts.token_holdback.amount;
+        require!(amount > 0, AgentAlphaError::NothingVested);
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.token_holdback.to_account_info(),
+                    to: ctx.accounts.price_vault.to_account_info(),
+                    authority: provider.to_account_info(),
+                },
+                &[&[b"provider", provider.seed_authority.as_ref(), &[provider.bump]]],
+            ),
+            amount,
+        )?;
+        
+        emit!(TokenHoldbackReleased {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            mint: ctx.accounts.token_holdback.mint,
+            destination: ctx.accounts.price_vault.key(),
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Claw a fraudulent provider's held-back token proceeds into the treasury (admin only)
+    pub fn claw_back_token_holdback(ctx: Context<ClawBackTokenHoldback>) -> Result<()> {
+        let provider = &ctx.accounts.provider;
+        
+        require!(provider.fraud_proven, AgentAlphaError::ProviderNotFraudulent);
+        let amount = ctx.accounts.token_holdback.amount;
+        require!(amount > 0, AgentAlphaError::NothingVested);
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.token_holdback.to_account_info(),
+                    to: ctx.accounts.treasury_token_account.to_account_info(),
+                    authority: provider.to_account_info(),
+                },
+                &[&[b"provider", provider.seed_authority.as_ref(), &[provider.bump]]],
+            ),
+            amount,
+        )?;
+        
+        emit!(TokenHoldbackReleased {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            mint: ctx.accounts.token_holdback.mint,
+            destination: ctx.accounts.treasury_token_account.key(),
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Configure the settlement circuit breaker thresholds (admin only)
+    pub fn set_circuit_breaker(
+        ctx: Context<AdminOnly>,
+        max_settlements: u32,    // Settlements per window, 0 = no volume limit
+        max_loss_bps: u16,       // Share of negative returns per window, 0 = no loss limit
+    ) -> Result<()> {
+        require!(max_loss_bps <= 10000, AgentAlphaError::InvalidBreakerThreshold);
+        let config = &mut ctx.accounts.config;
+        config.breaker_max_settlements = max_settlements;
+        config.breaker_max_loss_bps = max_loss_bps;
+        Ok(())
+    }
+
+    /// Designate who reviews and resets a tripped circuit breaker (admin only)
+    pub fn set_guardian(
+        ctx: Context<AdminOnly>,
+        guardian: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.guardian = guardian;
+        Ok(())
+    }
+
+    /// Resume reputation updates after reviewing a tripped circuit breaker (guardian only)
+    /// Settlements held back meanwhile are applied with apply_pending_reputation
+    pub fn reset_circuit_breaker(ctx: Context<GuardianOnly>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let clock = Clock::get()?;
+        
+        require!(config.breaker_tripped_at != 0, AgentAlphaError::BreakerNotTripped);
+        let tripped_at = std::mem::take(&mut config.breaker_tripped_at);
+        config.breaker_window_start = clock.unix_timestamp;
+        config.breaker_window_settlements = 0;
+        config.breaker_window_losses = 0;
+        
+        emit!(CircuitBreakerReset {
+            event_seq: event_seq(),
+            guardian: ctx.accounts.guardian.key(),
+            tripped_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Apply a settlement held back by the circuit breaker to the provider's reputation (permissionless)
+    pub fn apply_pending_reputation(ctx: Context<ApplyPendingReputation>) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        let clock = Clock::get()?;
+        
+        require!(commit.reputation_pending, AgentAlphaError::NoPendingReputation);
+        require!(ctx.accounts.config.breaker_tripped_at == 0, AgentAlphaError::ReputationPaused);
+        
+        commit.reputation_pending = false;
+        apply_reputation(
+            &mut ctx.accounts.provider,
+            ctx.accounts.provider_curve.as_mut(),
+            commit,
+            ctx.accounts.config.min_reputation_confidence,
+            clock.unix_timestamp,
+        );
+        
+        Ok(())
+    }
+
+    /// Configure random settlement audits: the secondary oracle and the sampled share (admin only)
+    pub fn set_audit_policy(
+        ctx: Context<AdminOnly>,
+        audit_oracle: Pubkey,
+        audit_rate_bps: u16,     // 0 = off
+    ) -> Result<()> {
+        require!(audit_rate_bps <= 10000, AgentAlphaError::InvalidAuditRate);
+        let config = &mut ctx.accounts.config;
+        config.audit_oracle = audit_oracle;
+        config.audit_rate_bps = audit_rate_bps;
+        Ok(())
+    }
+
+    /// Draw whether a settled signal is audited (permissionless, once per signal)
+    /// The draw hashes the signal with a seed taken from the slot hashes at the epoch's first draw,
+    /// and only covers signals settled in an earlier epoch
+    /// The first draw of an epoch fixes its seed, so only the audit oracle may make it: the
+    /// settlement oracle can't choose the slot its own signals are drawn against
+    pub fn draw_audit(ctx: Context<DrawAudit>) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        let config = &mut ctx.accounts.config;
+        let clock = Clock::get()?;
+        
+        require!(commit.outcome_recorded && commit.outcome != OUTCOME_VOIDED, AgentAlphaError::OutcomeNotRecorded);
+        require!(commit.audit_state == AUDIT_UNDRAWN, AgentAlphaError::AuditAlreadyDrawn);
+        let epoch = (clock.unix_timestamp / AUDIT_EPOCH_SECS) as u32;
+        require!(
+            ((commit.evaluated_at / AUDIT_EPOCH_SECS) as u32) < epoch,
+            AgentAlphaError::AuditTooEarly
+        );
+        
+        if config.audit_epoch != epoch {
+            require!(
+                ctx.accounts.audit_oracle.as_ref().is_some_and(|o| o.key() == config.audit_oracle),
+                AgentAlphaError::AuditEpochNotSeeded
+            );
+            // SlotHashes data: u64 entry count, then (slot, hash) entries, most recent first
+            let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+            config.audit_seed.copy_from_slice(&data[16..48]);
+            config.audit_epoch = epoch;
+        }
+        
+        let mut hasher = Sha256::new();
+        hasher.update(config.audit_seed);
+        hasher.update(commit.key().as_ref());
+        let draw: [u8; 32] = hasher.finalize().into();
+        let roll = u64::from_le_bytes(draw[..8].try_into().unwrap()) % 10000;
+        let selected = roll < config.audit_rate_bps as u64;
+        commit.audit_state = if selected { AUDIT_SELECTED } else { AUDIT_NOT_SELECTED };
+        
+        if selected {
+            emit!(AuditDrawn {
+                event_seq: event_seq(),
+                signal: commit.key(),
+                settled_by: commit.settled_by,
+                epoch,
+            });
+        }
+        
+        Ok(())
+    }
+
+    /// Re-verify a drawn signal's settlement price (audit oracle only)
+    /// Agreement within AUDIT_TOLERANCE_BPS counts toward the settling oracle's reliability record
+    pub fn submit_audit(
+        ctx: Context<SubmitAudit>,
+        final_price_cents: u64,  // Secondary oracle's price at the signal's evaluation
+    ) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        let record = &mut ctx.accounts.oracle_record;
+        
+        require!(commit.audit_state == AUDIT_SELECTED, AgentAlphaError::NotSelectedForAudit);
+        require!(final_price_cents > 0, AgentAlphaError::InvalidAuditPrice);
+        
+        let deviation_bps = commit.final_price_cents.abs_diff(final_price_cents) as u128 * 10000 / final_price_cents as u128;
+        let agreed = deviation_bps <= AUDIT_TOLERANCE_BPS;
+        commit.audit_state = if agreed { AUDIT_AGREED } else { AUDIT_DISAGREED };
+        commit.audit_price_cents = final_price_cents;
+        
+        record.oracle = commit.settled_by;
+        record.bump = ctx.bumps.oracle_record;
+        record.audits += 1;
+        if agreed {
+            record.agreements += 1;
+        }
+        
+        emit!(AuditSubmitted {
+            event_seq: event_seq(),
+            signal: commit.key(),
+            settled_by: commit.settled_by,
+            settled_price_cents: commit.final_price_cents,
+            audit_price_cents: final_price_cents,
+            agreed,
+            audits: record.audits,
+            agreements: record.agreements,
+        });
+        
+        Ok(())
+    }
+
+    /// Designate the provider account whose signals form the protocol baseline (admin only)
+    pub fn set_baseline_provider(
+        ctx: Context<AdminOnly>,
+        baseline_provider: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.baseline_provider = baseline_provider;
+        Ok(())
+    }
+
+    /// Open a baseline signal on a registered token at its current price (price oracle)
+    /// The coin-flip direction and standard TP/SL are set by draw_baseline_direction from the hash
+    /// of the slot this lands in, which the oracle can't know when it sends; `signal_hash` is only
+    /// the signal's id, nothing is revealed against it
+    /// Baseline signals settle through record_outcome like any other, so the baseline provider's
+    /// record is a live null model
+    pub fn open_baseline_signal(
+        ctx: Context<OpenBaselineSignal>,
+        signal_hash: [u8; 32],
+        token: String,
+        entry_cents: u64,        // Current price, on the token's tick grid
+    ) -> Result<()> {
+        let registry = &ctx.accounts.token_registry;
+        let clock = Clock::get()?;
+        
+        require!(
+            registry.halted_at == 0 && entry_cents > 0 && registry.is_on_tick(entry_cents),
+            AgentAlphaError::InvalidBaselineEntry
+        );
+        // Either side of the flip must give usable levels, so the draw can't fail
+        require!(
+            baseline_levels(entry_cents, registry.tick_size_cents, 0).is_some()
+                && baseline_levels(entry_cents, registry.tick_size_cents, 1).is_some(),
+            AgentAlphaError::InvalidBaselineEntry
+        );
+        
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.price_oracle.to_account_info(),
+                    to: ctx.accounts.signal_commit.to_account_info(),
+                },
+            ),
+            SETTLEMENT_DEPOSIT_LAMPORTS,
+        )?;
+        open_commit(
+            &mut ctx.accounts.signal_commit,
+            &mut ctx.accounts.provider,
+            None,
+            signal_hash,
+            false,
+            TIER_UNLIMITED,
+            0,
+            0,
+            ctx.bumps.signal_commit,
+            clock.unix_timestamp,
+        )?;
+        
+        let commit = &mut ctx.accounts.signal_commit;
+        commit.token = token;
+        commit.entry_cents = entry_cents;
+        commit.timeframe_hours = BASELINE_TIMEFRAME_HOURS;
+        commit.confidence = BASELINE_CONFIDENCE;
+        commit.quote_currency = QUOTE_USD;
+        commit.quote_mint = None;
+        commit.min_notional_cents = None;
+        commit.max_notional_cents = None;
+        commit.benchmark = BENCH_NONE;
+        commit.entry_ladder = Vec::new();
+        commit.baseline_draw_slot = clock.slot;
+        
+        emit!(BaselineSignalCommitted {
+            event_seq: event_seq(),
+            signal: commit.key(),
+            token: commit.token.clone(),
+            entry_cents,
+            draw_slot: clock.slot,
+        });
+        
+        Ok(())
+    }
+
+    /// Flip a committed baseline signal's direction and set its TP/SL, revealing it (permissionless)
+    /// The flip hashes the signal with the hash of the slot it was opened in; once that slot has
+    /// aged out of SlotHashes the signal can't be drawn and expires unrevealed
+    pub fn draw_baseline_direction(ctx: Context<DrawBaselineDirection>) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        let clock = Clock::get()?;
+        
+        require!(
+            commit.awaits_baseline_draw() && !commit.revealed && !commit.expired,
+            AgentAlphaError::BaselineAlreadyDrawn
+        );
+        let slot_hash = slot_hash_at_or_after(&ctx.accounts.slot_hashes.try_borrow_data()?, commit.baseline_draw_slot)
+            .ok_or(AgentAlphaError::BaselineDrawUnavailable)?;
+        
+        let mut hasher = Sha256::new();
+        hasher.update(slot_hash);
+        hasher.update(commit.key().as_ref());
+        let flip: [u8; 32] = hasher.finalize().into();
+        let direction = flip[0] & 1;
+        let (tp_cents, sl_cents) = baseline_levels(commit.entry_cents, ctx.accounts.token_registry.tick_size_cents, direction)
+            .ok_or(AgentAlphaError::InvalidBaselineEntry)?;
+        
+        commit.revealed = true;
+        commit.direction = direction;
+        commit.tp_cents = tp_cents;
+        commit.sl_cents = sl_cents;
+        commit.revealed_at = clock.unix_timestamp;
+        commit.baseline_draw_slot = 0;
+        
+        emit!(BaselineSignalOpened {
+            event_seq: event_seq(),
+            signal: commit.key(),
+            token: commit.token.clone(),
+            direction,
+            entry_cents: commit.entry_cents,
+            tp_cents,
+            sl_cents,
+        });
+        
+        Ok(())
+    }
+
+    /// Compare a provider's headline record with the protocol baseline (returned via return data)
+    pub fn get_outperformance(ctx: Context<GetOutperformance>) -> Result<Outperformance> {
+        let provider = &ctx.accounts.provider;
+        let baseline = &ctx.accounts.baseline_provider;
+        
+        let hit_rate_bps = provider.hit_rate_bps();
+        let baseline_hit_rate_bps = baseline.hit_rate_bps();
+        Ok(Outperformance {
+            hit_rate_bps: hit_rate_bps as u16,
+            baseline_hit_rate_bps: baseline_hit_rate_bps as u16,
+            excess_hit_rate_bps: hit_rate_bps as i32 - baseline_hit_rate_bps as i32,
+            avg_return_bps: provider.avg_return_bps(),
+            baseline_avg_return_bps: baseline.avg_return_bps(),
+            excess_return_bps: provider.avg_return_bps() - baseline.avg_return_bps(),
+            sample_size: provider.total_signals,
+            baseline_sample_size: baseline.total_signals,
+        })
+    }
+
+    /// Create the feature registry clients read to discover what this deployment supports (admin only)
+    pub fn init_feature_registry(ctx: Context<InitFeatureRegistry>) -> Result<()> {
+        // Slots start zeroed, i.e. every feature inactive
+        ctx.accounts.feature_registry.bump = ctx.bumps.feature_registry;
+        Ok(())
+    }
+
+    /// Record a protocol feature as activated at the current slot, or deactivate it (admin only)
+    /// Re-activating an active feature keeps its original activation slot
+    pub fn set_feature_active(
+        ctx: Context<SetFeatureActive>,
+        feature: u8,             // FEATURE_* id
+        active: bool,
+    ) -> Result<()> {
+        require!((feature as usize) < MAX_FEATURES, AgentAlphaError::UnknownFeature);
+        let registry = &mut ctx.accounts.feature_registry;
+        let clock = Clock::get()?;
+        
+        let slot = &mut registry.activated_slots[feature as usize];
+        if !active {
+            *slot = 0;
+        } else if *slot == 0 {
+            *slot = clock.slot;
+        }
+        registry.updated_at = clock.unix_timestamp;
+        
+        emit!(FeatureActivationChanged {
+            event_seq: event_seq(),
+            feature,
+            activated_slot: registry.activated_slots[feature as usize],
+        });
+        
+        Ok(())
+    }
+
+    /// Sell an unsold EXCLUSIVE signal by sealed-bid auction instead of at the list price (authority)
+    /// Buyers commit bid hashes with an escrow deposit until bidding closes, then reveal them;
+    /// the highest revealed bid at or above the reserve wins access
+    pub fn open_auction(
+        ctx: Context<OpenAuction>,
+        reserve_lamports: u64,
+        bidding_secs: u32,       // Commit phase length
+        reveal_secs: u32,        // Reveal phase length, starting when bidding closes
+    ) -> Result<()> {
+        let commit = &mut ctx.accounts.signal_commit;
+        let clock = Clock::get()?;
+        
+        require!(commit.exclusivity == TIER_EXCLUSIVE, AgentAlphaError::AuctionRequiresExclusive);
+        require!(!commit.outcome_recorded && !commit.expired, AgentAlphaError::SignalClosed);
+        require!(commit.buyer_count == 0, AgentAlphaError::SignalSoldOut);
+        require!(
+            bidding_secs > 0
+                && reveal_secs > 0
+                && bidding_secs as i64 <= MAX_AUCTION_PHASE_SECS
+                && reveal_secs as i64 <= MAX_AUCTION_PHASE_SECS,
+            AgentAlphaError::InvalidAuctionWindow
+        );
+        commit.auctioned = true;
+        
+        let auction = &mut ctx.accounts.auction;
+        auction.signal = commit.key();
+        auction.provider = ctx.accounts.provider.key();
+        auction.reserve_lamports = reserve_lamports;
+        auction.bidding_ends_at = clock.unix_timestamp + bidding_secs as i64;
+        auction.reveal_ends_at = auction.bidding_ends_at + reveal_secs as i64;
+        auction.bump = ctx.bumps.auction;
+        
+        emit!(AuctionOpened {
+            event_seq: event_seq(),
+            auction: auction.key(),
+            signal: auction.signal,
+            reserve_lamports,
+            bidding_ends_at: auction.bidding_ends_at,
+            reveal_ends_at: auction.reveal_ends_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Commit a sealed bid, escrowing `deposit_lamports` (at least the bid, and may exceed it to hide it)
+    /// bid_hash = SHA256(domain::AUCTION_BID_V1 || bidder || amount_lamports as u64 LE || salt), see AuctionBid::bid_hash
+    pub fn commit_bid(
+        ctx: Context<CommitBid>,
+        bid_hash: [u8; 32],
+        deposit_lamports: u64,
+    ) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let clock = Clock::get()?;
+        
+        require!(clock.unix_timestamp < auction.bidding_ends_at, AgentAlphaError::BiddingClosed);
+        require!(deposit_lamports > 0, AgentAlphaError::InvalidBid);
+        
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.bidder.to_account_info(),
+                    to: ctx.accounts.bid.to_account_info(),
+                },
+            ),
+            deposit_lamports,
+        )?;
+        auction.bid_count += 1;
+        
+        let bid = &mut ctx.accounts.bid;
+        bid.auction = auction.key();
+        bid.bidder = ctx.accounts.bidder.key();
+        bid.bid_hash = bid_hash;
+        bid.deposit_lamports = deposit_lamports;
+        bid.committed_at = clock.unix_timestamp;
+        bid.bump = ctx.bumps.bid;
+        
+        emit!(BidCommitted {
+            event_seq: event_seq(),
+            auction: bid.auction,
+            bidder: bid.bidder,
+            deposit_lamports,
+        });
+        
+        Ok(())
+    }
+
+    /// Reveal a committed bid once bidding has closed; a bid above the current best takes the lead
+    /// Ties go to the bid revealed first
+    pub fn reveal_bid(
+        ctx: Context<RevealBid>,
+        amount_lamports: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let bid = &mut ctx.accounts.bid;
+        let clock = Clock::get()?;
+        
+        require!(
+            auction.bidding_ends_at <= clock.unix_timestamp && clock.unix_timestamp < auction.reveal_ends_at,
+            AgentAlphaError::BidRevealClosed
+        );
+        require!(!bid.revealed, AgentAlphaError::AlreadyRevealed);
+        require!(
+            AuctionBid::bid_hash(&bid.bidder, amount_lamports, &salt) == bid.bid_hash
+                || (bid.committed_at < UNTAGGED_MESSAGES_CUTOFF
+                    && AuctionBid::untagged_bid_hash(&bid.bidder, amount_lamports, &salt) == bid.bid_hash),
+            AgentAlphaError::HashMismatch
+        );
+        require!(
+            amount_lamports >= auction.reserve_lamports && amount_lamports > 0 && amount_lamports <= bid.deposit_lamports,
+            AgentAlphaError::InvalidBid
+        );
+        
+        bid.revealed = true;
+        bid.amount_lamports = amount_lamports;
+        auction.revealed_count += 1;
+        let leading = amount_lamports > auction.highest_bid_lamports;
+        if leading {
+            auction.highest_bid_lamports = amount_lamports;
+            auction.highest_bidder = bid.bidder;
+        }
+        
+        emit!(BidRevealed {
+            event_seq: event_seq(),
+            auction: auction.key(),
+            bidder: bid.bidder,
+            amount_lamports,
+            leading,
+        });
+        
+        Ok(())
+    }
+
+    /// Pay the winning bid from its deposit and grant the winner access (anyone can crank)
+    /// The rest of the winner's deposit and the bid account's rent go back to the winner.
+    /// Curator shares, vesting and the purchase escrow apply as for a purchase; cited providers earn
+    /// no royalty on auctions.
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let clock = Clock::get()?;
+        
+        require!(clock.unix_timestamp >= auction.reveal_ends_at, AgentAlphaError::AuctionNotEnded);
+        require!(!auction.settled, AgentAlphaError::AuctionAlreadySettled);
+        require!(auction.highest_bidder != Pubkey::default(), AgentAlphaError::NoWinningBid);
+        let commit = &ctx.accounts.signal_commit;
+        require!(!commit.outcome_recorded && !commit.expired, AgentAlphaError::SignalClosed);
+        auction.settled = true;
+        
+        let price_lamports = auction.highest_bid_lamports;
+        let escrow = ctx.accounts.winning_bid.to_account_info();
+        let provider = &mut ctx.accounts.provider;
+        let curator_cut = if provider.total_bonded > 0 {
+            price_lamports * provider.curator_share_bps as u64 / 10000
+        } else {
+            0
+        };
+        if curator_cut > 0 {
+            transfer_from_program_account(&escrow, &provider.to_account_info(), curator_cut)?;
+            provider.curator_reward_index += curator_cut as u128 * REWARD_INDEX_SCALE / provider.total_bonded as u128;
+        }
+        let protocol_fee = ctx.accounts.config.protocol_fee(price_lamports);
+        if protocol_fee > 0 {
+            let treasury = ctx.accounts.config.treasury_account(ctx.accounts.treasury.as_ref())?;
+            transfer_from_program_account(&escrow, &treasury, protocol_fee)?;
+        }
+        let proceeds = price_lamports - curator_cut - protocol_fee;
+        let held = ctx.accounts.config.vesting_holdback(provider.created_at, proceeds, clock.unix_timestamp);
+        if held > 0 {
+            transfer_from_program_account(&escrow, &provider.to_account_info(), held)?;
+            provider.vesting_held_lamports += held;
+        }
+        transfer_from_program_account(&escrow, &ctx.accounts.signal_access.to_account_info(), proceeds - held)?;
+        
+        let commit = &mut ctx.accounts.signal_commit;
+        commit.buyer_count += 1;
+        
+        let access = &mut ctx.accounts.signal_access;
+        access.buyer = auction.highest_bidder;
+        access.signal = commit.key();
+        access.payment_mint = Pubkey::default();
+        access.price_paid = price_lamports;
+        access.purchased_at = clock.unix_timestamp;
+        access.escrow_lamports = proceeds - held;
+        access.bump = ctx.bumps.signal_access;
+        
+        emit!(AuctionSettled {
+            event_seq: event_seq(),
+            auction: auction.key(),
+            signal: commit.key(),
+            winner: access.buyer,
+            price_lamports,
+        });
+        emit!(SignalPurchased {
+            event_seq: event_seq(),
+            provider: commit.provider,
+            signal_hash: commit.signal_hash,
+            buyer: access.buyer,
+            price_lamports,
+            payment_mint: Pubkey::default(),
+            price_paid: price_lamports,
+            exclusivity: commit.exclusivity,
+            buyer_count: commit.buyer_count,
+            session_key: Pubkey::default(),
+            subscribed: false,
+        });
+        
+        check_invariants!(ctx.accounts.signal_access, ctx.accounts.signal_commit, ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Close an auction that ended without a valid bid; the signal can then be bought at its list price (authority)
+    pub fn end_unsold_auction(ctx: Context<EndUnsoldAuction>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let clock = Clock::get()?;
+        
+        require!(clock.unix_timestamp >= auction.reveal_ends_at, AgentAlphaError::AuctionNotEnded);
+        require!(!auction.settled, AgentAlphaError::AuctionAlreadySettled);
+        require!(auction.highest_bidder == Pubkey::default(), AgentAlphaError::AuctionHasWinner);
+        auction.settled = true;
+        ctx.accounts.signal_commit.auctioned = false;
+        
+        emit!(AuctionSettled {
+            event_seq: event_seq(),
+            auction: auction.key(),
+            signal: auction.signal,
+            winner: Pubkey::default(),
+            price_lamports: 0,
+        });
+        
+        Ok(())
+    }
+
+    /// Reclaim a bid's deposit and rent after the reveal phase (bidder)
+    /// The leading bid stays locked for settle_auction unless the signal closed before it settled
+    pub fn withdraw_bid(ctx: Context<WithdrawBid>) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+        let commit = &ctx.accounts.signal_commit;
+        let clock = Clock::get()?;
+        
+        require!(clock.unix_timestamp >= auction.reveal_ends_at, AgentAlphaError::AuctionNotEnded);
+        require!(
+            ctx.accounts.bidder.key() != auction.highest_bidder || commit.outcome_recorded || commit.expired,
+            AgentAlphaError::WinningBidLocked
+        );
+        
+        emit!(BidWithdrawn {
+            event_seq: event_seq(),
+            auction: auction.key(),
+            bidder: ctx.accounts.bidder.key(),
+            deposit_lamports: ctx.accounts.bid.deposit_lamports,
+        });
+        
+        Ok(())
+    }
+
+    /// Register as an integrator: once the admin approves it, purchases that pass this account
+    /// pay it `fee_bps` of the price out of the provider's SOL proceeds (up to MAX_INTEGRATOR_FEE_BPS)
+    pub fn register_integrator(
+        ctx: Context<RegisterIntegrator>,
+        fee_bps: u16,
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_INTEGRATOR_FEE_BPS, AgentAlphaError::InvalidIntegratorFee);
+        let integrator = &mut ctx.accounts.integrator;
+        integrator.authority = ctx.accounts.authority.key();
+        integrator.fee_bps = fee_bps;
+        integrator.registered_at = Clock::get()?.unix_timestamp;
+        integrator.bump = ctx.bumps.integrator;
+        integrator.approved = false;
+        
+        emit!(IntegratorFeeChanged {
+            event_seq: event_seq(),
+            integrator: integrator.key(),
+            fee_bps,
+        });
+        
+        Ok(())
+    }
+
+    /// Approve or revoke an integrator (admin only)
+    /// Only approved integrators can be passed to purchases, so buyers can't register one to
+    /// rebate themselves part of the provider's price
+    pub fn set_integrator_approved(
+        ctx: Context<ApproveIntegrator>,
+        approved: bool,
+    ) -> Result<()> {
+        let integrator = &mut ctx.accounts.integrator;
+        integrator.approved = approved;
+        
+        emit!(IntegratorApprovalChanged {
+            event_seq: event_seq(),
+            integrator: integrator.key(),
+            approved,
+        });
+        
+        Ok(())
+    }
+
+    /// Change an integrator's fee share (integrator authority)
+    pub fn set_integrator_fee(
+        ctx: Context<UpdateIntegrator>,
+        fee_bps: u16,
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_INTEGRATOR_FEE_BPS, AgentAlphaError::InvalidIntegratorFee);
+        let integrator = &mut ctx.accounts.integrator;
+        integrator.fee_bps = fee_bps;
+        
+        emit!(IntegratorFeeChanged {
+            event_seq: event_seq(),
+            integrator: integrator.key(),
+            fee_bps,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw the fees an integrator has accrued (integrator authority)
+    pub fn claim_integrator_fees(ctx: Context<UpdateIntegrator>) -> Result<()> {
+        let integrator = &mut ctx.accounts.integrator;
+        let amount = std::mem::take(&mut integrator.fees_unclaimed);
+        
+        require!(amount > 0, AgentAlphaError::NoIntegratorFees);
+        transfer_from_program_account(
+            &integrator.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            amount,
+        )?;
+        
+        emit!(IntegratorFeesClaimed {
+            event_seq: event_seq(),
+            integrator: integrator.key(),
+            amount,
+        });
+        
+        check_invariants!(ctx.accounts.integrator);
+        
+        Ok(())
+    }
+
+    /// Offer subscriptions at a price per SUBSCRIPTION_PERIOD_DAYS for TIER_UNLIMITED signals,
+    /// marked up by the exclusivity multipliers for higher tiers (0 stops new subscriptions)
+    pub fn set_subscription_price(
+        ctx: Context<UpdateProvider>,
+        price_lamports: u64,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        provider.subscription_price_lamports = price_lamports;
+        provider.layout_version = PROVIDER_LAYOUT_VERSION;
+        provider.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Let subscribers renew once at the rate they last paid after a price increase (delisting
+    /// protection); applies to rates paid from now on
+    pub fn set_subscription_price_lock(
+        ctx: Context<UpdateProvider>,
+        lock_renewal: bool,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        provider.subscription_price_lock = lock_renewal;
+        provider.layout_version = PROVIDER_LAYOUT_VERSION;
+        provider.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Subscribe to a provider for `days`, or renew/change an existing subscription
+    /// A subscription covers every signal up to its tier: purchase_signal with the Subscription
+    /// grants access without payment. Renewals extend from the current expiry; changing tier
+    /// first converts the remaining time at the ratio of the rate last paid to the new tier's price.
+    /// An active subscriber renewing its tier for up to a period pays the locked rate while a
+    /// locked renewal remains. Payments less the protocol fee are escrowed in the subscription and
+    /// paid to the provider as the time they cover passes (see release_subscription_escrow).
+    pub fn subscribe(
+        ctx: Context<Subscribe>,
+        tier: u8,
+        days: u16,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        // The provider is paid what the subscription has earned so far; the new payment is earned from now
+        release_earned_subscription(
+            &mut ctx.accounts.subscription,
+            &mut ctx.accounts.provider,
+            &ctx.accounts.provider_authority.to_account_info(),
+            &ctx.accounts.config,
+            clock.unix_timestamp,
+        )?;
+        let provider = &ctx.accounts.provider;
+        
+        require!(tier <= TIER_EXCLUSIVE, AgentAlphaError::InvalidExclusivity);
+        require!((1..=MAX_SUBSCRIPTION_DAYS).contains(&days), AgentAlphaError::InvalidSubscriptionLength);
+        require!(
+            provider.is_accepting_purchases(clock.unix_timestamp),
+            AgentAlphaError::ProviderNotAcceptingPurchases
+        );
+        let current_price = provider.subscription_price(tier).ok_or(AgentAlphaError::SubscriptionsNotOffered)?;
+        
+        let subscription = &mut ctx.accounts.subscription;
+        if subscription.buyer == Pubkey::default() {
+            subscription.buyer = ctx.accounts.buyer.key();
+            subscription.provider = provider.key();
+            subscription.started_at = clock.unix_timestamp;
+            subscription.bump = ctx.bumps.subscription;
+        }
+        let remaining = (subscription.expires_at - clock.unix_timestamp).max(0);
+        // A locked renewal covers at most one period
+        let renews_period = tier == subscription.tier && days as u64 <= SUBSCRIPTION_PERIOD_DAYS;
+        let (period_price, at_locked_rate) = if remaining > 0 && renews_period {
+            subscription.renewal_price(current_price)
+        } else {
+            (current_price, false)
+        };
+        // Pro-rate the unused time onto the new tier, at the rate it was paid for
+        if remaining > 0 && tier != subscription.tier {
+            let old_price = match subscription.locked_period_price {
+                0 => provider.subscription_price(subscription.tier).unwrap_or(period_price),
+                locked => locked,
+            };
+            subscription.expires_at =
+                clock.unix_timestamp + (remaining as u128 * old_price as u128 / period_price as u128) as i64;
+        }
+        subscription.record_rate(period_price, at_locked_rate, provider.subscription_price_lock());
+        subscription.tier = tier;
+        subscription.expires_at = subscription.expires_at.max(clock.unix_timestamp) + days as i64 * 86400;
+        
+        let lamports = (period_price as u128 * days as u128 / SUBSCRIPTION_PERIOD_DAYS as u128) as u64;
+        let protocol_fee = ctx.accounts.config.protocol_fee(lamports);
+        let treasury = if protocol_fee > 0 {
+            ctx.accounts.config.treasury_account(ctx.accounts.treasury.as_ref())?
+        } else {
+            ctx.accounts.provider_authority.to_account_info()
+        };
+        let payouts = [
+            (treasury, protocol_fee),
+            (subscription.to_account_info(), lamports - protocol_fee),
+        ];
+        for (to, amount) in payouts {
+            if amount == 0 {
+                continue;
+            }
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to,
+                    },
+                ),
+                amount,
+            )?;
+        }
+        subscription.escrow_lamports += lamports - protocol_fee;
+        subscription.total_paid += lamports;
+        
+        emit!(Subscribed {
+            event_seq: event_seq(),
+            provider: subscription.provider,
+            buyer: subscription.buyer,
+            tier,
+            days,
+            lamports,
+            expires_at: subscription.expires_at,
+        });
+        
+        check_invariants!(ctx.accounts.subscription, ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Fund the caller's BuyerCredit, the balance auto-renewals are paid from
+    pub fn deposit_buyer_credit(
+        ctx: Context<DepositBuyerCredit>,
+        lamports: u64,
+    ) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.buyer_credit.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+        
+        let credit = &mut ctx.accounts.buyer_credit;
+        if credit.buyer == Pubkey::default() {
+            credit.buyer = ctx.accounts.buyer.key();
+            credit.bump = ctx.bumps.buyer_credit;
+        }
+        credit.total_deposited += lamports;
+        
+        emit!(BuyerCreditChanged {
+            event_seq: event_seq(),
+            buyer: credit.buyer,
+            balance_lamports: spendable_lamports(&credit.to_account_info())?,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw from the caller's BuyerCredit; renewals it can no longer cover stop
+    pub fn withdraw_buyer_credit(
+        ctx: Context<WithdrawBuyerCredit>,
+        lamports: u64,
+    ) -> Result<()> {
+        let credit = &ctx.accounts.buyer_credit;
+        require!(lamports <= spendable_lamports(&credit.to_account_info())?, AgentAlphaError::InsufficientBuyerCredit);
+        transfer_from_program_account(&credit.to_account_info(), &ctx.accounts.buyer.to_account_info(), lamports)?;
+        
+        emit!(BuyerCreditChanged {
+            event_seq: event_seq(),
+            buyer: credit.buyer,
+            balance_lamports: spendable_lamports(&credit.to_account_info())?,
+        });
+        
+        Ok(())
+    }
+
+    /// Let renew_subscription extend this subscription from the caller's BuyerCredit, spending at
+    /// most `cap_lamports` per RENEWAL_MONTH_SECS month (0 revokes auto-renewal)
+    pub fn set_auto_renew(
+        ctx: Context<SetAutoRenew>,
+        cap_lamports: u64,
+    ) -> Result<()> {
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.renewal_cap_lamports = cap_lamports;
+        
+        emit!(AutoRenewChanged {
+            event_seq: event_seq(),
+            provider: subscription.provider,
+            buyer: subscription.buyer,
+            cap_lamports,
+        });
+        
+        Ok(())
+    }
+
+    /// Renew an auto-renewing subscription for SUBSCRIPTION_PERIOD_DAYS, paid from the buyer's
+    /// BuyerCredit (anyone can crank). Only from AUTO_RENEW_WINDOW_SECS before expiry until as
+    /// long after, and within the buyer's monthly cap. The rate is read from the subscription:
+    /// its locked rate while a locked renewal remains, else the tier's current price.
+    pub fn renew_subscription(ctx: Context<RenewSubscription>) -> Result<()> {
+        let clock = Clock::get()?;
+        release_earned_subscription(
+            &mut ctx.accounts.subscription,
+            &mut ctx.accounts.provider,
+            &ctx.accounts.provider_authority.to_account_info(),
+            &ctx.accounts.config,
+            clock.unix_timestamp,
+        )?;
+        let provider = &ctx.accounts.provider;
+        let subscription = &mut ctx.accounts.subscription;
+        
+        require!(subscription.renewal_due(clock.unix_timestamp), AgentAlphaError::RenewalNotDue);
+        require!(
+            provider.is_accepting_purchases(clock.unix_timestamp),
+            AgentAlphaError::ProviderNotAcceptingPurchases
+        );
+        let current_price = provider
+            .subscription_price(subscription.tier)
+            .ok_or(AgentAlphaError::SubscriptionsNotOffered)?;
+        let (lamports, at_locked_rate) = subscription.renewal_price(current_price);
+        subscription.authorize_renewal(lamports, clock.unix_timestamp)?;
+        require!(
+            lamports <= spendable_lamports(&ctx.accounts.buyer_credit.to_account_info())?,
+            AgentAlphaError::InsufficientBuyerCredit
+        );
+        subscription.expires_at =
+            subscription.expires_at.max(clock.unix_timestamp) + SUBSCRIPTION_PERIOD_DAYS as i64 * 86400;
+        subscription.total_paid += lamports;
+        subscription.record_rate(lamports, at_locked_rate, provider.subscription_price_lock());
+        
+        let protocol_fee = ctx.accounts.config.protocol_fee(lamports);
+        let treasury = if protocol_fee > 0 {
+            ctx.accounts.config.treasury_account(ctx.accounts.treasury.as_ref())?
+        } else {
+            ctx.accounts.provider_authority.to_account_info()
+        };
+        let payouts = [
+            (treasury, protocol_fee),
+            (subscription.to_account_info(), lamports - protocol_fee),
+        ];
+        let credit = ctx.accounts.buyer_credit.to_account_info();
+        for (to, amount) in payouts {
+            if amount > 0 {
+                transfer_from_program_account(&credit, &to, amount)?;
+            }
+        }
+        subscription.escrow_lamports += lamports - protocol_fee;
+        ctx.accounts.buyer_credit.total_spent += lamports;
+        
+        emit!(SubscriptionRenewed {
+            event_seq: event_seq(),
+            provider: subscription.provider,
+            buyer: subscription.buyer,
+            tier: subscription.tier,
+            lamports,
+            expires_at: subscription.expires_at,
+            spent_this_month: subscription.renewal_spent,
+            at_locked_rate,
+        });
+        
+        check_invariants!(ctx.accounts.subscription, ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Share a subscription with `seat` (a teammate's or gift recipient's key): it can take the
+    /// subscription's signals through purchase_signal under its own SignalAccess
+    pub fn add_subscription_seat(
+        ctx: Context<UpdateSubscriptionSeats>,
+        seat: Pubkey,
+    ) -> Result<()> {
+        let subscription = &mut ctx.accounts.subscription;
+        require!(!subscription.has_member(&seat), AgentAlphaError::SeatAlreadyAdded);
+        require!(subscription.seats.len() < MAX_SUBSCRIPTION_SEATS, AgentAlphaError::TooManySeats);
+        subscription.seats.push(seat);
+        
+        emit!(SubscriptionSeatChanged {
+            event_seq: event_seq(),
+            provider: subscription.provider,
+            buyer: subscription.buyer,
+            seat,
+            added: true,
+        });
+        
+        check_invariants!(ctx.accounts.subscription);
+        
+        Ok(())
+    }
+
+    /// Stop sharing a subscription with `seat`; signals it already took stay accessible
+    pub fn remove_subscription_seat(
+        ctx: Context<UpdateSubscriptionSeats>,
+        seat: Pubkey,
+    ) -> Result<()> {
+        let subscription = &mut ctx.accounts.subscription;
+        let index = subscription
+            .seats
+            .iter()
+            .position(|s| *s == seat)
+            .ok_or(AgentAlphaError::SeatNotFound)?;
+        subscription.seats.swap_remove(index);
+        
+        emit!(SubscriptionSeatChanged {
+            event_seq: event_seq(),
+            provider: subscription.provider,
+            buyer: subscription.buyer,
+            seat,
+            added: false,
+        });
+        
+        Ok(())
+    }
+
+    /// Pay the provider the part of a subscription's escrow it has earned (anyone can crank)
+    pub fn release_subscription_escrow(ctx: Context<SettleSubscriptionEscrow>) -> Result<()> {
+        let released = release_earned_subscription(
+            &mut ctx.accounts.subscription,
+            &mut ctx.accounts.provider,
+            &ctx.accounts.provider_authority.to_account_info(),
+            &ctx.accounts.config,
+            Clock::get()?.unix_timestamp,
+        )?;
+        require!(released > 0, AgentAlphaError::NothingEarned);
+        
+        check_invariants!(ctx.accounts.subscription, ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// End a subscription to a suspended or fraudulent provider and refund the buyer its unearned
+    /// escrow (anyone can crank). A suspended provider is paid what was earned up to its
+    /// suspension; once fraud is proven the whole escrow goes back to the buyer.
+    pub fn refund_subscription(ctx: Context<SettleSubscriptionEscrow>) -> Result<()> {
+        let clock = Clock::get()?;
+        let provider = &ctx.accounts.provider;
+        
+        require!(provider.suspended || provider.fraud_proven, AgentAlphaError::SubscriptionNotRefundable);
+        release_earned_subscription(
+            &mut ctx.accounts.subscription,
+            &mut ctx.accounts.provider,
+            &ctx.accounts.provider_authority.to_account_info(),
+            &ctx.accounts.config,
+            clock.unix_timestamp,
+        )?;
+        
+        let subscription = &mut ctx.accounts.subscription;
+        let lamports = std::mem::take(&mut subscription.escrow_lamports);
+        transfer_from_program_account(&subscription.to_account_info(), &ctx.accounts.buyer.to_account_info(), lamports)?;
+        subscription.expires_at = subscription.expires_at.min(clock.unix_timestamp);
+        
+        emit!(SubscriptionRefunded {
+            event_seq: event_seq(),
+            provider: subscription.provider,
+            buyer: subscription.buyer,
+            lamports,
+        });
+        
+        check_invariants!(ctx.accounts.subscription, ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Offer prepaid bundles of signals at a discount off the base price (0 stops new bundles)
+    /// Credits already bought stay spendable
+    pub fn set_bundle_discount(
+        ctx: Context<UpdateProvider>,
+        discount_bps: u16,
+    ) -> Result<()> {
+        require!(discount_bps <= MAX_BUNDLE_DISCOUNT_BPS, AgentAlphaError::InvalidBundleDiscount);
+        let provider = &mut ctx.accounts.provider;
+        provider.bundle_discount_bps = discount_bps;
+        provider.layout_version = PROVIDER_LAYOUT_VERSION;
+        provider.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Prepay for `signals` future signals from a provider at its bundle discount
+    /// Each credit is spent by a purchase_signal that passes the Credits account, for one
+    /// TIER_UNLIMITED signal sold at a flat price, in a category priced no higher than the base price.
+    /// Payment goes to the provider as with subscribe.
+    pub fn purchase_bundle(
+        ctx: Context<PurchaseBundle>,
+        signals: u32,
+    ) -> Result<()> {
+        let provider = &ctx.accounts.provider;
+        let clock = Clock::get()?;
+        
+        require!((1..=MAX_BUNDLE_SIGNALS).contains(&signals), AgentAlphaError::InvalidBundleSize);
+        require!(
+            provider.is_accepting_purchases(clock.unix_timestamp),
+            AgentAlphaError::ProviderNotAcceptingPurchases
+        );
+        let lamports = provider
+            .bundle_price(signals, clock.unix_timestamp)
+            .ok_or(AgentAlphaError::BundlesNotOffered)?;
+        
+        let credits = &mut ctx.accounts.credits;
+        if credits.buyer == Pubkey::default() {
+            credits.buyer = ctx.accounts.buyer.key();
+            credits.provider = provider.key();
+            credits.bump = ctx.bumps.credits;
+        }
+        credits.remaining += signals;
+        credits.total_purchased += signals;
+        credits.total_paid += lamports;
+        credits.last_purchased_at = clock.unix_timestamp;
+        
+        let protocol_fee = ctx.accounts.config.protocol_fee(lamports);
+        let treasury = if protocol_fee > 0 {
+            ctx.accounts.config.treasury_account(ctx.accounts.treasury.as_ref())?
+        } else {
+            ctx.accounts.provider_authority.to_account_info()
+        };
+        // New providers have part of their proceeds held back in the provider account to vest
+        let held = ctx.accounts.config.vesting_holdback(provider.created_at, lamports - protocol_fee, clock.unix_timestamp);
+        let payouts = [
+            (treasury, protocol_fee),
+            (ctx.accounts.provider.to_account_info(), held),
+            (ctx.accounts.provider_authority.to_account_info(), lamports - protocol_fee - held),
+        ];
+        for (to, amount) in payouts {
+            if amount == 0 {
+                continue;
+            }
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to,
+                    },
+                ),
+                amount,
+            )?;
+        }
+        ctx.accounts.provider.vesting_held_lamports += held;
+        
+        emit!(BundlePurchased {
+            event_seq: event_seq(),
+            provider: credits.provider,
+            buyer: credits.buyer,
+            signals,
+            lamports,
+            remaining: credits.remaining,
+        });
+        
+        check_invariants!(ctx.accounts.credits, ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Price signals of one of the provider's categories separately from its base price (0 clears it)
+    /// A new category price starts from the base price buyers pay today; like update_provider,
+    /// decreases apply immediately and increases after PRICE_NOTICE_SECS. Clearing a category
+    /// price returns its signals to the base price at once. Buyers paying in the price mint pay
+    /// price_token_amount scaled by the same ratio to the base price.
+    pub fn set_category_price(
+        ctx: Context<SetCategoryPrice>,
+        category: u8,
+        price_lamports: u64,
+    ) -> Result<()> {
+        let provider = &mut ctx.accounts.provider;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(
+            provider.categories.is_empty() || provider.categories.contains(&category),
+            AgentAlphaError::InvalidCategory
+        );
+        if !provider.has_layout(8) {
+            provider.category_prices = Vec::new();
+            provider.layout_version = PROVIDER_LAYOUT_VERSION;
+        }
+        
+        let base_price = provider.effective_price_lamports(now);
+        let prices = &mut provider.category_prices;
+        let entry = match prices.iter().position(|p| p.category == category) {
+            _ if price_lamports == 0 => {
+                prices.retain(|p| p.category != category);
+                CategoryPrice { category, price_lamports: base_price, ..Default::default() }
+            }
+            Some(i) => {
+                prices[i].reprice(price_lamports, now);
+                prices[i]
+            }
+            None => {
+                require!(prices.len() < MAX_CATEGORY_PRICES, AgentAlphaError::TooManyCategoryPrices);
+                let mut entry = CategoryPrice { category, price_lamports: base_price, ..Default::default() };
+                entry.reprice(price_lamports, now);
+                prices.push(entry);
+                entry
+            }
+        };
+        provider.updated_at = now;
+        
+        emit!(CategoryPriceChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            category,
+            price_lamports: entry.price_lamports,
+            scheduled_price_lamports: entry.scheduled_price_lamports,
+            effective_at: entry.price_effective_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Charge new signals a share of buyers' realized return instead of a flat price (0 = flat pricing)
+    /// Buyers escrow the fee on a 100% return of their notional; the fee on the actual return is
+    /// paid out of it at settlement and the rest goes back to the buyer
+    pub fn set_performance_fee(
+        ctx: Context<UpdateProvider>,
+        perf_fee_bps: u16,
+    ) -> Result<()> {
+        require!(perf_fee_bps <= MAX_PERF_FEE_BPS, AgentAlphaError::InvalidPerformanceFee);
+        let provider = &mut ctx.accounts.provider;
+        provider.perf_fee_bps = perf_fee_bps;
+        provider.layout_version = PROVIDER_LAYOUT_VERSION;
+        provider.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Pay out a performance-fee purchase's escrow once the signal is settled (anyone can crank)
+    /// The provider gets the fee record_outcome set per unit of notional, less the protocol fee
+    /// and any vesting holdback; the buyer gets the rest. Only Config.settlement_oracle can set
+    /// that rate, so neither party chooses the split; a signal settled any other way (social
+    /// settlement, voiding) or expired unrevealed charges nothing.
+    pub fn settle_performance_fee(ctx: Context<SettlePerformanceFee>) -> Result<()> {
+        let commit = &ctx.accounts.signal_commit;
+        let access = &mut ctx.accounts.signal_access;
+        let clock = Clock::get()?;
+        
+        require!(access.notional_lamports > 0, AgentAlphaError::NotPerformanceFeePurchase);
+        require!(commit.outcome_recorded || commit.expired, AgentAlphaError::OutcomeNotRecorded);
+        require!(commit.outcome != OUTCOME_VOIDED, AgentAlphaError::SignalVoided);
+        let escrow = std::mem::take(&mut access.escrow_lamports);
+        require!(escrow > 0, AgentAlphaError::NothingEscrowed);
+        
+        // The rate can never exceed what the signal was sold under
+        let rate_bps = commit.perf_fee_rate_bps.min(commit.performance_fee_bps());
+        let fee = (access.notional_lamports as u128 * rate_bps as u128 / 10000) as u64;
+        let fee = if commit.expired { 0 } else { fee.min(escrow) };
+        access.price_paid = fee;
+        let from = access.to_account_info();
+        
+        let protocol_fee = ctx.accounts.config.protocol_fee(fee);
+        if protocol_fee > 0 {
+            let treasury = ctx.accounts.config.treasury_account(ctx.accounts.treasury.as_ref())?;
+            transfer_from_program_account(&from, &treasury, protocol_fee)?;
+        }
+        let provider = &mut ctx.accounts.provider;
+        let held = ctx.accounts.config.vesting_holdback(provider.created_at, fee - protocol_fee, clock.unix_timestamp);
+        if held > 0 {
+            transfer_from_program_account(&from, &provider.to_account_info(), held)?;
+            provider.vesting_held_lamports += held;
+        }
+        transfer_from_program_account(&from, &ctx.accounts.provider_authority, fee - protocol_fee - held)?;
+        transfer_from_program_account(&from, &ctx.accounts.buyer, escrow - fee)?;
+        
+        emit!(PerformanceFeeSettled {
+            event_seq: event_seq(),
+            signal: access.signal,
+            buyer: access.buyer,
+            notional_lamports: access.notional_lamports,
+            return_bps: commit.return_bps,
+            fee_lamports: fee,
+            returned_lamports: escrow - fee,
+        });
+        
+        check_invariants!(ctx.accounts.signal_access, ctx.accounts.provider);
+        
+        Ok(())
+    }
+
+    /// Rewrite a Provider created by the original deployment in the current layout (anyone can
+    /// crank; the payer funds the larger account). See the migration module.
+    pub fn migrate_provider(ctx: Context<MigrateAccount>) -> Result<()> {
+        let info = ctx.accounts.account.to_account_info();
+        let original: OriginalProvider = migration::read_original(
+            &info.try_borrow_data()?,
+            Provider::DISCRIMINATOR,
+            OriginalProvider::SIZE,
+        )?;
+        let provider = Provider::from_original(original, Clock::get()?.unix_timestamp)?;
+        
+        migration::grow_account(&info, &ctx.accounts.payer, &ctx.accounts.system_program, Provider::SIZE)?;
+        provider.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        
+        emit!(AccountMigrated {
+            event_seq: event_seq(),
+            account: info.key(),
+            layout_version: PROVIDER_LAYOUT_VERSION,
+        });
+        
+        Ok(())
+    }
+
+    /// Rewrite a SignalCommit created by the original deployment in the current layout (anyone can
+    /// crank; the payer funds the larger account)
+    pub fn migrate_signal_commit(ctx: Context<MigrateAccount>) -> Result<()> {
+        let info = ctx.accounts.account.to_account_info();
+        let original: OriginalSignalCommit = migration::read_original(
+            &info.try_borrow_data()?,
+            SignalCommit::DISCRIMINATOR,
+            OriginalSignalCommit::SIZE,
+        )?;
+        let commit = SignalCommit::from_original(original)?;
+        
+        migration::grow_account(&info, &ctx.accounts.payer, &ctx.accounts.system_program, SignalCommit::SIZE)?;
+        commit.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        
+        emit!(AccountMigrated {
+            event_seq: event_seq(),
+            account: info.key(),
+            layout_version: SIGNAL_COMMIT_LAYOUT_VERSION,
+        });
+        
+        Ok(())
+    }
+}
+
+// ==================== CONSTANTS ====================
+
+/// Account layout policy: every SIZE ends with an unused reserve that later fields grow into.
+/// Provider and SignalCommit also record the layout version they were written with; new fields
+/// are appended after layout_version under a bumped version, so an older account reads them as
+/// zero from its reserve and their accessors check has_layout before trusting them.
+/// Accounts from the original deployment predate layout versions and were laid out before
+/// fields were inserted mid-struct; they are rewritten once by migrate_provider and
+/// migrate_signal_commit (see the migration module).
+pub const ACCOUNT_RESERVE: usize = 32;
+pub const ACCOUNT_RESERVE_LARGE: usize = 64; // Long-lived accounts that keep gaining fields
+pub const PROVIDER_LAYOUT_VERSION: u8 = 13;
+pub const SIGNAL_COMMIT_LAYOUT_VERSION: u8 = 4;
+
+// Layouts only grow: sizes at their current layout versions are floors for every later one
+const _: () = assert!(Provider::SIZE >= 1772 && PROVIDER_LAYOUT_VERSION >= 1);
+const _: () = assert!(SignalCommit::SIZE >= 715 && SIGNAL_COMMIT_LAYOUT_VERSION >= 1);
+const _: () = assert!(Config::SIZE >= 393);
+const _: () = assert!(SignalAccess::SIZE >= 211);
+
+/// Quote currencies for revealed prices
+pub const QUOTE_USD: u8 = 0;
+pub const QUOTE_SOL: u8 = 1;
+pub const QUOTE_BTC: u8 = 2;
+pub const QUOTE_ETH: u8 = 3;
+pub const QUOTE_SPL: u8 = 4;
+
+/// Number of settlements kept in a ProviderCurve
+pub const CURVE_POINTS: usize = 64;
+
+/// Longest signal timeframe, used to bound how long settlements can take
+pub const MAX_TIMEFRAME_SECS: i64 = 72 * 3600;
+
+/// Time after a bounty's entries could have settled in which they can still be scored
+pub const BOUNTY_SCORING_SECS: i64 = 24 * 3600;
+
+/// Warning codes for business-level rejections that succeed as no-ops
+pub const WARN_AMENDMENT_TOO_LATE: u16 = 1;    // Parent already settled or past its window
+pub const WARN_BOUNTY_ENTRY_NOT_BEST: u16 = 2; // Scored entry did not beat the current best
+pub const WARN_AMENDMENT_SL_CROSSED: u16 = 3;  // Amended SL is at or past the parent's mark, or no fresh mark
+pub const WARN_DUPLICATE_PURCHASE: u16 = 4;    // Retried purchase intent already completed, nothing charged
+
+/// Oldest mark an SL amendment can be checked against
+pub const MAX_AMENDMENT_MARK_AGE_SECS: i64 = 300;
+
+/// Notice slots in a buyer Mailbox
+pub const MAILBOX_SLOTS: usize = 16;
+
+/// Mailbox notice kinds
+pub const NOTICE_NEW_SIGNAL: u8 = 1;
+pub const NOTICE_OUTCOME: u8 = 2;
+
+/// Provider payload schemas
+pub const SCHEMA_JSON_V1: u8 = 0;
+pub const SCHEMA_BINARY_V2: u8 = 1;
+pub const SCHEMA_ENCRYPTED: u8 = 2;
+
+/// Maximum providers in a curated Bundle
+pub const MAX_BUNDLE_MEMBERS: usize = 8;
+
+/// Notice a provider must give before a price increase takes effect
+pub const PRICE_NOTICE_SECS: i64 = 7 * 24 * 3600;
+
+/// Notice before a new endpoint replaces the one buyers are routed to
+pub const ENDPOINT_CHANGE_DELAY_SECS: i64 = 48 * 3600;
+
+/// Delay between scheduling and applying a recovery authority rotation
+pub const RECOVERY_DELAY_SECS: i64 = 48 * 3600;
+
+/// Longest bidding or reveal phase of a signal auction
+pub const MAX_AUCTION_PHASE_SECS: i64 = 7 * 24 * 3600;
+
+/// Destinations a provider's withdrawal allowlist can hold
+pub const MAX_WITHDRAWAL_DESTINATIONS: usize = 3;
+
+/// What a scheduled withdrawal pays out
+pub const WITHDRAWAL_VESTED: u8 = 0;    // Vested held-back proceeds
+pub const WITHDRAWAL_ROYALTIES: u8 = 1; // Citation royalties
+pub const WITHDRAWAL_TOKEN: u8 = 2;     // Token sale proceeds from a price vault
+
+/// How long a SOL purchase's payment stays escrowed waiting for the buyer's delivery ack
+pub const PURCHASE_ESCROW_SECS: i64 = 72 * 3600;
+
+/// Lamports a provider deposits at commit to pay whoever settles or expires the signal
+pub const SETTLEMENT_DEPOSIT_LAMPORTS: u64 = 10_000;
+
+// ==================== HELPERS ====================
+
+#[cfg(target_os = "solana")]
+extern "C" {
+    fn sol_remaining_compute_units() -> u64;
+}
+
+/// Ordering key carried by every event: compute units consumed so far in the transaction
+/// The compute meter is shared by all instructions of a transaction and only decreases,
+/// so keys strictly increase across every event a transaction emits
+pub fn event_seq() -> u64 {
+    #[cfg(target_os = "solana")]
+    let remaining = unsafe { sol_remaining_compute_units() };
+    #[cfg(not(target_os = "solana"))]
+    let remaining = u64::MAX;
+    u64::MAX - remaining
+}
+
+/// Lamports a program account holds above its rent-exempt minimum
+pub fn spendable_lamports(info: &AccountInfo) -> Result<u64> {
+    Ok(info.lamports().saturating_sub(Rent::get()?.minimum_balance(info.data_len())))
+}
+
+/// Move lamports out of an account owned by this program
+pub fn transfer_from_program_account(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? -= lamports;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+/// A provider's vault a token purchase is refunded from, and the buyer's account for its mint
+pub struct TokenRefund<'a, 'info> {
+    pub provider: &'a Account<'info, Provider>,
+    pub vault: &'a Account<'info, TokenAccount>,
+    pub buyer_token: &'a Account<'info, TokenAccount>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+/// Return a voided signal's price to the buyer
+/// A SOL purchase is refunded from its escrow (less any the buyer reclaimed) and the rest from the
+/// provider authority; a token
+/// purchase from the provider's vault for its mint, which `token_refund` must supply.
+/// A withdrawal with a replacement credit also lets the buyer claim the provider's next signal free
+pub fn refund_purchase<'info>(
+    access: &mut Account<'info, SignalAccess>,
+    commit: &SignalCommit,
+    buyer: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_refund: Option<TokenRefund<'_, 'info>>,
+) -> Result<()> {
+    require!(commit.outcome == OUTCOME_VOIDED, AgentAlphaError::SignalNotVoided);
+    require!(!access.refunded, AgentAlphaError::AlreadyRefunded);
+    
+    access.refunded = true;
+    access.refund_bps = 0;
+    if access.payment_mint == Pubkey::default() {
+        let from_escrow = std::mem::take(&mut access.escrow_lamports);
+        transfer_from_program_account(&access.to_account_info(), buyer, from_escrow)?;
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: authority.clone(),
+                    to: buyer.clone(),
+                },
+            ),
+            // Escrow the buyer already reclaimed isn't paid again
+            access.price_paid - from_escrow - access.escrow_reclaimed,
+        )?;
+    } else {
+        let refund = token_refund.ok_or(AgentAlphaError::MissingPaymentAccounts)?;
+        require!(
+            refund.vault.mint == access.payment_mint
+                && refund.buyer_token.mint == access.payment_mint
+                && refund.buyer_token.owner == access.buyer,
+            AgentAlphaError::InvalidRefundAccount
+        );
+        let provider = refund.provider;
+        token::transfer(
+            CpiContext::new_with_signer(
+                refund.token_program.to_account_info(),
+                token::Transfer {
+                    from: refund.vault.to_account_info(),
+                    to: refund.buyer_token.to_account_info(),
+                    authority: provider.to_account_info(),
+                },
+                &[&[b"provider", provider.seed_authority.as_ref(), &[provider.bump]]],
+            ),
+            access.price_paid,
+        )?;
+    }
+    if commit.grants_replacement_credit() && access.price_paid > 0 {
+        access.credit_provider = commit.provider;
+    }
+    
+    emit!(PurchaseRefunded {
+        event_seq: event_seq(),
+        signal: access.signal,
+        buyer: access.buyer,
+        lamports: access.price_paid,
+        payment_mint: access.payment_mint,
+    });
+    
+    Ok(())
+}
+
+/// Apply the subscription's pending SLA credit and pause time, then pay the provider the escrow earned by `now`:
+/// a new provider's holdback into the provider account, the rest to its authority. Nothing is
+/// earned past a suspension, and nothing is paid once fraud is proven. Returns the lamports released.
+pub fn release_earned_subscription<'info>(
+    subscription: &mut Account<'info, Subscription>,
+    provider: &mut Account<'info, Provider>,
+    provider_authority: &AccountInfo<'info>,
+    config: &Config,
+    now: i64,
+) -> Result<u64> {
+    subscription.apply_credit(provider, now);
+    if provider.fraud_proven {
+        return Ok(0);
+    }
+    let until = if provider.suspended { now.min(provider.suspended_at) } else { now };
+    let earned = subscription.take_earned(until);
+    if earned == 0 {
+        return Ok(0);
+    }
+    let held = config.vesting_holdback(provider.created_at, earned, now);
+    let info = subscription.to_account_info();
+    transfer_from_program_account(&info, &provider.to_account_info(), held)?;
+    transfer_from_program_account(&info, provider_authority, earned - held)?;
+    provider.vesting_held_lamports += held;
+    
+    emit!(SubscriptionEscrowReleased {
+        event_seq: event_seq(),
+        provider: subscription.provider,
+        buyer: subscription.buyer,
+        lamports: earned,
+    });
+    
+    Ok(earned)
+}
+
+/// Pay out whatever a purchase still holds in escrow to the provider's authority
+pub fn release_escrow(access: &mut Account<SignalAccess>, provider_authority: &AccountInfo) -> Result<()> {
+    let lamports = std::mem::take(&mut access.escrow_lamports);
+    if lamports == 0 {
+        return Ok(());
+    }
+    transfer_from_program_account(&access.to_account_info(), provider_authority, lamports)?;
+    
+    emit!(PurchaseEscrowReleased {
+        event_seq: event_seq(),
+        signal: access.signal,
+        buyer: access.buyer,
+        lamports,
+    });
+    
+    Ok(())
+}
+
+/// Fold a settled signal into the provider's reputation, extending its equity curve and refreshing its badge
+pub fn apply_reputation(
+    provider: &mut Account<Provider>,
+    curve: Option<&mut Account<ProviderCurve>>,
+    commit: &SignalCommit,
+    min_confidence: u8,
+    now: i64,
+) {
+    if !provider.record_settlement(commit, min_confidence, now) {
+        return;
+    }
+    if let Some(curve) = curve {
+        curve.push(now, provider.total_return_bps);
+    }
+    let previous_badge = provider.badge;
+    if provider.refresh_badge(now) {
+        emit!(BadgeChanged {
+            event_seq: event_seq(),
+            provider: provider.key(),
+            previous_badge,
+            badge: provider.badge,
+        });
+    }
+}
+
+/// Pay lamports for a buyer: from their session's balance when a session key is purchasing, else from the buyer
+pub fn pay_as_buyer<'info>(
+    session: Option<&AccountInfo<'info>>,
+    buyer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    match session {
+        Some(session) => transfer_from_program_account(session, to, lamports),
+        None => system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: buyer.clone(),
+                    to: to.clone(),
+                },
+            ),
+            lamports,
+        ),
+    }
+}
+
+/// TP/SL of a baseline signal in `direction`, rounded down to the tick grid
+/// None when rounding collapses a level onto the entry or to zero
+pub fn baseline_levels(entry_cents: u64, tick_cents: u64, direction: u8) -> Option<(u64, u64)> {
+    let tp_move = entry_cents * BASELINE_TP_BPS / 10000;
+    let sl_move = entry_cents * BASELINE_SL_BPS / 10000;
+    let (tp_cents, sl_cents) = if direction == 0 {
+        (entry_cents + tp_move, entry_cents.checked_sub(sl_move)?)
+    } else {
+        (entry_cents.checked_sub(tp_move)?, entry_cents + sl_move)
+    };
+    let (tp_cents, sl_cents) = (tp_cents - tp_cents % tick_cents, sl_cents - sl_cents % tick_cents);
+    (tp_cents != entry_cents && sl_cents != entry_cents && tp_cents > 0 && sl_cents > 0).then_some((tp_cents, sl_cents))
+}
+
+/// Hash of the first slot at or after `slot` that produced a block, from SlotHashes sysvar data
+/// (u64 entry count, then (slot, hash) entries, most recent first)
+/// None before that slot has a hash, or once it has aged out of the sysvar's window
+pub fn slot_hash_at_or_after(data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    let count = u64::from_le_bytes(data.get(..8)?.try_into().ok()?) as usize;
+    let mut found = None;
+    for i in 0..count {
+        let entry = data.get(8 + i * 40..8 + (i + 1) * 40)?;
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().ok()?);
+        if entry_slot < slot {
+            return found;
+        }
+        found = Some(entry[8..].try_into().ok()?);
+        if entry_slot == slot {
+            return found;
+        }
+    }
+    // The window ends after `slot`: older entries that may have been the first are gone
+    None
+}
+
+/// Create a program-owned PDA of `space` bytes as Anchor's `init` does, with the payer funding rent
+/// An address that already holds lamports (anyone can send to it) is topped up, allocated and
+/// assigned instead, since create_account refuses it
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer = &[seeds];
+    if account.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+    
+    let shortfall = rent.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate { account_to_allocate: account.clone() },
+            signer,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign { account_to_assign: account.clone() },
+            signer,
+        ),
+        &crate::ID,
+    )
+}
+
+/// Move `lamports` from the buyer into their wrapped SOL token account and sync its balance
+pub fn wrap_sol<'info>(
+    buyer: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            system_program::Transfer {
+                from: buyer.clone(),
+                to: token_account.clone(),
+            },
+        ),
+        lamports,
+    )?;
+    token::sync_native(CpiContext::new(
+        token_program.clone(),
+        token::SyncNative { account: token_account.clone() },
+    ))
+}
+
+/// EXPIRED settles as BREAK_EVEN when the return is within the configured band of entry
+pub fn break_even_outcome(outcome: u8, return_bps: i32, config: &Config) -> u8 {
+    if outcome == 3 && config.break_even_bps > 0 && return_bps.unsigned_abs() <= config.break_even_bps as u32 {
+        OUTCOME_BREAK_EVEN
+    } else {
+        outcome
+    }
+}
+
+/// Median of a non-empty set of prices (the lower middle for an even count)
+pub fn median_cents(mut prices: Vec<u64>) -> u64 {
+    prices.sort_unstable();
+    prices[(prices.len() - 1) / 2]
+}
+
+/// Draw SOCIAL_PANEL_SIZE distinct reporters from `members`, None if there aren't enough
+/// Each seat rehashes the seed and picks among the members not drawn yet
+pub fn draw_panel(members: &[Pubkey], seed: [u8; 32]) -> Option<[Pubkey; SOCIAL_PANEL_SIZE]> {
+    if members.len() < SOCIAL_PANEL_SIZE {
+        return None;
+    }
+    let mut pool = members.to_vec();
+    let mut panel = [Pubkey::default(); SOCIAL_PANEL_SIZE];
+    let mut draw = seed;
+    for seat in panel.iter_mut() {
+        draw = Sha256::digest(draw).into();
+        let index = (u64::from_le_bytes(draw[..8].try_into().unwrap()) % pool.len() as u64) as usize;
+        *seat = pool.swap_remove(index);
+    }
+    Some(panel)
+}
+
+/// SHA256 Merkle root of the leaves (an odd node is paired with itself; all zeros when empty)
+pub fn merkle_root(mut level: Vec<[u8; 32]>) -> [u8; 32] {
+    if level.is_empty() { return [0u8; 32]; }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut hasher = Sha256::new();
+                hasher.update(pair[0]);
+                hasher.update(pair.get(1).unwrap_or(&pair[0]));
+                hasher.finalize().into()
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// Initialize a newly created provider account (shared by direct and platform batch registration)
+#[allow(clippy::too_many_arguments)]
+pub fn init_provider(
+    provider: &mut Account<Provider>,
+    authority: Pubkey,
+    name: String,
+    endpoint: String,
+    categories: Vec<u8>,
+    price_lamports: u64,
+    bump: u8,
+    now: i64,
+) -> Result<()> {
+    require!(name.len() <= 64, AgentAlphaError::NameTooLong);
+    require!(endpoint.len() <= 256, AgentAlphaError::EndpointTooLong);
+    require!(categories.len() <= 8, AgentAlphaError::TooManyCategories);
+    
+    provider.authority = authority;
+    provider.seed_authority = authority;
+    provider.name = name;
+    provider.endpoint = endpoint;
+    provider.pending_endpoint = String::new();
+    provider.endpoint_effective_at = 0;
+    provider.categories = categories;
+    provider.price_lamports = price_lamports;
+    provider.scheduled_price_lamports = 0;
+    provider.price_effective_at = 0;
+    provider.total_signals = 0;
+    provider.correct_signals = 0;
+    provider.total_return_bps = 0;
+    provider.last_settled_at = 0;
+    provider.accepting_purchases = true;
+    provider.pause_start = 0;
+    provider.pause_end = 0;
+    provider.exp_total_signals = 0;
+    provider.exp_correct_signals = 0;
+    provider.exp_total_return_bps = 0;
+    provider.low_conf_signals = 0;
+    provider.low_conf_correct_signals = 0;
+    provider.low_conf_total_return_bps = 0;
+    provider.max_open_signals = 0;
+    provider.open_signals = 0;
+    provider.fill_reports = 0;
+    provider.total_slippage_bps = 0;
+    provider.executions = 0;
+    provider.total_realized_return_bps = 0;
+    provider.total_realized_pnl_cents = 0;
+    provider.stake_per_confidence_lamports = 0;
+    provider.attestation_hash = [0u8; 32];
+    provider.tee_verified = false;
+    provider.expired_signals = 0;
+    provider.total_tp_progress_bps = 0;
+    provider.benchmarked_signals = 0;
+    provider.total_alpha_bps = 0;
+    provider.min_client_version = 0;
+    provider.payload_schema = SCHEMA_JSON_V1;
+    provider.suspended = false;
+    provider.suspended_at = 0;
+    provider.recovery_key = Pubkey::default();
+    provider.frozen = false;
+    provider.pending_authority = Pubkey::default();
+    provider.rotation_at = 0;
+    provider.curator_share_bps = 0;
+    provider.total_bonded = 0;
+    provider.curator_reward_index = 0;
+    provider.fraud_proven = false;
+    provider.latency_buckets = [0; 8];
+    provider.regime_signals = [0; 4];
+    provider.regime_correct = [0; 4];
+    provider.total_brier_bps = 0;
+    provider.badge = BADGE_NONE;
+    provider.relay_nonce = 0;
+    provider.health_probes = [EndpointProbe::default(); MAX_HEALTH_PROBES];
+    provider.health_probe_head = 0;
+    provider.payment_mint = Pubkey::default();
+    provider.price_mint = Pubkey::default();
+    provider.price_token_amount = 0;
+    provider.purchase_policy = Pubkey::default();
+    provider.gate_mint = Pubkey::default();
+    provider.gate_min_amount = 0;
+    provider.citation_royalties_unclaimed = 0;
+    provider.citation_royalties_earned = 0;
+    provider.vesting_held_lamports = 0;
+    provider.vesting_released_lamports = 0;
+    provider.platform = Pubkey::default();
+    provider.platform_fee_bps = 0;
+    provider.capacity_usd = 0;
+    provider.max_followers = 0;
+    provider.follower_count = 0;
+    provider.sla_min_signals = 0;
+    provider.sla_max_reveal_delay_secs = 0;
+    provider.sla_epoch = (now / SLA_EPOCH_SECS) as u32;
+    provider.sla_epoch_signals = 0;
+    provider.sla_epoch_late_reveals = 0;
+    provider.sla_met_epochs = 0;
+    provider.sla_missed_epochs = 0;
+    provider.created_at = now;
+    provider.updated_at = now;
+    provider.category_prices = Vec::new();
+    provider.bundle_discount_bps = 0;
+    provider.subscription_price_lock = false;
+    provider.subscriber_credit_secs = 0;
+    provider.subscriber_credit_through = 0;
+    provider.pending_withdrawal_kind = WITHDRAWAL_VESTED;
+    provider.paused_secs = 0;
+    provider.pause_set_at = now;
+    provider.last_pause_began_at = 0;
+    provider.paused_secs_before_last_pause = 0;
+    provider.bump = bump;
+    provider.layout_version = PROVIDER_LAYOUT_VERSION;
+    
+    emit!(ProviderRegistered {
+        event_seq: event_seq(),
+        provider: provider.key(),
+        authority: provider.authority,
+        name: provider.name.clone(),
+        endpoint: provider.endpoint.clone(),
+    });
+    
+    Ok(())
+}
+
+/// Initialize a new signal commit for the provider (shared by direct and relayed commits)
+#[allow(clippy::too_many_arguments)]
+pub fn open_commit(
+    commit: &mut SignalCommit,
+    provider: &mut Account<Provider>,
+    parent_signal: Option<Pubkey>,
+    signal_hash: [u8; 32],
+    experimental: bool,
+    exclusivity: u8,
+    max_buyers: u16,
+    category: u8,
+    bump: u8,
+    now: i64,
+) -> Result<()> {
+    require!(
+        provider.categories.is_empty() || provider.categories.contains(&category),
+        AgentAlphaError::InvalidCategory
+    );
+    
+    let max_buyers = match exclusivity {
+        TIER_UNLIMITED => 0,
+        TIER_LIMITED => {
+            require!(max_buyers > 0, AgentAlphaError::InvalidExclusivity);
+            max_buyers
+        }
+        TIER_EXCLUSIVE => 1,
+        _ => return err!(AgentAlphaError::InvalidExclusivity),
+    };
+    
+    require!(!provider.suspended, AgentAlphaError::ProviderSuspended);
+    require!(!provider.frozen, AgentAlphaError::ProviderFrozen);
+    require!(
+        provider.max_open_signals == 0 || provider.open_signals < provider.max_open_signals,
+        AgentAlphaError::TooManyOpenSignals
+    );
+    provider.open_signals += 1;
+    let provider_key = provider.key();
+    provider.roll_sla_epoch(provider_key, now);
+    provider.sla_epoch_signals = provider.sla_epoch_signals.saturating_add(1);
+    provider.record_activity(now);
+    
+    commit.provider = provider_key;
+    commit.signal_hash = signal_hash;
+    commit.committed_at = now;
+    commit.revealed = false;
+    commit.outcome_recorded = false;
+    commit.parent_signal = parent_signal;
+    commit.amended_sl_cents = None;
+    commit.bounty = None;
+    commit.experimental = experimental;
+    commit.expired = false;
+    commit.settlement_deposit = SETTLEMENT_DEPOSIT_LAMPORTS;
+    commit.exclusivity = exclusivity;
+    commit.max_buyers = max_buyers;
+    commit.buyer_count = 0;
+    commit.stake_lamports = 0;
+    commit.category = category;
+    commit.hash_schema = HASH_SCHEMA_V1;
+    commit.citations = [Pubkey::default(); MAX_CITATIONS];
+    commit.bump = bump;
+    commit.layout_version = SIGNAL_COMMIT_LAYOUT_VERSION;
+    commit.perf_fee_bps = provider.perf_fee_bps();
+    
+    emit!(SignalCommitted {
+        event_seq: event_seq(),
+        provider: commit.provider,
+        signal_hash,
+        committed_at: commit.committed_at,
+        experimental,
+        exclusivity,
+        max_buyers,
+        category,
+    });
+    
+    Ok(())
+}
+
+/// Message a provider authority signs to authorize a relayed commit
+/// prefix || provider || signal_hash || experimental || exclusivity || max_buyers (LE) || category || nonce (LE)
+/// where prefix is domain::RELAY_COMMIT_V2 (or domain::RELAY_COMMIT_V1 during the migration)
+#[allow(clippy::too_many_arguments)]
+pub fn relayed_commit_message(
+    prefix: &[u8],
+    provider: &Pubkey,
+    signal_hash: &[u8; 32],
+    experimental: bool,
+    exclusivity: u8,
+    max_buyers: u16,
+    category: u8,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(prefix.len() + 32 + 32 + 1 + 1 + 2 + 1 + 8);
+    message.extend_from_slice(prefix);
+    message.extend_from_slice(provider.as_ref());
+    message.extend_from_slice(signal_hash);
+    message.push(experimental as u8);
+    message.push(exclusivity);
+    message.extend_from_slice(&max_buyers.to_le_bytes());
+    message.push(category);
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+/// Check that the instruction before the current one is an ed25519 program verification
+/// of exactly one signature by `signer` over `message`, with all data inline
+pub fn verify_preceding_ed25519(instructions_sysvar: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = solana_instructions_sysvar::load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, AgentAlphaError::MissingSignatureVerification);
+    let ix = solana_instructions_sysvar::load_instruction_at_checked(current as usize - 1, instructions_sysvar)?;
+    require_keys_eq!(ix.program_id, solana_sdk_ids::ed25519_program::ID, AgentAlphaError::MissingSignatureVerification);
+    
+    // Header: signature count and padding, then one 14-byte offsets record
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, AgentAlphaError::InvalidSignatureVerification);
+    let field = |i: usize| u16::from_le_bytes([data[2 + i * 2], data[3 + i * 2]]) as usize;
+    let (pubkey_offset, message_offset, message_size) = (field(2), field(4), field(5));
+    // Signature, pubkey and message must all live in the ed25519 instruction itself
+    require!(
+        [field(1), field(3), field(6)].iter().all(|&ix_index| ix_index == u16::MAX as usize),
+        AgentAlphaError::InvalidSignatureVerification
+    );
+    require!(
+        data.get(pubkey_offset..pubkey_offset + 32) == Some(signer.as_ref())
+            && data.get(message_offset..message_offset + message_size) == Some(message),
+        AgentAlphaError::InvalidSignatureVerification
+    );
+    
+    Ok(())
+}
+
+/// Pay a keeper's per-action reward out of a signal's settlement deposit
+/// A surplus deposit goes to the config reserve; a shortfall is topped up from it (above rent)
+/// only for a `trusted` keeper (see Config::is_trusted_keeper), so anyone else can't drain it
+/// Returns the reward actually paid
+pub fn pay_keeper_reward(
+    commit: &AccountInfo,
+    config: &AccountInfo,
+    keeper: &AccountInfo,
+    deposit: u64,
+    reward_lamports: u64,
+    trusted: bool,
+) -> Result<u64> {
+    let from_deposit = deposit.min(reward_lamports);
+    transfer_from_program_account(commit, keeper, from_deposit)?;
+    transfer_from_program_account(commit, config, deposit - from_deposit)?;
+    
+    let reserve = config.lamports().saturating_sub(Rent::get()?.minimum_balance(config.data_len()));
+    let top_up = if trusted { (reward_lamports - from_deposit).min(reserve) } else { 0 };
+    transfer_from_program_account(config, keeper, top_up)?;
+    
+    Ok(from_deposit + top_up)
+}
+
+/// Confidence stakes: bonus paid on a winning signal and share forfeited on a losing one (bps of the stake)
+pub const STAKE_BONUS_BPS: u64 = 1000;
+pub const STAKE_FORFEIT_BPS: u64 = 5000;
+
+/// Oracle task queue: slots, task states and how long a claim holds before it can be requeued
+pub const TASK_QUEUE_SLOTS: usize = 64;
+pub const TASK_EMPTY: u8 = 0;
+pub const TASK_PENDING: u8 = 1;
+pub const TASK_CLAIMED: u8 = 2;
+pub const TASK_CLAIM_TIMEOUT_SECS: i64 = 10 * 60;
+
+/// Maximum rules in a PurchasePolicy
+pub const MAX_EMBARGO_RULES: usize = 8;
+
+/// ReputationV1 flag bits
+pub const REPUTATION_FLAG_SUSPENDED: u8 = 1;
+pub const REPUTATION_FLAG_FRAUD_PROVEN: u8 = 2;
+pub const REPUTATION_FLAG_TEE_VERIFIED: u8 = 4;
+
+/// Length of a provider SLA evaluation epoch
+pub const SLA_EPOCH_SECS: i64 = 30 * 24 * 3600;
+
+/// Activity is tracked per week over the last ACTIVITY_WEEKS weeks
+pub const ACTIVITY_WEEK_SECS: i64 = 7 * 24 * 3600;
+pub const ACTIVITY_WEEKS: u32 = 52;
+
+/// Window after a reveal in which its prices may be corrected once
+pub const CORRECTION_WINDOW_SECS: i64 = 5 * 60;
+
+/// Maximum age of a posted token USD rate used to price a token payment
+pub const MAX_RATE_AGE_SECS: i64 = 300;
+
+/// Upper bounds of the delivery-latency buckets (the last bucket is open-ended)
+pub const LATENCY_BUCKET_SECS: [i64; 7] = [1, 5, 15, 60, 300, 900, 3600];
+
+/// Volatility regimes of a settled signal's window and the range (high - low, bps of entry) bounding each
+pub const REGIME_UNKNOWN: u8 = 0;
+pub const REGIME_CALM: u8 = 1;
+pub const REGIME_NORMAL: u8 = 2;
+pub const REGIME_VOLATILE: u8 = 3;
+pub const REGIME_EXTREME: u8 = 4;
+pub const REGIME_RANGE_BPS: [u64; 3] = [200, 500, 1000];
+
+/// Provider badges and the thresholds for BRONZE, SILVER, GOLD and DIAMOND (all must be met)
+pub const BADGE_NONE: u8 = 0;
+pub const BADGE_BRONZE: u8 = 1;
+pub const BADGE_SILVER: u8 = 2;
+pub const BADGE_GOLD: u8 = 3;
+pub const BADGE_DIAMOND: u8 = 4;
+pub const BADGE_MIN_SIGNALS: [u64; 4] = [20, 50, 200, 500];
+pub const BADGE_MIN_HIT_RATE_BPS: [u64; 4] = [5000, 5500, 6000, 6500];
+pub const BADGE_MAX_BRIER_BPS: [u64; 4] = [3000, 2700, 2400, 2100];
+pub const BADGE_MIN_TENURE_SECS: [i64; 4] = [14 * 86400, 30 * 86400, 90 * 86400, 180 * 86400];
+
+/// Maximum levels in a laddered entry
+pub const MAX_ENTRY_LEVELS: usize = 3;
+
+/// Maximum signals revealed by one reveal_signals_batch call (keeps it within compute limits)
+pub const MAX_BATCH_REVEALS: usize = 10;
+
+/// Maximum buyers refunded by one withdraw_signal call (the rest use refund_voided_purchase)
+pub const MAX_BATCH_REFUNDS: usize = 10;
+
+/// Maximum providers created by one register_providers_batch call (each needs its own signer)
+pub const MAX_BATCH_REGISTRATIONS: usize = 8;
+
+/// Largest performance fee a provider can charge on buyers' realized return
+pub const MAX_PERF_FEE_BPS: u16 = 5000;
+
+/// Largest protocol fee the admin can set
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1000;
+
+/// Largest cut of a provider's SOL sales an onboarding platform can take
+pub const MAX_PLATFORM_FEE_BPS: u16 = 2000;
+
+/// Largest fee share an integrator can register
+pub const MAX_INTEGRATOR_FEE_BPS: u16 = 500;
+
+/// Signal exclusivity tiers and their price multipliers (bps of the provider's price)
+pub const TIER_UNLIMITED: u8 = 0;
+pub const TIER_LIMITED: u8 = 1;
+pub const TIER_EXCLUSIVE: u8 = 2;
+pub const TIER_LIMITED_PRICE_BPS: u64 = 20000;
+pub const TIER_EXCLUSIVE_PRICE_BPS: u64 = 50000;
+
+/// Subscription pricing period and the longest a single subscribe call can buy
+pub const SUBSCRIPTION_PERIOD_DAYS: u64 = 30;
+pub const MAX_SUBSCRIPTION_DAYS: u16 = 365;
+
+/// Auto-renewal runs from this long before a subscription expires until as long after
+pub const AUTO_RENEW_WINDOW_SECS: i64 = 86400;
+/// Period a buyer's auto-renewal cap applies to
+pub const RENEWAL_MONTH_SECS: i64 = 30 * 86400;
+
+/// Keys besides the subscriber a subscription can be shared with
+pub const MAX_SUBSCRIPTION_SEATS: usize = 5;
+
+/// Most signals a single purchase_bundle call can prepay, and the deepest bundle discount
+pub const MAX_BUNDLE_SIGNALS: u32 = 100;
+pub const MAX_BUNDLE_DISCOUNT_BPS: u16 = 5000;
+
+/// Benchmarks a signal can be measured against
+pub const BENCH_NONE: u8 = 0;
+pub const BENCH_SOL: u8 = 1;
+pub const BENCH_BTC: u8 = 2;
+pub const BENCH_ETH: u8 = 3;
+
+/// Oracle-reported outcome when TP and SL were both touched within one candle
+pub const OUTCOME_BOTH_TOUCHED: u8 = 4;
+
+/// Precedence applied to OUTCOME_BOTH_TOUCHED (worst case for the provider by default)
+pub const PRECEDENCE_WORST_CASE: u8 = 0;
+pub const PRECEDENCE_BEST_CASE: u8 = 1;
+pub const PRECEDENCE_EXPIRE: u8 = 2;
+
+/// Signal hash preimage formats; each can be disabled for new commits from Config
+/// LEGACY is the legacy program's preimage, accepted at reveal until it is disabled
+/// V2 is the V1 preimage under the domain::COMMIT_V2 tag
+pub const HASH_SCHEMA_V1: u8 = 1;
+pub const HASH_SCHEMA_LEGACY: u8 = 2;
+pub const HASH_SCHEMA_V2: u8 = 3;
+pub const MAX_HASH_SCHEMAS: usize = 4;
+
+/// V1 preimages are honoured for signals committed before this date (2027-04-01 00:00 UTC);
+/// clients commit V2 hashes from then on
+pub const HASH_SCHEMA_V1_SUNSET: i64 = 1_806_537_600;
+
+/// End of the migration to domain-tagged relay authorizations and bid hashes
+/// (2026-12-01 00:00 UTC): until then the untagged formats are accepted as well
+pub const UNTAGGED_MESSAGES_CUTOFF: i64 = 1_796_083_200;
+
+/// LEGACY preimages don't bind the TP/SL prices, so they are only honoured for signals committed
+/// before this cutoff (2026-11-01 00:00 UTC), whatever the admin sets
+pub const LEGACY_HASH_SCHEMA_CUTOFF: i64 = 1_793_491_200;
+
+/// Curator bonds: scale of the per-lamport reward index, share cap and unbonding delay
+pub const REWARD_INDEX_SCALE: u128 = 1_000_000_000_000;
+pub const MAX_CURATOR_SHARE_BPS: u16 = 5000;
+pub const UNBOND_DELAY_SECS: i64 = 7 * 24 * 3600;
+
+/// Governance: curator bonds weight votes on Config changes. Voting is shorter than the unbonding
+/// delay so counted bonds stay locked until it closes; passed proposals wait out the timelock
+pub const GOVERNANCE_VOTING_SECS: i64 = 3 * 24 * 3600;
+pub const GOVERNANCE_TIMELOCK_SECS: i64 = 2 * 24 * 3600;
+pub const GOVERNANCE_QUORUM_LAMPORTS: u64 = 100_000_000_000;
+pub const GOVERNANCE_MIN_PROPOSAL_LAMPORTS: u64 = 10_000_000_000;
+
+/// Config parameters a proposal can change
+pub const PARAM_PROTOCOL_FEE_BPS: u8 = 0;
+pub const PARAM_BREAK_EVEN_BPS: u8 = 1;
+pub const PARAM_BOTH_TOUCHED_PRECEDENCE: u8 = 2;
+pub const PARAM_MIN_REPUTATION_CONFIDENCE: u8 = 3;
+pub const PARAM_AUDIT_RATE_BPS: u8 = 4;
+pub const PARAM_SETTLEMENT_ORACLE: u8 = 5;
+pub const PARAM_PRICE_ORACLE: u8 = 6;
+pub const PARAM_AUDIT_ORACLE: u8 = 7;
+
+/// Social settlement: panel size, grace after a signal's window before the panel may settle it,
+/// outlier threshold (deviation from the panel median), reporter minimum stake and outlier slash
+pub const SOCIAL_PANEL_SIZE: usize = 5;
+pub const SOCIAL_SETTLEMENT_GRACE_SECS: i64 = 24 * 3600;
+pub const SOCIAL_OUTLIER_BPS: u64 = 500;
+/// Panel members commit to their observation within SOCIAL_COMMIT_SECS of opening, then reveal
+/// within SOCIAL_REVEAL_SECS; after that a panel with SOCIAL_QUORUM reveals can be finalized
+pub const SOCIAL_COMMIT_SECS: i64 = 6 * 3600;
+pub const SOCIAL_REVEAL_SECS: i64 = 6 * 3600;
+pub const SOCIAL_QUORUM: usize = 3;
+/// Active reporters panels are drawn from
+pub const MAX_PANEL_REPORTERS: usize = 64;
+pub const MIN_REPORTER_STAKE_LAMPORTS: u64 = 1_000_000_000;
+pub const REPORTER_SLASH_BPS: u64 = 2000;
+
+/// Maximum approved providers per buyer session
+pub const MAX_SESSION_PROVIDERS: usize = 8;
+
+/// Providers itemized in a buyer's SpendReport (further providers only count toward the totals)
+pub const MAX_SPEND_PROVIDERS: usize = 16;
+
+/// Outcome of a signal voided because its token stopped trading (no reputation effect)
+pub const OUTCOME_VOIDED: u8 = 5;
+
+/// Neutral outcomes, settled without reputation effect: an EXPIRED signal that closed within
+/// Config.break_even_bps of entry, and one whose entry price was never reached (oracle-reported)
+pub const OUTCOME_BREAK_EVEN: u8 = 6;
+pub const OUTCOME_NOT_FILLED: u8 = 7;
+
+/// Widest break-even band the admin can set
+pub const MAX_BREAK_EVEN_BPS: u16 = 500;
+
+/// Categories a provider can price separately from its base price
+pub const MAX_CATEGORY_PRICES: usize = 8;
+
+/// Endpoint probes kept per provider, and how often one prober may probe the same provider
+pub const MAX_HEALTH_PROBES: usize = 4;
+pub const PROBE_MIN_INTERVAL_SECS: i64 = 600;
+
+/// Upstream providers a signal can cite, and the cut of each SOL sale each cited provider receives
+pub const MAX_CITATIONS: usize = 2;
+pub const CITATION_ROYALTY_BPS: u64 = 200;
+
+/// Settlement circuit breaker window, and the settlements a window needs before its loss share is judged
+pub const BREAKER_WINDOW_SECS: i64 = 3600;
+pub const BREAKER_MIN_SAMPLE: u32 = 20;
+
+/// Audit states of a settled signal
+pub const AUDIT_UNDRAWN: u8 = 0;
+pub const AUDIT_NOT_SELECTED: u8 = 1;
+pub const AUDIT_SELECTED: u8 = 2;
+pub const AUDIT_AGREED: u8 = 3;
+pub const AUDIT_DISAGREED: u8 = 4;
+
+/// Audit sampling epoch (signals are drawn in an epoch after the one they settled in, so the
+/// settling oracle cannot know the seed) and the price deviation an audit still agrees with
+pub const AUDIT_EPOCH_SECS: i64 = 86400;
+pub const AUDIT_TOLERANCE_BPS: u128 = 50;
+
+/// Baseline (null model) signals: standard TP/SL distances, window and stated confidence
+pub const BASELINE_TP_BPS: u64 = 500;
+pub const BASELINE_SL_BPS: u64 = 500;
+pub const BASELINE_TIMEFRAME_HOURS: u8 = 24;
+pub const BASELINE_CONFIDENCE: u8 = 50;
+
+/// Protocol features listed in the FeatureRegistry (index into activated_slots)
+pub const FEATURE_ENCRYPTED_REVEALS: u8 = 0; // SCHEMA_ENCRYPTED provider payloads
+pub const FEATURE_BATCH_REVEALS: u8 = 1;     // reveal_signals_batch
+pub const FEATURE_SPL_PAYMENTS: u8 = 2;      // purchases paid in a provider's payment_mint
+
+/// Feature slots in the FeatureRegistry (room for ids not assigned yet)
+pub const MAX_FEATURES: usize = 32;
+
+// ==================== ACCOUNTS ====================
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct RegisterProvider<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Provider::SIZE,
+        seeds = [b"provider", authority.key().as_ref()],
+        bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterProvidersBatch<'info> {
+    /// Onboarding platform: pays each provider's rent and is recorded as its attribution key
+    #[account(mut)]
+    pub platform: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProvider<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority,
+        constraint = !provider.frozen @ AgentAlphaError::ProviderFrozen
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCategoryPrice<'info> {
+    // Providers from before layout 8 grow into the category price list
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority,
+        constraint = !provider.frozen @ AgentAlphaError::ProviderFrozen,
+        realloc = Provider::SIZE,
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ActivateEndpoint<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        constraint = !provider.frozen @ AgentAlphaError::ProviderFrozen
+    )]
+    pub provider: Account<'info, Provider>,
+}
+
+#[derive(Accounts)]
+pub struct CancelEndpointChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        constraint = signer.key() == provider.authority
+            || signer.key() == provider.recovery_key @ AgentAlphaError::Unauthorized
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecoverProvider<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = recovery_key @ AgentAlphaError::Unauthorized
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    pub recovery_key: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(coupon_id: u64)]
+pub struct CreateCoupon<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Coupon::SIZE,
+        seeds = [b"coupon", provider.key().as_ref(), &coupon_id.to_le_bytes()],
+        bump
+    )]
+    pub coupon: Account<'info, Coupon>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitProviderCurve<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProviderCurve::SIZE,
+        seeds = [b"curve", provider.key().as_ref()],
+        bump
+    )]
+    pub provider_curve: Account<'info, ProviderCurve>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(signal_hash: [u8; 32])]
+pub struct CommitSignal<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = SignalCommit::SIZE,
+        seeds = [b"signal", provider.key().as_ref(), &signal_hash],
+        bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Optional parent signal this commit amends (must belong to the same provider)
+    #[account(
+        constraint = parent_commit.provider == provider.key() @ AgentAlphaError::ParentProviderMismatch
+    )]
+    pub parent_commit: Option<Account<'info, SignalCommit>>,
+    
+    /// Holds the hash schema kill switches
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(signal_hash: [u8; 32])]
+pub struct RelayedCommitSignal<'info> {
+    #[account(
+        init,
+        payer = relayer,
+        space = SignalCommit::SIZE,
+        seeds = [b"signal", provider.key().as_ref(), &signal_hash],
+        bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Holds the hash schema kill switches
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// Pays fees, rent and the settlement deposit; needs no authority over the provider
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, read to find the ed25519 verification instruction
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(token: String)]
+pub struct RevealSignal<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Parent signal, required when revealing an amendment
+    #[account(
+        mut,
+        constraint = parent_commit.provider == provider.key() @ AgentAlphaError::ParentProviderMismatch
+    )]
+    pub parent_commit: Option<Account<'info, SignalCommit>>,
+    
+    /// CHECK: TokenRegistry PDA for the revealed token; only validated if it has been registered
+    #[account(seeds = [b"token", token.as_bytes()], bump)]
+    pub token_registry: UncheckedAccount<'info>,
+    
+    /// Oracle work queue, the revealed signal is queued for settlement when present
+    #[account(mut, seeds = [b"oracle_queue"], bump = task_queue.bump)]
+    pub task_queue: Option<Box<Account<'info, OracleTaskQueue>>>,
+    
+    /// The token's open-interest stats, updated when present
+    #[account(mut, seeds = [b"token_stats", token.as_bytes()], bump = token_stats.bump)]
+    pub token_stats: Option<Box<Account<'info, TokenStats>>>,
+    
+    /// The token's consensus feed, updated when present
+    #[account(mut, seeds = [b"consensus", token.as_bytes()], bump = token_consensus.bump)]
+    pub token_consensus: Option<Box<Account<'info, TokenConsensus>>>,
+    
+    /// Holds the hash schema kill switches
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// Pays the confidence stake when the provider has opted in
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealSignalsBatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Oracle work queue, revealed signals are queued for settlement when present
+    #[account(mut, seeds = [b"oracle_queue"], bump = task_queue.bump)]
+    pub task_queue: Option<Box<Account<'info, OracleTaskQueue>>>,
+    
+    /// Holds the hash schema kill switches
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// Pays the confidence stakes when the provider has opted in
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseSignal<'info> {
+    /// Created on first purchase; an existing record makes a matching-intent retry a no-op
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = SignalAccess::SIZE,
+        seeds = [b"access", signal_commit.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Holds the payout vesting policy and protocol fee
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    
+    /// Provider coupon, required when a coupon code is supplied
+    #[account(
+        mut,
+        has_one = provider @ AgentAlphaError::InvalidCoupon
+    )]
+    pub coupon: Option<Account<'info, Coupon>>,
+    
+    /// Registry entry of the provider's payment token; when present the buyer pays in that token,
+    /// converted from the SOL price
+    #[account(
+        seeds = [b"token", payment_registry.symbol.as_bytes()],
+        bump = payment_registry.bump
+    )]
+    pub payment_registry: Option<Account<'info, TokenRegistry>>,
+    
+    /// SOL registry entry, supplies the SOL/USD rate for token payments
+    #[account(
+        seeds = [b"token", b"SOL".as_ref()],
+        bump = sol_registry.bump
+    )]
+    pub sol_registry: Option<Account<'info, TokenRegistry>>,
+    
+    /// The buyer's account for the payment's mint; a wrapped SOL one short of the price is topped
+    /// up from the buyer's lamports
+    #[account(mut)]
+    pub buyer_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// The provider's vault for the payment's mint; when present the buyer pays in tokens into it:
+    /// the price-mint price, or the payment token when payment_registry is present
+    #[account(
+        mut,
+        seeds = [b"price_vault", provider.key().as_ref(), price_vault.mint.as_ref()],
+        bump
+    )]
+    pub price_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Holds a new provider's held-back token proceeds, required for a token purchase while they are held back
+    #[account(
+        mut,
+        seeds = [b"token_holdback", provider.key().as_ref(), token_holdback.mint.as_ref()],
+        bump
+    )]
+    pub token_holdback: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// The treasury's account for the payment's mint, required for a token purchase while a protocol fee is set
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    
+    /// The provider's embargo policy, required when it has one
+    pub purchase_policy: Option<Box<Account<'info, PurchasePolicy>>>,
+    
+    /// CHECK: The buyer's BuyerClass PDA under the policy (may be uninitialized = no classes)
+    pub buyer_class: Option<UncheckedAccount<'info>>,
+    
+    /// The buyer's holding of the provider's gate mint, required when the feed is token-gated
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Upstream providers the signal cites, each required for a SOL purchase when cited
+    #[account(mut)]
+    pub cited_provider: Option<Box<Account<'info, Provider>>>,
+    
+    #[account(mut)]
+    pub second_cited_provider: Option<Box<Account<'info, Provider>>>,
+    
+    /// CHECK: The protocol treasury, required for a SOL purchase while a protocol fee is set
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: The provider's onboarding platform, required for a SOL purchase when it takes a fee
+    #[account(mut)]
+    pub platform: Option<UncheckedAccount<'info>>,
+    
+    /// A subscription to the provider the buyer holds or has a seat on; when present it must
+    /// cover the signal, which is then free
+    #[account(
+        seeds = [b"subscription", provider.key().as_ref(), subscription.buyer.as_ref()],
+        bump = subscription.bump,
+        constraint = subscription.has_member(&buyer.key()) @ AgentAlphaError::NotSubscriptionMember
+    )]
+    pub subscription: Option<Box<Account<'info, Subscription>>>,
+    
+    /// Access record of a withdrawn signal holding a replacement credit with this provider, spent for a free purchase
+    #[account(mut)]
+    pub replacement_credit: Option<Box<Account<'info, SignalAccess>>>,
+    
+    /// The buyer's prepaid bundle with the provider; when present one credit pays for the signal
+    #[account(
+        mut,
+        seeds = [b"credits", provider.key().as_ref(), buyer.key().as_ref()],
+        bump = credits.bump
+    )]
+    pub credits: Option<Box<Account<'info, Credits>>>,
+    
+    /// Front-end or framework that routed the purchase, takes its fee share of a SOL purchase
+    /// Must be admin-approved and not the buyer's own
+    #[account(
+        mut,
+        seeds = [b"integrator", integrator.authority.as_ref()],
+        bump = integrator.bump,
+        constraint = integrator.approved && integrator.authority != buyer.key() @ AgentAlphaError::IntegratorNotApproved
+    )]
+    pub integrator: Option<Box<Account<'info, Integrator>>>,
+    
+    /// The buyer's session, when a session key is purchasing on the buyer's behalf (pays from its balance)
+    #[account(
+        mut,
+        seeds = [b"session", buyer.key().as_ref(), buyer_session.session_key.as_ref()],
+        bump = buyer_session.bump
+    )]
+    pub buyer_session: Option<Box<Account<'info, BuyerSession>>>,
+    
+    /// Hot key of the buyer_session, signing instead of the buyer
+    pub session_key: Option<Signer<'info>>,
+    
+    /// The buyer's spend report, updated when present
+    #[account(mut, seeds = [b"spend_report", buyer.key().as_ref()], bump = spend_report.bump)]
+    pub spend_report: Option<Box<Account<'info, SpendReport>>>,
+    
+    /// Pays rent for the access record: the buyer, or the provider/a sponsor onboarding buyers without SOL
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+    
+    /// CHECK: Signs the purchase, unless a buyer_session of theirs and its session_key are supplied
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        constraint = signal_commit.provider == provider.key()
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Optional equity-curve buffer, appended to when present
+    #[account(
+        mut,
+        seeds = [b"curve", provider.key().as_ref()],
+        bump = provider_curve.bump
+    )]
+    pub provider_curve: Option<Account<'info, ProviderCurve>>,
+    
+    /// The signal token's open-interest stats, updated when present
+    #[account(
+        mut,
+        seeds = [b"token_stats", signal_commit.token.as_bytes()],
+        bump = token_stats.bump
+    )]
+    pub token_stats: Option<Box<Account<'info, TokenStats>>>,
+    
+    /// The signal token's consensus feed, updated when present
+    #[account(
+        mut,
+        seeds = [b"consensus", signal_commit.token.as_bytes()],
+        bump = token_consensus.bump
+    )]
+    pub token_consensus: Option<Box<Account<'info, TokenConsensus>>>,
+    
+    /// Holds the keeper-reward policy and reserve, and names the settlement oracle
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.settlement_oracle == oracle.key() @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Config.settlement_oracle - trusted to report outcomes, receives the settlement reward
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetContentRoot<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PostMark<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", signal_commit.provider.as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.settlement_oracle == oracle.key() @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Config.settlement_oracle posting the mark
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReportFill<'info> {
+    #[account(
+        init,
+        payer = reporter,
+        space = FillReport::SIZE,
+        seeds = [b"fill", signal_commit.key().as_ref(), reporter.key().as_ref()],
+        bump
+    )]
+    pub fill_report: Account<'info, FillReport>,
+    
+    /// Only buyers of the signal can report fills, once per purchase (the report PDA shares its seeds)
+    #[account(
+        seeds = [b"access", signal_commit.key().as_ref(), reporter.key().as_ref()],
+        bump = signal_access.bump
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    /// CHECK: The reporter's ExecutionRecord PDA; if it exists the entry's slippage was already counted
+    #[account(seeds = [b"execution", signal_commit.key().as_ref(), reporter.key().as_ref()], bump)]
+    pub execution_record: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        constraint = signal_commit.provider == provider.key()
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitMailbox<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Mailbox::SIZE,
+        seeds = [b"mailbox", owner.key().as_ref()],
+        bump
+    )]
+    pub mailbox: Account<'info, Mailbox>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PostNotice<'info> {
+    #[account(
+        mut,
+        seeds = [b"mailbox", mailbox.owner.as_ref()],
+        bump = mailbox.bump
+    )]
+    pub mailbox: Account<'info, Mailbox>,
+    
+    #[account(
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    /// The mailbox owner's purchase of the signal, or
+    #[account(
+        seeds = [b"access", signal_commit.key().as_ref(), mailbox.owner.as_ref()],
+        bump = signal_access.bump
+    )]
+    pub signal_access: Option<Account<'info, SignalAccess>>,
+    
+    /// its subscription to the provider (one of the two is required)
+    #[account(
+        seeds = [b"subscription", provider.key().as_ref(), mailbox.owner.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AckNotices<'info> {
+    #[account(
+        mut,
+        seeds = [b"mailbox", owner.key().as_ref()],
+        bump = mailbox.bump,
+        has_one = owner
+    )]
+    pub mailbox: Account<'info, Mailbox>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireSignal<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        constraint = signal_commit.provider == provider.key()
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Holds the keeper-reward policy and reserve
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// Receives the expiry reward
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Config::SIZE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Agentalpha>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ AgentAlphaError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdminOnly<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SuspendProvider<'info> {
+    #[account(mut)]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveIntegrator<'info> {
+    #[account(mut)]
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyAttestation<'info> {
+    #[account(mut)]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = attestation_authority @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub attestation_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct RegisterToken<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = TokenRegistry::SIZE,
+        seeds = [b"token", symbol.as_bytes()],
+        bump
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"token", token_registry.symbol.as_bytes()],
+        bump = token_registry.bump
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(bounty_id: u64)]
+pub struct CreateBounty<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = SignalBounty::SIZE,
+        seeds = [b"bounty", creator.key().as_ref(), &bounty_id.to_le_bytes()],
+        bump
+    )]
+    pub bounty: Account<'info, SignalBounty>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnterBounty<'info> {
+    #[account(mut)]
+    pub bounty: Account<'info, SignalBounty>,
+    
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ScoreBountyEntry<'info> {
+    #[account(mut)]
+    pub bounty: Account<'info, SignalBounty>,
+    
+    pub signal_commit: Account<'info, SignalCommit>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBounty<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        close = creator
+    )]
+    pub bounty: Account<'info, SignalBounty>,
+    
+    #[account(
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Bounty creator, receives the account rent back
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBounty<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        close = creator
+    )]
+    pub bounty: Account<'info, SignalBounty>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(bundle_id: u64)]
+pub struct CreateBundle<'info> {
+    #[account(
+        init,
+        payer = curator,
+        space = Bundle::SIZE,
+        seeds = [b"bundle", curator.key().as_ref(), &bundle_id.to_le_bytes()],
+        bump
+    )]
+    pub bundle: Account<'info, Bundle>,
+    
+    #[account(mut)]
+    pub curator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TallyBundleOutcome<'info> {
+    #[account(mut)]
+    pub bundle: Account<'info, Bundle>,
+    
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    /// Marker that this signal has been counted for this bundle
+    #[account(
+        init,
+        payer = payer,
+        space = BundleTally::SIZE,
+        seeds = [b"bundle_tally", bundle.key().as_ref(), signal_commit.key().as_ref()],
+        bump
+    )]
+    pub tally: Account<'info, BundleTally>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BondProvider<'info> {
+    #[account(
+        init,
+        payer = curator,
+        space = CuratorBond::SIZE,
+        seeds = [b"bond", provider.key().as_ref(), curator.key().as_ref()],
+        bump
+    )]
+    pub curator_bond: Account<'info, CuratorBond>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub curator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CuratorBondAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"bond", provider.key().as_ref(), curator.key().as_ref()],
+        bump = curator_bond.bump,
+        has_one = curator
+    )]
+    pub curator_bond: Account<'info, CuratorBond>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub curator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBond<'info> {
+    #[account(
+        mut,
+        close = curator,
+        seeds = [b"bond", provider.key().as_ref(), curator.key().as_ref()],
+        bump = curator_bond.bump,
+        has_one = curator
+    )]
+    pub curator_bond: Account<'info, CuratorBond>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub curator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SlashBond<'info> {
+    #[account(
+        mut,
+        close = config,
+        seeds = [b"bond", provider.key().as_ref(), curator_bond.curator.as_ref()],
+        bump = curator_bond.bump
+    )]
+    pub curator_bond: Account<'info, CuratorBond>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateProposal<'info> {
+    #[account(
+        init,
+        payer = curator,
+        space = Proposal::SIZE,
+        seeds = [b"proposal", curator.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    /// The proposer's bond, which must carry GOVERNANCE_MIN_PROPOSAL_LAMPORTS
+    #[account(
+        seeds = [b"bond", provider.key().as_ref(), curator.key().as_ref()],
+        bump = curator_bond.bump,
+        has_one = curator
+    )]
+    pub curator_bond: Account<'info, CuratorBond>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub curator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.proposer.as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    /// One per bond and proposal, so a bond votes once
+    #[account(
+        init,
+        payer = curator,
+        space = Vote::SIZE,
+        seeds = [b"vote", proposal.key().as_ref(), curator_bond.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, Vote>,
+    
+    #[account(
+        seeds = [b"bond", provider.key().as_ref(), curator.key().as_ref()],
+        bump = curator_bond.bump,
+        has_one = curator
+    )]
+    pub curator_bond: Account<'info, CuratorBond>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub curator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.proposer.as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AckDelivery<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", signal_commit.key().as_ref(), buyer.key().as_ref()],
+        bump = signal_access.bump,
+        has_one = buyer
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    #[account(
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Provider's authority, receives the escrowed payment
+    #[account(mut, address = provider.authority)]
+    pub provider_authority: UncheckedAccount<'info>,
+    
+    pub buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReleasePurchaseEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", signal_commit.key().as_ref(), signal_access.buyer.as_ref()],
+        bump = signal_access.bump
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    #[account(
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Provider's authority, receives the escrowed payment
+    #[account(mut, address = provider.authority)]
+    pub provider_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", signal_commit.key().as_ref(), buyer.key().as_ref()],
+        bump = signal_access.bump
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    #[account(
+        seeds = [b"signal", signal_commit.provider.as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+}
+
+/// Sets a mint the provider is paid in (its price mint or payment token) and creates its vaults
+#[derive(Accounts)]
+pub struct SetProviderMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority,
+        constraint = !provider.frozen @ AgentAlphaError::ProviderFrozen
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    /// Holds the token sale proceeds, owned by the provider PDA
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"price_vault", provider.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = provider
+    )]
+    pub price_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Holds the share of token proceeds held back while the provider is new, owned by the provider PDA
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"token_holdback", provider.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = provider
+    )]
+    pub token_holdback: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTokenEarnings<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(
+        mut,
+        seeds = [b"price_vault", provider.key().as_ref(), price_vault.mint.as_ref()],
+        bump
+    )]
+    pub price_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, constraint = destination.mint == price_vault.mint @ AgentAlphaError::InvalidPaymentAccount)]
+    pub destination: Account<'info, TokenAccount>,
+    
+    /// Receives the lamports when wrapped SOL paid to their own account is unwrapped
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTokenRate<'info> {
+    #[account(
+        mut,
+        seeds = [b"token", token_registry.symbol.as_bytes()],
+        bump = token_registry.bump
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = price_oracle @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub price_oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CorrectReveal<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: TokenRegistry PDA for the signal's token; only validated if it has been registered
+    #[account(seeds = [b"token", signal_commit.token.as_bytes()], bump)]
+    pub token_registry: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FollowProvider<'info> {
+    #[account(
+        init,
+        payer = follower,
+        space = Follow::SIZE,
+        seeds = [b"follow", provider.key().as_ref(), follower.key().as_ref()],
+        bump
+    )]
+    pub follow: Account<'info, Follow>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub follower: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnfollowProvider<'info> {
+    #[account(
+        mut,
+        close = follower,
+        seeds = [b"follow", provider.key().as_ref(), follower.key().as_ref()],
+        bump = follow.bump,
+        has_one = follower
+    )]
+    pub follow: Account<'info, Follow>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub follower: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EvaluateSla<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+}
+
+#[derive(Accounts)]
+pub struct RecordExecution<'info> {
+    #[account(
+        init,
+        payer = buyer,
+        space = ExecutionRecord::SIZE,
+        seeds = [b"execution", signal_commit.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub execution_record: Account<'info, ExecutionRecord>,
+    
+    /// Only buyers of the signal can log executions
+    #[account(
+        seeds = [b"access", signal_commit.key().as_ref(), buyer.key().as_ref()],
+        bump = signal_access.bump
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    /// CHECK: The buyer's FillReport PDA; if it exists the entry's slippage was already counted
+    #[account(seeds = [b"fill", signal_commit.key().as_ref(), buyer.key().as_ref()], bump)]
+    pub fill_report: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitStakePool<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = StakePool::SIZE,
+        seeds = [b"stake_pool"],
+        bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Provider's authority, receives the returned stake and bonus
+    #[account(mut, address = provider.authority)]
+    pub provider_authority: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"stake_pool"], bump = stake_pool.bump)]
+    pub stake_pool: Account<'info, StakePool>,
+}
+
+#[derive(Accounts)]
+pub struct InitReporterSet<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ReporterSet::SIZE,
+        seeds = [b"reporter_set"],
+        bump
+    )]
+    pub reporter_set: Box<Account<'info, ReporterSet>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitTaskQueue<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = OracleTaskQueue::SIZE,
+        seeds = [b"oracle_queue"],
+        bump
+    )]
+    pub task_queue: Box<Account<'info, OracleTaskQueue>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTask<'info> {
+    #[account(mut, seeds = [b"oracle_queue"], bump = task_queue.bump)]
+    pub task_queue: Box<Account<'info, OracleTaskQueue>>,
+    
+    /// Names the settlement oracle
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.settlement_oracle == oracle.key() @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Config.settlement_oracle, the only key that can settle the claimed signal
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteTask<'info> {
+    #[account(mut, seeds = [b"oracle_queue"], bump = task_queue.bump)]
+    pub task_queue: Box<Account<'info, OracleTaskQueue>>,
+    
+    pub signal_commit: Account<'info, SignalCommit>,
+}
+
+#[derive(Accounts)]
+pub struct RequeueStaleTasks<'info> {
+    #[account(mut, seeds = [b"oracle_queue"], bump = task_queue.bump)]
+    pub task_queue: Box<Account<'info, OracleTaskQueue>>,
+}
+
+#[derive(Accounts)]
+pub struct GetReputation<'info> {
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewSettlement<'info> {
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    /// Supplies the BOTH_TOUCHED precedence and reputation threshold
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct ExportProviderDigest<'info> {
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Optional equity-curve buffer, committed to as a Merkle root when present
+    #[account(seeds = [b"curve", provider.key().as_ref()], bump = provider_curve.bump)]
+    pub provider_curve: Option<Box<Account<'info, ProviderCurve>>>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePurchasePolicy<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = PurchasePolicy::SIZE,
+        seeds = [b"policy", provider.key().as_ref()],
+        bump
+    )]
+    pub purchase_policy: Account<'info, PurchasePolicy>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePurchasePolicy<'info> {
+    #[account(
+        mut,
+        seeds = [b"policy", provider.key().as_ref()],
+        bump = purchase_policy.bump
+    )]
+    pub purchase_policy: Account<'info, PurchasePolicy>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetBuyerClass<'info> {
+    #[account(
+        init_if_needed,
+        payer = classifier,
+        space = BuyerClass::SIZE,
+        seeds = [b"buyer_class", purchase_policy.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_class: Account<'info, BuyerClass>,
+    
+    #[account(has_one = classifier @ AgentAlphaError::Unauthorized)]
+    pub purchase_policy: Account<'info, PurchasePolicy>,
+    
+    /// CHECK: The buyer being classified
+    pub buyer: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub classifier: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterReporter<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Reporter::SIZE,
+        seeds = [b"reporter", authority.key().as_ref()],
+        bump
+    )]
+    pub reporter: Account<'info, Reporter>,
+    
+    #[account(mut, seeds = [b"reporter_set"], bump = reporter_set.bump)]
+    pub reporter_set: Box<Account<'info, ReporterSet>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReporterAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"reporter", authority.key().as_ref()],
+        bump = reporter.bump,
+        has_one = authority
+    )]
+    pub reporter: Account<'info, Reporter>,
+    
+    #[account(mut, seeds = [b"reporter_set"], bump = reporter_set.bump)]
+    pub reporter_set: Box<Account<'info, ReporterSet>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawReporterStake<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"reporter", authority.key().as_ref()],
+        bump = reporter.bump,
+        has_one = authority
+    )]
+    pub reporter: Account<'info, Reporter>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenSocialSettlement<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = SocialSettlement::SIZE,
+        seeds = [b"social", signal_commit.key().as_ref()],
+        bump
+    )]
+    pub social_settlement: Account<'info, SocialSettlement>,
+    
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    /// The signal token's registry entry, which must allow social settlement
+    #[account(
+        seeds = [b"token", signal_commit.token.as_bytes()],
+        bump = token_registry.bump,
+        constraint = token_registry.social_settlement @ AgentAlphaError::OracleCoverageAvailable
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+    
+    #[account(seeds = [b"reporter_set"], bump = reporter_set.bump)]
+    pub reporter_set: Box<Account<'info, ReporterSet>>,
+    
+    /// CHECK: SlotHashes sysvar, seeds the panel draw
+    #[account(address = solana_sdk_ids::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitObservation<'info> {
+    #[account(
+        mut,
+        seeds = [b"social", social_settlement.signal.as_ref()],
+        bump = social_settlement.bump
+    )]
+    pub social_settlement: Account<'info, SocialSettlement>,
+    
+    #[account(
+        mut,
+        seeds = [b"reporter", authority.key().as_ref()],
+        bump = reporter.bump,
+        has_one = authority
+    )]
+    pub reporter: Account<'info, Reporter>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSocialSettlement<'info> {
+    #[account(
+        mut,
+        seeds = [b"social", signal_commit.key().as_ref()],
+        bump = social_settlement.bump
+    )]
+    pub social_settlement: Account<'info, SocialSettlement>,
+    
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        constraint = signal_commit.provider == provider.key()
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Optional equity-curve buffer, appended to when present
+    #[account(
+        mut,
+        seeds = [b"curve", provider.key().as_ref()],
+        bump = provider_curve.bump
+    )]
+    pub provider_curve: Option<Account<'info, ProviderCurve>>,
+    
+    /// The signal token's open-interest stats, updated when present
+    #[account(
+        mut,
+        seeds = [b"token_stats", signal_commit.token.as_bytes()],
+        bump = token_stats.bump
+    )]
+    pub token_stats: Option<Box<Account<'info, TokenStats>>>,
+    
+    /// The signal token's consensus feed, updated when present
+    #[account(
+        mut,
+        seeds = [b"consensus", signal_commit.token.as_bytes()],
+        bump = token_consensus.bump
+    )]
+    pub token_consensus: Option<Box<Account<'info, TokenConsensus>>>,
+    
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// Anyone can finalize a full panel and receives the settlement reward
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SlashOutlier<'info> {
+    #[account(
+        mut,
+        seeds = [b"social", social_settlement.signal.as_ref()],
+        bump = social_settlement.bump,
+        constraint = social_settlement.finalized @ AgentAlphaError::PanelIncomplete
+    )]
+    pub social_settlement: Account<'info, SocialSettlement>,
+    
+    #[account(
+        mut,
+        seeds = [b"reporter", reporter.authority.as_ref()],
+        bump = reporter.bump
+    )]
+    pub reporter: Account<'info, Reporter>,
+    
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateBuyerSession<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = BuyerSession::SIZE,
+        seeds = [b"session", owner.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub buyer_session: Account<'info, BuyerSession>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBuyerSession<'info> {
+    #[account(
+        mut,
+        seeds = [b"session", owner.key().as_ref(), buyer_session.session_key.as_ref()],
+        bump = buyer_session.bump,
+        has_one = owner
+    )]
+    pub buyer_session: Account<'info, BuyerSession>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBuyerSession<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"session", owner.key().as_ref(), buyer_session.session_key.as_ref()],
+        bump = buyer_session.bump,
+        has_one = owner
+    )]
+    pub buyer_session: Account<'info, BuyerSession>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitSpendReport<'info> {
+    #[account(
+        init,
+        payer = buyer,
+        space = SpendReport::SIZE,
+        seeds = [b"spend_report", buyer.key().as_ref()],
+        bump
+    )]
+    pub spend_report: Box<Account<'info, SpendReport>>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReportPurchaseOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"spend_report", signal_access.buyer.as_ref()],
+        bump = spend_report.bump
+    )]
+    pub spend_report: Box<Account<'info, SpendReport>>,
+    
+    #[account(
+        mut,
+        seeds = [b"access", signal_commit.key().as_ref(), signal_access.buyer.as_ref()],
+        bump = signal_access.bump
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    pub signal_commit: Account<'info, SignalCommit>,
+}
+
+#[derive(Accounts)]
+#[instruction(token: String)]
+pub struct InitTokenStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = TokenStats::SIZE,
+        seeds = [b"token_stats", token.as_bytes()],
+        bump
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(token: String)]
+pub struct InitTokenConsensus<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = TokenConsensus::SIZE,
+        seeds = [b"consensus", token.as_bytes()],
+        bump
+    )]
+    pub token_consensus: Account<'info, TokenConsensus>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoidSignal<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        constraint = signal_commit.provider == provider.key()
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Registry entry of the signal's token, carries the halt
+    #[account(seeds = [b"token", signal_commit.token.as_bytes()], bump = token_registry.bump)]
+    pub token_registry: Account<'info, TokenRegistry>,
+    
+    /// The signal token's open-interest stats, updated when present
+    #[account(
+        mut,
+        seeds = [b"token_stats", signal_commit.token.as_bytes()],
+        bump = token_stats.bump
+    )]
+    pub token_stats: Option<Box<Account<'info, TokenStats>>>,
+    
+    /// The signal token's consensus feed, updated when present
+    #[account(
+        mut,
+        seeds = [b"consensus", signal_commit.token.as_bytes()],
+        bump = token_consensus.bump
+    )]
+    pub token_consensus: Option<Box<Account<'info, TokenConsensus>>>,
+    
+    /// Holds the keeper-reward policy and reserve
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// Anyone can void a signal on a halted token and receives the settlement reward
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundVoidedPurchase<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", signal_commit.key().as_ref(), signal_access.buyer.as_ref()],
+        bump = signal_access.bump
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    #[account(
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: The buyer, receives the refund
+    #[account(mut, address = signal_access.buyer)]
+    pub buyer: UncheckedAccount<'info>,
+    
+    /// The provider's vault for the payment's mint, required to refund a token purchase
+    #[account(
+        mut,
+        seeds = [b"price_vault", provider.key().as_ref(), price_vault.mint.as_ref()],
+        bump
+    )]
+    pub price_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// The buyer's account for the payment's mint, receives a token refund
+    #[account(mut)]
+    pub buyer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimVoidedPurchase<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", signal_commit.key().as_ref(), buyer.key().as_ref()],
+        bump = signal_access.bump
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    #[account(
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// The provider's vault for the payment's mint, required to reclaim a token purchase
+    #[account(
+        mut,
+        seeds = [b"price_vault", provider.key().as_ref(), price_vault.mint.as_ref()],
+        bump
+    )]
+    pub price_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// The buyer's account for the payment's mint, receives a token refund
+    #[account(mut)]
+    pub buyer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(prober_authority: Pubkey)]
+pub struct RegisterProber<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Prober::SIZE,
+        seeds = [b"prober", prober_authority.as_ref()],
+        bump
+    )]
+    pub prober: Account<'info, Prober>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveProber<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"prober", prober.authority.as_ref()],
+        bump = prober.bump
+    )]
+    pub prober: Account<'info, Prober>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AttestEndpointHealth<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(
+        mut,
+        seeds = [b"prober", authority.key().as_ref()],
+        bump = prober.bump,
+        has_one = authority
+    )]
+    pub prober: Account<'info, Prober>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CiteSources<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(seeds = [b"provider", cited_provider.seed_authority.as_ref()], bump = cited_provider.bump)]
+    pub cited_provider: Box<Account<'info, Provider>>,
+    
+    #[account(
+        seeds = [b"provider", second_cited_provider.seed_authority.as_ref()],
+        bump = second_cited_provider.bump
+    )]
+    pub second_cited_provider: Option<Box<Account<'info, Provider>>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCitationRoyalties<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Receives the royalties; checked against the provider's withdrawal allowlist
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseVestedEarnings<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: Receives the proceeds; checked against the provider's withdrawal allowlist
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Must be the destination the withdrawal was scheduled to
+    #[account(
+        mut,
+        address = provider.pending_withdrawal_destination @ AgentAlphaError::WithdrawalDestinationNotAllowed
+    )]
+    pub destination: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteTokenWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(
+        mut,
+        seeds = [b"price_vault", provider.key().as_ref(), destination.mint.as_ref()],
+        bump
+    )]
+    pub price_vault: Account<'info, TokenAccount>,
+    
+    /// Must be the token account the withdrawal was scheduled to
+    #[account(
+        mut,
+        address = provider.pending_withdrawal_destination @ AgentAlphaError::WithdrawalDestinationNotAllowed
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClawBackVesting<'info> {
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseTokenHoldback<'info> {
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_holdback", provider.key().as_ref(), token_holdback.mint.as_ref()],
+        bump
+    )]
+    pub token_holdback: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"price_vault", provider.key().as_ref(), token_holdback.mint.as_ref()],
+        bump
+    )]
+    pub price_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClawBackTokenHoldback<'info> {
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_holdback", provider.key().as_ref(), token_holdback.mint.as_ref()],
+        bump
+    )]
+    pub token_holdback: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == config.treasury
+            && treasury_token_account.mint == token_holdback.mint @ AgentAlphaError::MissingTreasuryAccount
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianOnly<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = guardian @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyPendingReputation<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        constraint = signal_commit.provider == provider.key()
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Optional equity-curve buffer, appended to when present
+    #[account(
+        mut,
+        seeds = [b"curve", provider.key().as_ref()],
+        bump = provider_curve.bump
+    )]
+    pub provider_curve: Option<Account<'info, ProviderCurve>>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct DrawAudit<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", signal_commit.provider.as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: SlotHashes sysvar, seeds the epoch's draws
+    #[account(address = solana_sdk_ids::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    /// Required for the epoch's first draw, which fixes its seed
+    pub audit_oracle: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitAudit<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", signal_commit.provider.as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    /// Reliability record of the oracle that settled the signal
+    #[account(
+        init_if_needed,
+        payer = audit_oracle,
+        space = OracleRecord::SIZE,
+        seeds = [b"oracle_record", signal_commit.settled_by.as_ref()],
+        bump
+    )]
+    pub oracle_record: Account<'info, OracleRecord>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = audit_oracle @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub audit_oracle: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(signal_hash: [u8; 32], token: String)]
+pub struct OpenBaselineSignal<'info> {
+    #[account(
+        init,
+        payer = price_oracle,
+        space = SignalCommit::SIZE,
+        seeds = [b"signal", provider.key().as_ref(), &signal_hash],
+        bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    /// The protocol's baseline provider
+    #[account(mut, address = config.baseline_provider @ AgentAlphaError::NotBaselineProvider)]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(seeds = [b"token", token.as_bytes()], bump = token_registry.bump)]
+    pub token_registry: Account<'info, TokenRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = price_oracle @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Posts the entry price and funds the settlement deposit
+    #[account(mut)]
+    pub price_oracle: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DrawBaselineDirection<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", signal_commit.provider.as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    /// Supplies the tick grid the TP/SL are rounded to
+    #[account(seeds = [b"token", signal_commit.token.as_bytes()], bump = token_registry.bump)]
+    pub token_registry: Account<'info, TokenRegistry>,
+    
+    /// CHECK: SlotHashes sysvar, supplies the coin flip
+    #[account(address = solana_sdk_ids::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetOutperformance<'info> {
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(address = config.baseline_provider @ AgentAlphaError::NotBaselineProvider)]
+    pub baseline_provider: Account<'info, Provider>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct InitFeatureRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = FeatureRegistry::SIZE,
+        seeds = [b"features"],
+        bump
+    )]
+    pub feature_registry: Account<'info, FeatureRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeatureActive<'info> {
+    #[account(mut, seeds = [b"features"], bump = feature_registry.bump)]
+    pub feature_registry: Account<'info, FeatureRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentAlphaError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenAuction<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = SignalAuction::SIZE,
+        seeds = [b"auction", signal_commit.key().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, SignalAuction>,
+    
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CommitBid<'info> {
+    #[account(mut, seeds = [b"auction", auction.signal.as_ref()], bump = auction.bump)]
+    pub auction: Account<'info, SignalAuction>,
+    
+    #[account(
+        init,
+        payer = bidder,
+        space = AuctionBid::SIZE,
+        seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, AuctionBid>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    #[account(mut, seeds = [b"auction", auction.signal.as_ref()], bump = auction.bump)]
+    pub auction: Account<'info, SignalAuction>,
+    
+    #[account(
+        mut,
+        seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, AuctionBid>,
+    
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", signal_commit.key().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, SignalAuction>,
+    
+    #[account(
+        mut,
+        close = winner,
+        seeds = [b"bid", auction.key().as_ref(), auction.highest_bidder.as_ref()],
+        bump = winning_bid.bump
+    )]
+    pub winning_bid: Account<'info, AuctionBid>,
+    
+    /// CHECK: The winning bidder, refunded the rest of its deposit
+    #[account(mut, address = auction.highest_bidder)]
+    pub winner: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = SignalAccess::SIZE,
+        seeds = [b"access", signal_commit.key().as_ref(), auction.highest_bidder.as_ref()],
+        bump
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// Holds the payout vesting policy and protocol fee
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: The protocol treasury, required while a protocol fee is set
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    
+    /// Pays the access record's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EndUnsoldAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", signal_commit.key().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, SignalAuction>,
+    
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBid<'info> {
+    #[account(
+        seeds = [b"auction", signal_commit.key().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, SignalAuction>,
+    
+    /// The auctioned signal, read to release a leading bid whose signal closed unsold
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, AuctionBid>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSignal<'info> {
+    #[account(
+        mut,
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump,
+        has_one = authority
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// The provider's vaults for the mints buyers paid in, required to refund token purchases
+    #[account(
+        mut,
+        seeds = [b"price_vault", provider.key().as_ref(), price_vault.mint.as_ref()],
+        bump
+    )]
+    pub price_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        seeds = [b"price_vault", provider.key().as_ref(), payment_vault.mint.as_ref()],
+        bump
+    )]
+    pub payment_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    
+    /// Funds the refunds beyond what the purchases hold in escrow
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterIntegrator<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Integrator::SIZE,
+        seeds = [b"integrator", authority.key().as_ref()],
+        bump
+    )]
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateIntegrator<'info> {
+    #[account(
+        mut,
+        seeds = [b"integrator", authority.key().as_ref()],
+        bump = integrator.bump,
+        has_one = authority
+    )]
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = Subscription::SIZE,
+        seeds = [b"subscription", provider.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Receives the vested part of the payment
+    #[account(mut, address = provider.authority)]
+    pub provider_authority: UncheckedAccount<'info>,
+    
+    /// Holds the payout vesting policy and protocol fee
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    
+    /// CHECK: The protocol treasury, required while a protocol fee is set
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    
+    /// Pays rent for a new subscription: the buyer, or a sponsor onboarding buyers without SOL
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositBuyerCredit<'info> {
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = BuyerCredit::SIZE,
+        seeds = [b"buyer_credit", buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_credit: Account<'info, BuyerCredit>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBuyerCredit<'info> {
+    #[account(
+        mut,
+        seeds = [b"buyer_credit", buyer.key().as_ref()],
+        bump = buyer_credit.bump,
+        has_one = buyer
+    )]
+    pub buyer_credit: Account<'info, BuyerCredit>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAutoRenew<'info> {
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.provider.as_ref(), buyer.key().as_ref()],
+        bump = subscription.bump,
+        has_one = buyer
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    pub buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RenewSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [b"subscription", provider.key().as_ref(), subscription.buyer.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        mut,
+        seeds = [b"buyer_credit", subscription.buyer.as_ref()],
+        bump = buyer_credit.bump
+    )]
+    pub buyer_credit: Account<'info, BuyerCredit>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Receives the vested part of the payment
+    #[account(mut, address = provider.authority)]
+    pub provider_authority: UncheckedAccount<'info>,
+    
+    /// Holds the payout vesting policy and protocol fee
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    
+    /// CHECK: The protocol treasury, required while a protocol fee is set
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSubscriptionSeats<'info> {
+    // Subscriptions from before seats grow into the seat list
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.provider.as_ref(), buyer.key().as_ref()],
+        bump = subscription.bump,
+        has_one = buyer,
+        realloc = Subscription::SIZE,
+        realloc::payer = buyer,
+        realloc::zero = false
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleSubscriptionEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [b"subscription", provider.key().as_ref(), buyer.key().as_ref()],
+        bump = subscription.bump,
+        has_one = buyer
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Receives the earned part of the escrow
+    #[account(mut, address = provider.authority)]
+    pub provider_authority: UncheckedAccount<'info>,
+    
+    /// Holds the payout vesting policy
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    
+    /// CHECK: The subscriber, receives a refund
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseBundle<'info> {
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = Credits::SIZE,
+        seeds = [b"credits", provider.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub credits: Account<'info, Credits>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Receives the vested part of the payment
+    #[account(mut, address = provider.authority)]
+    pub provider_authority: UncheckedAccount<'info>,
+    
+    /// Holds the payout vesting policy and protocol fee
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    
+    /// CHECK: The protocol treasury, required while a protocol fee is set
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettlePerformanceFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", signal_commit.key().as_ref(), signal_access.buyer.as_ref()],
+        bump = signal_access.bump
+    )]
+    pub signal_access: Account<'info, SignalAccess>,
+    
+    #[account(
+        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
+        bump = signal_commit.bump
+    )]
+    pub signal_commit: Account<'info, SignalCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"provider", provider.seed_authority.as_ref()],
+        bump = provider.bump
+    )]
+    pub provider: Account<'info, Provider>,
+    
+    /// CHECK: Provider's authority, receives the fee
+    #[account(mut, address = provider.authority)]
+    pub provider_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: The buyer, receives the escrow the fee did not use
+    #[account(mut, address = signal_access.buyer)]
+    pub buyer: UncheckedAccount<'info>,
+    
+    /// Holds the payout vesting policy and protocol fee
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    
+    /// CHECK: The protocol treasury, required while a protocol fee is set
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Provider or SignalCommit in its original layout, checked by migration::read_original
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// ==================== STATE ====================
+
+#[account]
+pub struct Provider {
+    pub authority: Pubkey,        // 32
+    pub seed_authority: Pubkey,   // 32 (registering authority, PDA seed; survives rotation)
+    pub name: String,             // 4 + 64
+    pub endpoint: String,         // 4 + 256
+    pub pending_endpoint: String, // 4 + 256 (announced replacement, empty = none)
+    pub endpoint_effective_at: i64, // 8 (0 = no change pending)
+    pub categories: Vec<u8>,      // 4 + 8
+    pub price_lamports: u64,      // 8
+    pub scheduled_price_lamports: u64, // 8 (announced increase, 0 = none)
+    pub price_effective_at: i64,  // 8
+    pub total_signals: u64,       // 8
+    pub correct_signals: u64,     // 8
+    pub total_return_bps: i64,    // 8
+    pub last_settled_at: i64,     // 8 (last headline-stat settlement)
+    pub accepting_purchases: bool, // 1
+    pub pause_start: i64,         // 8
+    pub pause_end: i64,           // 8
+    // Experimental (paper) track
+    pub exp_total_signals: u64,   // 8
+    pub exp_correct_signals: u64, // 8
+    pub exp_total_return_bps: i64, // 8
+    // Signals below Config.min_reputation_confidence: visible, but outside the headline stats
+    pub low_conf_signals: u64,    // 8
+    pub low_conf_correct_signals: u64, // 8
+    pub low_conf_total_return_bps: i64, // 8
+    pub max_open_signals: u16,    // 2 (0 = unlimited)
+    pub open_signals: u16,        // 2 (committed, not yet settled or expired)
+    pub fill_reports: u64,        // 8
+    pub total_slippage_bps: i64,  // 8 (sum of realized-vs-entry slippage)
+    pub executions: u64,          // 8 (buyer-logged executions)
+    pub total_realized_return_bps: i64, // 8
+    pub total_realized_pnl_cents: i64, // 8 (quote-currency pnl across logged executions)
+    pub stake_per_confidence_lamports: u64, // 8 (self-stake per confidence point at reveal, 0 = off)
+    pub attestation_hash: [u8; 32], // 32 (TEE attestation report hash)
+    pub tee_verified: bool,       // 1
+    pub expired_signals: u64,     // 8
+    pub total_tp_progress_bps: i64, // 8 (graded quality of EXPIRED outcomes)
+    pub benchmarked_signals: u64, // 8
+    pub total_alpha_bps: i64,     // 8 (return in excess of declared benchmarks)
+    pub min_client_version: u32,  // 4
+    pub payload_schema: u8,       // 1 (0=JSON_V1, 1=BINARY_V2, 2=ENCRYPTED)
+    pub suspended: bool,          // 1 (set by the protocol admin)
+    pub suspended_at: i64,        // 8
+    // Key-compromise recovery
+    pub recovery_key: Pubkey,     // 32 (default = none)
+    pub frozen: bool,             // 1
+    pub pending_authority: Pubkey, // 32
+    pub rotation_at: i64,         // 8
+    // Curation
+    pub curator_share_bps: u16,   // 2 (cut of each sale paid to bonded curators)
+    pub total_bonded: u64,        // 8 (lamports bonded by curators, excluding unbonding)
+    pub curator_reward_index: u128, // 16 (cumulative rewards per bonded lamport, scaled by REWARD_INDEX_SCALE)
+    pub fraud_proven: bool,       // 1 (set by the protocol admin; bonds become slashable)
+    pub latency_buckets: [u32; 8], // 32 (buyer-acked delivery latencies, see LATENCY_BUCKET_SECS)
+    pub payment_mint: Pubkey,     // 32 (creator token also accepted as payment, default = SOL only)
+    pub price_mint: Pubkey,       // 32 (SPL mint signals are also priced in, default = SOL only)
+    pub price_token_amount: u64,  // 8 (price in base units of price_mint)
+    pub purchase_policy: Pubkey,  // 32 (embargo policy checked at purchase, default = none)
+    pub gate_mint: Pubkey,        // 32 (buyers must hold this SPL token or NFT mint, default = open)
+    pub gate_min_amount: u64,     // 8 (base units of gate_mint the buyer must hold)
+    pub citation_royalties_unclaimed: u64, // 8 (earned from signals citing this provider, held in this account)
+    pub citation_royalties_earned: u64, // 8
+    pub vesting_held_lamports: u64, // 8 (new-provider proceeds held in this account to vest)
+    pub vesting_released_lamports: u64, // 8
+    // Platform attribution (providers onboarded by register_providers_batch)
+    pub platform: Pubkey,         // 32 (onboarding platform, default = self-registered)
+    pub platform_fee_bps: u16,    // 2 (platform's cut of each SOL sale)
+    // Withdrawal security, managed by the recovery key
+    pub withdrawal_allowlist: [Pubkey; MAX_WITHDRAWAL_DESTINATIONS], // 32 * MAX_WITHDRAWAL_DESTINATIONS (default = unused; none = authority only)
+    pub withdrawal_delay_secs: u32, // 4 (0 = withdrawals pay immediately)
+    pub pending_withdrawal_lamports: u64, // 8 (token units for a WITHDRAWAL_TOKEN withdrawal)
+    pub pending_withdrawal_destination: Pubkey, // 32
+    pub withdrawal_unlocks_at: i64, // 8 (0 = none pending)
+    // Declared capacity vs actual following
+    pub capacity_usd: u64,        // 8 (AUM the strategy is declared to scale to, 0 = undeclared)
+    pub max_followers: u32,       // 4 (declared follower limit, 0 = undeclared)
+    pub follower_count: u32,      // 4 (buyers currently following)
+    // Service-level commitment, evaluated per SLA_EPOCH_SECS epoch
+    pub sla_min_signals: u16,     // 2 (signals committed per epoch, 0 = no commitment)
+    pub sla_max_reveal_delay_secs: u32, // 4 (commit-to-reveal delay, 0 = no commitment)
+    pub sla_epoch: u32,           // 4 (epoch being tracked)
+    pub sla_epoch_signals: u16,   // 2
+    pub sla_epoch_late_reveals: u16, // 2
+    pub sla_met_epochs: u32,      // 4
+    pub sla_missed_epochs: u32,   // 4
+    pub regime_signals: [u32; 4], // 16 (settled signals per volatility regime, CALM..EXTREME)
+    pub regime_correct: [u32; 4], // 16
+    pub total_brier_bps: u64,     // 8 (sum of per-signal Brier scores, see calibration_error_bps)
+    pub badge: u8,                // 1 (0=NONE, 1=BRONZE, 2=SILVER, 3=GOLD, 4=DIAMOND)
+    pub relay_nonce: u64,         // 8 (next nonce a relayed commit must carry)
+    // Endpoint health attested by registered probers, cleared when the endpoint changes
+    pub health_probes: [EndpointProbe; MAX_HEALTH_PROBES], // 45 * MAX_HEALTH_PROBES
+    pub health_probe_head: u8,    // 1 (next slot to overwrite)
+    pub created_at: i64,          // 8
+    pub updated_at: i64,          // 8
+    pub bump: u8,                 // 1
+    pub layout_version: u8,       // 1 (PROVIDER_LAYOUT_VERSION the account was written with; fields are appended after it)
+    // Layout 2
+    pub subscription_price_lamports: u64, // 8 (per SUBSCRIPTION_PERIOD_DAYS at TIER_UNLIMITED, 0 = no subscriptions)
+    // Layout 3
+    pub guarantee_refund_bps: u16, // 2 (money-back share of a SOL purchase if the signal hits SL, 0 = no guarantee)
+    // Layout 4
+    pub activity_weeks: u64,      // 8 (bit n = committed a signal n weeks before activity_week)
+    pub activity_week: u32,       // 4 (ACTIVITY_WEEK_SECS week index of bit 0)
+    // Layout 5
+    pub self_voided_signals: u32, // 4 (purchased signals the provider withdrew before revealing)
+    // Layout 6
+    pub perf_fee_bps: u16,        // 2 (share of buyers' realized return charged on new signals, 0 = flat pricing)
+    // Layout 7
+    pub break_even_signals: u32,  // 4 (settled BREAK_EVEN, outside the headline stats)
+    pub not_filled_signals: u32,  // 4 (settled NOT_FILLED, outside the headline stats)
+    // Layout 8
+    pub category_prices: Vec<CategoryPrice>, // 4 + 25 * MAX_CATEGORY_PRICES (overrides price_lamports per category)
+    // Layout 9
+    pub bundle_discount_bps: u16, // 2 (discount on prepaid signal bundles, 0 = bundles not offered)
+    // Layout 10
+    pub subscription_price_lock: bool, // 1 (subscribers' next renewal keeps the rate they last paid)
+    // Layout 11
+    pub subscriber_credit_secs: u64, // 8 (subscription time owed to subscribers for missed SLA epochs, cumulative)
+    pub subscriber_credit_through: i64, // 8 (end of the last SLA epoch that earned subscribers credit)
+    // Layout 12
+    pub pending_withdrawal_kind: u8, // 1 (WITHDRAWAL_* of the pending withdrawal)
+    // Layout 13
+    pub paused_secs: u64,         // 8 (sales paused under earlier pause settings, cumulative)
+    pub pause_set_at: i64,        // 8 (when the current pause settings took effect, 0 = pauses not tracked yet)
+    pub last_pause_began_at: i64, // 8 (start of the latest pause under earlier settings)
+    pub paused_secs_before_last_pause: u64, // 8 (paused_secs when that pause began)
+}
+
+impl Provider {
+    pub const SIZE: usize = 8 + 32 + 32 + (4 + 64) + (4 + 256) + (4 + 256) + 8 + (4 + 8) + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 2 + 2 + 8 + 8 + 8 + 8 + 8 + 32 + 1 + 8 + 8 + 8 + 8 + 4 + 1 + 1 + 8 + 32 + 1 + 32 + 8 + 2 + 8 + 16 + 1 + 32 + 32 + 32 + 8 + 4 + 4 + 2 + 4 + 4 + 2 + 2 + 4 + 4 + 8 + 16 + 16 + 8 + 1 + 8 + 45 * MAX_HEALTH_PROBES + 1 + 8 + 8 + 8 + 8 + 8 + 32 + 8 + 8 + 8 + 8 + 8 + 32 * MAX_WITHDRAWAL_DESTINATIONS + 4 + 8 + 32 + 8 + 32 + 2 + 32 + 8 + 1 + 1 + 8 + 2 + 8 + 4 + 4 + 2 + 4 + 4 + (4 + 25 * MAX_CATEGORY_PRICES) + 2 + 1 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + ACCOUNT_RESERVE_LARGE;
+    
+    /// Whether the account was written with at least layout `version`; accessors of fields
+    /// appended in that version return their default for older accounts
+    pub fn has_layout(&self, version: u8) -> bool {
+        self.layout_version >= version
+    }
+    
+    /// Subscription price per period for a tier, None when the provider doesn't offer subscriptions
+    pub fn subscription_price(&self, tier: u8) -> Option<u64> {
+        if !self.has_layout(2) || self.subscription_price_lamports == 0 {
+            return None;
+        }
+        let multiplier_bps = match tier {
+            TIER_LIMITED => TIER_LIMITED_PRICE_BPS,
+            TIER_EXCLUSIVE => TIER_EXCLUSIVE_PRICE_BPS,
+            _ => 10000,
+        };
+        Some((self.subscription_price_lamports as u128 * multiplier_bps as u128 / 10000) as u64)
+    }
+    
+    /// Money-back share of new purchases' escrow (0 for accounts from before layout 3)
+    pub fn guarantee_refund_bps(&self) -> u16 {
+        if self.has_layout(3) { self.guarantee_refund_bps } else { 0 }
+    }
+    
+    /// Price of a prepaid bundle of `signals` at `now`, None when the provider doesn't offer bundles
+    pub fn bundle_price(&self, signals: u32, now: i64) -> Option<u64> {
+        if !self.has_layout(9) || self.bundle_discount_bps == 0 {
+            return None;
+        }
+        let full = self.effective_price_lamports(now) as u128 * signals as u128;
+        u64::try_from(full * (10000 - self.bundle_discount_bps as u128) / 10000).ok()
+    }
+    
+    /// Whether subscribers' next renewal keeps their rate (false for accounts from before layout 10)
+    pub fn subscription_price_lock(&self) -> bool {
+        self.has_layout(10) && self.subscription_price_lock
+    }
+    
+    /// Subscription time owed to subscribers for missed SLA epochs, and the end of the last epoch
+    /// that earned it ((0, 0) for accounts from before layout 11)
+    pub fn subscriber_credit(&self) -> (u64, i64) {
+        if self.has_layout(11) {
+            (self.subscriber_credit_secs, self.subscriber_credit_through)
+        } else {
+            (0, 0)
+        }
+    }
+    
+    /// Performance fee new signals are sold under (0 for accounts from before layout 6)
+    pub fn perf_fee_bps(&self) -> u16 {
+        if self.has_layout(6) { self.perf_fee_bps } else { 0 }
+    }
+    
+    /// Whether buyers can be charged right now (not suspended, not paused and outside any pause window)
+    pub fn is_accepting_purchases(&self, now: i64) -> bool {
+        !self.suspended
+            && self.accepting_purchases
+            && !(self.pause_start <= now && now < self.pause_end)
+    }
+    
+    /// The pause under the current settings begun by `now`: its start and its length so far
+    /// An indefinite pause (accepting_purchases off) covers any window it overlaps
+    fn current_pause(&self, now: i64) -> Option<(i64, u64)> {
+        let (start, end) = if self.accepting_purchases {
+            (self.pause_start.max(self.pause_set_at), self.pause_end.min(now))
+        } else {
+            (self.pause_set_at, now)
+        };
+        (start < end).then_some((start, (end - start) as u64))
+    }
+    
+    /// Whether pauses are tracked: from registration, or for older accounts from their first
+    /// pause change at layout 13
+    fn tracks_pauses(&self) -> bool {
+        self.has_layout(13) && self.pause_set_at > 0
+    }
+    
+    /// Seconds sales have been paused by `now`, the start of the latest pause begun by then and
+    /// the paused seconds before it ((0, 0, 0) while pauses aren't tracked)
+    pub fn pause_clock(&self, now: i64) -> (u64, i64, u64) {
+        if !self.tracks_pauses() {
+            return (0, 0, 0);
+        }
+        match self.current_pause(now) {
+            Some((began, elapsed)) => (self.paused_secs + elapsed, began, self.paused_secs),
+            None => (self.paused_secs, self.last_pause_began_at, self.paused_secs_before_last_pause),
+        }
+    }
+    
+    /// Bank the pause under the current settings before they change at `now`
+    pub fn close_pause(&mut self, now: i64) {
+        if !self.tracks_pauses() {
+            self.paused_secs = 0;
+            self.last_pause_began_at = 0;
+            self.paused_secs_before_last_pause = 0;
+        } else if let Some((began, elapsed)) = self.current_pause(now) {
+            self.last_pause_began_at = began;
+            self.paused_secs_before_last_pause = self.paused_secs;
+            self.paused_secs += elapsed;
+        }
+        self.pause_set_at = now;
+        self.layout_version = PROVIDER_LAYOUT_VERSION;
+    }
+    
+    /// When held-back proceeds are fully vested: the end of the vesting period that follows the new-provider period
+    pub fn vesting_ends_at(&self, vesting_period_secs: i64) -> i64 {
+        self.created_at + 2 * vesting_period_secs
+    }
+    
+    /// Held-back proceeds vested by `now`: linear over the vesting period that follows the new-provider period
+    pub fn vested_lamports(&self, vesting_period_secs: i64, now: i64) -> u64 {
+        let elapsed = now - (self.created_at + vesting_period_secs);
+        if vesting_period_secs == 0 || elapsed >= vesting_period_secs {
+            self.vesting_held_lamports
+        } else if elapsed <= 0 {
+            0
+        } else {
+            (self.vesting_held_lamports as u128 * elapsed as u128 / vesting_period_secs as u128) as u64
+        }
+    }
+    
+    /// Whether earnings may be withdrawn to `destination` (the authority when no allowlist is set)
+    pub fn allows_withdrawal_to(&self, destination: &Pubkey) -> bool {
+        let mut allowlist = self.withdrawal_allowlist.iter().filter(|d| **d != Pubkey::default()).peekable();
+        if allowlist.peek().is_none() {
+            return *destination == self.authority;
+        }
+        allowlist.any(|d| d == destination)
+    }
+    
+    /// What the pending withdrawal pays out (vested proceeds for accounts from before layout 12)
+    pub fn pending_withdrawal_kind(&self) -> u8 {
+        if self.has_layout(12) { self.pending_withdrawal_kind } else { WITHDRAWAL_VESTED }
+    }
+    
+    /// Royalties taken out of citation_royalties_unclaimed for a pending withdrawal
+    pub fn pending_royalties(&self) -> u64 {
+        if self.pending_withdrawal_kind() == WITHDRAWAL_ROYALTIES { self.pending_withdrawal_lamports } else { 0 }
+    }
+    
+    /// Schedule a withdrawal of `amount` to `destination`, payable once withdrawal_delay_secs has passed
+    pub fn schedule_withdrawal(&mut self, provider_key: Pubkey, kind: u8, destination: Pubkey, amount: u64, now: i64) {
+        self.pending_withdrawal_kind = kind;
+        self.layout_version = PROVIDER_LAYOUT_VERSION;
+        self.pending_withdrawal_lamports = amount;
+        self.pending_withdrawal_destination = destination;
+        self.withdrawal_unlocks_at = now + self.withdrawal_delay_secs as i64;
+        
+        emit!(WithdrawalScheduled {
+            event_seq: event_seq(),
+            provider: provider_key,
+            destination,
+            amount,
+            unlocks_at: self.withdrawal_unlocks_at,
+            kind,
+        });
+    }
+    
+    /// Clear the pending withdrawal, returning its kind, destination and amount
+    pub fn take_pending_withdrawal(&mut self) -> (u8, Pubkey, u64) {
+        let kind = self.pending_withdrawal_kind();
+        self.pending_withdrawal_kind = WITHDRAWAL_VESTED;
+        self.withdrawal_unlocks_at = 0;
+        (
+            kind,
+            std::mem::take(&mut self.pending_withdrawal_destination),
+            std::mem::take(&mut self.pending_withdrawal_lamports),
+        )
+    }
+    
+    /// Drop the pending withdrawal; royalties go back to citation_royalties_unclaimed
+    pub fn drop_pending_withdrawal(&mut self) -> (Pubkey, u64) {
+        let (kind, destination, amount) = self.take_pending_withdrawal();
+        if kind == WITHDRAWAL_ROYALTIES {
+            self.citation_royalties_unclaimed += amount;
+        }
+        (destination, amount)
+    }
+    
+    /// Whether every recorded endpoint probe found the endpoint down (false with no probes)
+    pub fn endpoint_looks_dead(&self) -> bool {
+        let mut probes = self.health_probes.iter().filter(|p| p.prober != Pubkey::default()).peekable();
+        probes.peek().is_some() && probes.all(|p| !p.live)
+    }
+    
+    /// Price buyers pay at `now`, taking any announced increase into account
+    pub fn effective_price_lamports(&self, now: i64) -> u64 {
+        if self.scheduled_price_lamports > 0 && now >= self.price_effective_at {
+            self.scheduled_price_lamports
+        } else {
+            self.price_lamports
+        }
+    }
+    
+    /// Category price overrides (none for accounts from before layout 8)
+    pub fn category_prices(&self) -> &[CategoryPrice] {
+        if self.has_layout(8) { &self.category_prices } else { &[] }
+    }
+    
+    /// Price buyers pay at `now` for a signal in `category`: its category price if one is set,
+    /// the provider's base price otherwise
+    pub fn price_for_category(&self, category: u8, now: i64) -> u64 {
+        self.category_prices()
+            .iter()
+            .find(|p| p.category == category)
+            .map_or_else(|| self.effective_price_lamports(now), |p| p.effective_price_lamports(now))
+    }
+    
+    /// Price in price_mint base units for a signal in `category`: price_token_amount, scaled by the
+    /// category's price over the base price when the category has its own price
+    /// None if the category is priced while the base price is zero, so there is nothing to scale by
+    pub fn token_price_for_category(&self, category: u8, now: i64) -> Option<u64> {
+        let base_lamports = self.effective_price_lamports(now);
+        let category_lamports = self.price_for_category(category, now);
+        if category_lamports == base_lamports {
+            return Some(self.price_token_amount);
+        }
+        let scaled = (self.price_token_amount as u128 * category_lamports as u128).checked_div(base_lamports as u128)?;
+        u64::try_from(scaled).ok()
+    }
+    
+    /// Fold an announced increase into price_lamports once it is in effect
+    pub fn apply_scheduled_price(&mut self, now: i64) {
+        if self.scheduled_price_lamports > 0 && now >= self.price_effective_at {
+            self.price_lamports = self.scheduled_price_lamports;
+            self.scheduled_price_lamports = 0;
+            self.price_effective_at = 0;
+        }
+    }
+    
+    pub fn hit_rate_bps(&self) -> u64 {
+        if self.total_signals == 0 { return 0; }
+        (self.correct_signals * 10000) / self.total_signals
+    }
+    
+    pub fn avg_return_bps(&self) -> i64 {
+        if self.total_signals == 0 { return 0; }
+        self.total_return_bps / self.total_signals as i64
+    }
+    
+    /// Hit rate shrunk towards 50% for small samples: (correct + 5) / (total + 10)
+    pub fn reputation_score_bps(&self) -> u16 {
+        ((self.correct_signals + 5) * 10000 / (self.total_signals + 10)) as u16
+    }
+    
+    pub fn exp_hit_rate_bps(&self) -> u64 {
+        if self.exp_total_signals == 0 { return 0; }
+        (self.exp_correct_signals * 10000) / self.exp_total_signals
+    }
+    
+    /// Average share of the TP distance reached by signals that expired
+    pub fn expiry_quality_bps(&self) -> i64 {
+        if self.expired_signals == 0 { return 0; }
+        self.total_tp_progress_bps / self.expired_signals as i64
+    }
+    
+    pub fn avg_alpha_bps(&self) -> i64 {
+        if self.benchmarked_signals == 0 { return 0; }
+        self.total_alpha_bps / self.benchmarked_signals as i64
+    }
+    
+    pub fn avg_slippage_bps(&self) -> i64 {
+        if self.fill_reports == 0 { return 0; }
+        self.total_slippage_bps / self.fill_reports as i64
+    }
+    
+    pub fn avg_realized_return_bps(&self) -> i64 {
+        if self.executions == 0 { return 0; }
+        self.total_realized_return_bps / self.executions as i64
+    }
+    
+    pub fn has_sla(&self) -> bool {
+        self.sla_min_signals > 0 || self.sla_max_reveal_delay_secs > 0
+    }
+    
+    /// Close out the tracked SLA epoch once it has ended, counting it as met or missed
+    /// Skipped epochs (no activity at all) miss a signal-count commitment
+    /// Subscribers are owed the missed share of each missed epoch (see Subscription::apply_credit)
+    pub fn roll_sla_epoch(&mut self, provider_key: Pubkey, now: i64) {
+        let epoch = (now / SLA_EPOCH_SECS) as u32;
+        if epoch <= self.sla_epoch {
+            return;
+        }
+        if self.has_sla() {
+            let met = self.sla_epoch_signals >= self.sla_min_signals && self.sla_epoch_late_reveals == 0;
+            let mut credit_secs = 0;
+            let mut credit_through = (self.sla_epoch as i64 + 1) * SLA_EPOCH_SECS;
+            if met {
+                self.sla_met_epochs += 1;
+            } else {
+                self.sla_missed_epochs += 1;
+                credit_secs = SLA_EPOCH_SECS as u64 * self.sla_shortfall_bps() / 10000;
+            }
+            if self.sla_min_signals > 0 && epoch > self.sla_epoch + 1 {
+                let skipped = epoch - self.sla_epoch - 1;
+                self.sla_missed_epochs += skipped;
+                credit_secs += SLA_EPOCH_SECS as u64 * skipped as u64;
+                credit_through = epoch as i64 * SLA_EPOCH_SECS;
+            }
+            if credit_secs > 0 {
+                if !self.has_layout(11) {
+                    self.subscriber_credit_secs = 0;
+                    self.layout_version = PROVIDER_LAYOUT_VERSION;
+                }
+                self.subscriber_credit_secs += credit_secs;
+                self.subscriber_credit_through = credit_through;
+            }
+            
+            emit!(SlaEpochEvaluated {
+                event_seq: event_seq(),
+                provider: provider_key,
+                epoch: self.sla_epoch,
+                met,
+                signals: self.sla_epoch_signals,
+                late_reveals: self.sla_epoch_late_reveals,
+                met_epochs: self.sla_met_epochs,
+                missed_epochs: self.sla_missed_epochs,
+                subscriber_credit_secs: credit_secs,
+            });
+        }
+        self.sla_epoch = epoch;
+        self.sla_epoch_signals = 0;
+        self.sla_epoch_late_reveals = 0;
+    }
+    
+    /// How far the tracked SLA epoch fell short, in bps: the larger of the missing share of
+    /// sla_min_signals and the share of its signals revealed late
+    fn sla_shortfall_bps(&self) -> u64 {
+        let signals = self.sla_epoch_signals as u64;
+        let min_signals = self.sla_min_signals as u64;
+        let count_bps = if signals < min_signals { (min_signals - signals) * 10000 / min_signals } else { 0 };
+        let late_bps = (self.sla_epoch_late_reveals as u64 * 10000).checked_div(signals).unwrap_or(0).min(10000);
+        count_bps.max(late_bps)
+    }
+    
+    /// Mark the week of `now` as active (a signal was committed in it)
+    pub fn record_activity(&mut self, now: i64) {
+        let week = (now / ACTIVITY_WEEK_SECS) as u32;
+        if !self.has_layout(4) {
+            self.activity_weeks = 0;
+            self.layout_version = PROVIDER_LAYOUT_VERSION;
+        }
+        self.activity_weeks = self.activity_bits(week);
+        self.activity_weeks |= 1;
+        self.activity_week = week;
+    }
+    
+    /// Activity bits realigned so bit 0 is `week`, limited to the last ACTIVITY_WEEKS weeks
+    pub fn activity_bits(&self, week: u32) -> u64 {
+        let shift = week.saturating_sub(self.activity_week);
+        if !self.has_layout(4) || shift >= ACTIVITY_WEEKS {
+            return 0;
+        }
+        (self.activity_weeks << shift) & ((1u64 << ACTIVITY_WEEKS) - 1)
+    }
+    
+    /// Share of the last ACTIVITY_WEEKS weeks (or of the weeks since registration, if fewer)
+    /// in which the provider committed at least one signal
+    pub fn consistency_score_bps(&self, now: i64) -> u16 {
+        let week = (now / ACTIVITY_WEEK_SECS) as u32;
+        let active = self.activity_bits(week).count_ones();
+        let tracked = self.weeks_tracked(now);
+        (active.min(tracked) * 10000 / tracked) as u16
+    }
+    
+    /// Weeks the consistency score is measured over (at least 1)
+    pub fn weeks_tracked(&self, now: i64) -> u32 {
+        let registered_week = (self.created_at / ACTIVITY_WEEK_SECS) as u32;
+        let week = (now / ACTIVITY_WEEK_SECS) as u32;
+        (week.saturating_sub(registered_week) + 1).min(ACTIVITY_WEEKS)
+    }
+    
+    /// Count a reveal against the reveal-delay commitment
+    pub fn record_reveal_delay(&mut self, provider_key: Pubkey, delay_secs: i64, now: i64) {
+        self.roll_sla_epoch(provider_key, now);
+        if self.sla_max_reveal_delay_secs > 0 && delay_secs > self.sla_max_reveal_delay_secs as i64 {
+            self.sla_epoch_late_reveals = self.sla_epoch_late_reveals.saturating_add(1);
+        }
+    }
+    
+    /// Share of evaluated SLA epochs that were met (10000 when none evaluated)
+    pub fn sla_compliance_bps(&self) -> u64 {
+        let evaluated = self.sla_met_epochs as u64 + self.sla_missed_epochs as u64;
+        if evaluated == 0 { return 10000; }
+        self.sla_met_epochs as u64 * 10000 / evaluated
+    }
+    
+    /// Whether more buyers follow than the provider declared it can serve
+    pub fn is_over_capacity(&self) -> bool {
+        self.max_followers > 0 && self.follower_count > self.max_followers
+    }
+    
+    /// Count one acked delivery in its latency bucket
+    pub fn record_delivery_latency(&mut self, latency_secs: i64) {
+        let bucket = LATENCY_BUCKET_SECS
+            .iter()
+            .position(|&bound| latency_secs < bound)
+            .unwrap_or(LATENCY_BUCKET_SECS.len());
+        self.latency_buckets[bucket] = self.latency_buckets[bucket].saturating_add(1);
+    }
+    
+    /// Upper bound of the bucket holding the median acked latency (i64::MAX for the open bucket, None without acks)
+    pub fn median_delivery_latency_secs(&self) -> Option<i64> {
+        let total: u64 = self.latency_buckets.iter().map(|&n| n as u64).sum();
+        if total == 0 { return None; }
+        let mut seen = 0u64;
+        for (i, &n) in self.latency_buckets.iter().enumerate() {
+            seen += n as u64;
+            if seen * 2 >= total {
+                return Some(LATENCY_BUCKET_SECS.get(i).copied().unwrap_or(i64::MAX));
+            }
+        }
+        None
+    }
+    
+    /// Fold a settled signal into the reputation stats; returns whether it counted toward the headline stats
+    /// Experimental signals and those below the protocol's minimum confidence are tracked separately
+    pub fn record_settlement(&mut self, commit: &SignalCommit, min_confidence: u8, now: i64) -> bool {
+        let counted = !commit.experimental && commit.confidence >= min_confidence && !commit.has_neutral_outcome();
+        if commit.has_neutral_outcome() {
+            if commit.outcome == OUTCOME_BREAK_EVEN {
+                self.break_even_signals += 1;
+            } else {
+                self.not_filled_signals += 1;
+            }
+            self.layout_version = self.layout_version.max(7);
+        } else if commit.experimental {
+            self.exp_total_signals += 1;
+            if commit.was_correct {
+                self.exp_correct_signals += 1;
+            }
+            self.exp_total_return_bps += commit.return_bps as i64;
+        } else if !counted {
+            self.low_conf_signals += 1;
+            if commit.was_correct {
+                self.low_conf_correct_signals += 1;
+            }
+            self.low_conf_total_return_bps += commit.return_bps as i64;
+        } else {
+            self.total_signals += 1;
+            if commit.was_correct {
+                self.correct_signals += 1;
+            }
+            self.total_return_bps += commit.return_bps as i64;
+            self.total_brier_bps += commit.brier_bps();
+            self.last_settled_at = now;
+            self.record_regime_outcome(commit.vol_regime, commit.was_correct);
+            if commit.outcome == 3 {
+                self.expired_signals += 1;
+                self.total_tp_progress_bps += commit.tp_progress_bps as i64;
+            }
+            if commit.benchmark != BENCH_NONE {
+                self.benchmarked_signals += 1;
+                self.total_alpha_bps += commit.alpha_bps() as i64;
+            }
+        }
+        self.open_signals = self.open_signals.saturating_sub(1);
+        self.updated_at = now;
+        counted
+    }
+    
+    /// Mean Brier score of stated confidence against outcomes (0 = perfectly calibrated, 10000 = worst)
+    pub fn calibration_error_bps(&self) -> u64 {
+        if self.total_signals == 0 { return 10000; }
+        self.total_brier_bps / self.total_signals
+    }
+    
+    /// Highest badge whose sample, hit rate, calibration and tenure thresholds are all met
+    pub fn computed_badge(&self, now: i64) -> u8 {
+        if self.suspended || self.fraud_proven { return BADGE_NONE; }
+        let tenure_secs = now - self.created_at;
+        let hit_rate_bps = self.hit_rate_bps();
+        let calibration_error_bps = self.calibration_error_bps();
+        (0..BADGE_MIN_SIGNALS.len())
+            .rev()
+            .find(|&i| {
+                self.total_signals >= BADGE_MIN_SIGNALS[i]
+                    && hit_rate_bps >= BADGE_MIN_HIT_RATE_BPS[i]
+                    && calibration_error_bps <= BADGE_MAX_BRIER_BPS[i]
+                    && tenure_secs >= BADGE_MIN_TENURE_SECS[i]
+            })
+            .map_or(BADGE_NONE, |i| i as u8 + 1)
+    }
+    
+    /// Re-evaluate the stored badge; returns whether it changed
+    pub fn refresh_badge(&mut self, now: i64) -> bool {
+        let badge = self.computed_badge(now);
+        let changed = badge != self.badge;
+        self.badge = badge;
+        changed
+    }
+    
+    /// Count a settled signal in its volatility regime (unknown regimes are not tracked)
+    pub fn record_regime_outcome(&mut self, regime: u8, was_correct: bool) {
+        if regime == REGIME_UNKNOWN { return; }
+        let i = (regime - 1) as usize;
+        self.regime_signals[i] = self.regime_signals[i].saturating_add(1);
+        if was_correct {
+            self.regime_correct[i] = self.regime_correct[i].saturating_add(1);
+        }
+    }
+    
+    /// Hit rate within one volatility regime (0 without settled signals in it)
+    pub fn regime_hit_rate_bps(&self, regime: u8) -> u64 {
+        if regime == REGIME_UNKNOWN { return 0; }
+        let i = (regime - 1) as usize;
+        if self.regime_signals[i] == 0 { return 0; }
+        self.regime_correct[i] as u64 * 10000 / self.regime_signals[i] as u64
+    }
+}
+
+#[account]
+pub struct SignalCommit {
+    pub provider: Pubkey,           // 32
+    pub signal_hash: [u8; 32],      // 32
+    pub committed_at: i64,          // 8
+    pub revealed: bool,             // 1
+    pub outcome_recorded: bool,     // 1
+    pub parent_signal: Option<Pubkey>, // 1 + 32 (signal this one amends)
+    pub amended_sl_cents: Option<u64>, // 1 + 8 (SL superseded by an amendment)
+    pub bounty: Option<Pubkey>,     // 1 + 32 (bounty this signal was entered into)
+    pub experimental: bool,         // 1 (paper signal, excluded from headline stats)
+    pub expired: bool,              // 1 (never revealed, expired by a keeper)
+    pub settlement_deposit: u64,    // 8 (lamports held for the settler/expirer)
+    pub exclusivity: u8,            // 1 (0=UNLIMITED, 1=LIMITED, 2=EXCLUSIVE)
+    pub max_buyers: u16,            // 2 (0 = unlimited)
+    pub buyer_count: u16,           // 2
+    pub auctioned: bool,            // 1 (EXCLUSIVE signal sold through a SignalAuction only)
+    pub stake_lamports: u64,        // 8 (provider self-stake taken at reveal)
+    pub category: u8,               // 1 (declared at commit)
+    pub hash_schema: u8,            // 1 (hash preimage format version, 1=V1, 2=LEGACY, 3=V2; set from the matching preimage at reveal)
+    pub content_root: [u8; 32],     // 32 (Merkle root of the content attachment's chunks)
+    pub content_chunks: u32,        // 4 (0 = no attachment committed)
+    pub citations: [Pubkey; MAX_CITATIONS], // 32 * MAX_CITATIONS (cited upstream providers, default = none)
+    // Revealed data
+    pub token: String,              // 4 + 16
+    pub direction: u8,              // 1 (0=BUY, 1=SELL)
+    pub entry_cents: u64,           // 8
+    pub tp_cents: u64,              // 8
+    pub sl_cents: u64,              // 8
+    pub timeframe_hours: u8,        // 1
+    pub confidence: u8,             // 1
+    pub quote_currency: u8,         // 1 (0=USD, 1=SOL, 2=BTC, 3=ETH, 4=SPL)
+    pub quote_mint: Option<Pubkey>, // 1 + 32
+    pub min_notional_cents: Option<u64>, // 1 + 8
+    pub max_notional_cents: Option<u64>, // 1 + 8
+    pub benchmark: u8,              // 1 (0=NONE, 1=SOL, 2=BTC, 3=ETH)
+    pub entry_ladder: Vec<EntryLevel>, // 4 + 3 * 10
+    pub revealed_at: i64,           // 8
+    pub in_token_stats: bool,       // 1 (counted in its token's TokenStats until settled)
+    pub consensus_weight: u32,      // 4 (weight counted in its token's TokenConsensus until settled, 0 = not counted)
+    // Interim mark-to-market while open
+    pub last_mark_cents: u64,       // 8
+    pub last_mark_return_bps: i32,  // 4 (unrealized, from entry)
+    pub last_mark_at: i64,          // 8 (publish time of the last mark, 0 = none)
+    pub mark_count: u16,            // 2
+    // Outcome data
+    pub outcome: u8,                // 1 (1=TP_HIT, 2=SL_HIT, 3=EXPIRED, 5=VOIDED, 6=BREAK_EVEN, 7=NOT_FILLED)
+    pub final_price_cents: u64,     // 8
+    pub was_correct: bool,          // 1
+    pub return_bps: i32,            // 4
+    pub tp_progress_bps: i32,       // 4 (EXPIRED only: share of the TP distance reached)
+    pub benchmark_return_bps: i32,  // 4 (benchmark move over the signal's window)
+    pub both_touched: bool,         // 1 (TP and SL inside one candle, resolved by precedence)
+    pub precedence_applied: u8,     // 1 (0=WORST_CASE, 1=BEST_CASE, 2=EXPIRE; set when both_touched)
+    pub vol_regime: u8,             // 1 (0=UNKNOWN, 1=CALM, 2=NORMAL, 3=VOLATILE, 4=EXTREME)
+    pub reputation_pending: bool,   // 1 (settled while the circuit breaker was tripped, not yet in the provider's stats)
+    pub audit_state: u8,            // 1 (0=UNDRAWN, 1=NOT_SELECTED, 2=SELECTED, 3=AGREED, 4=DISAGREED)
+    pub audit_price_cents: u64,     // 8 (secondary oracle's price, once audited)
+    // Grace correction (original revealed prices, kept when corrected)
+    pub corrected_at: i64,          // 8 (0 = never corrected)
+    pub original_entry_cents: u64,  // 8
+    pub original_tp_cents: u64,     // 8
+    pub original_sl_cents: u64,     // 8
+    pub avg_entry_cents: u64,       // 8 (laddered only: average entry of the levels reached)
+    pub filled_weight_bps: u16,     // 2 (laddered only: weight of the levels reached)
+    pub evaluated_at: i64,          // 8
+    // Settlement proof
+    pub settled_by: Pubkey,         // 32
+    pub price_feed: Pubkey,         // 32
+    pub publish_slot: u64,          // 8
+    pub publish_time: i64,          // 8
+    pub update_hash: [u8; 32],      // 32
+    pub bump: u8,                   // 1
+    pub layout_version: u8,         // 1 (SIGNAL_COMMIT_LAYOUT_VERSION the account was written with; fields are appended after it)
+    // Layout 2
+    pub replacement_credit: bool,   // 1 (withdrawn by the provider with a free claim on its next signal for each refunded buyer)
+    // Layout 3
+    pub perf_fee_bps: u16,          // 2 (provider's performance fee when committed, 0 = flat price)
+    pub perf_fee_rate_bps: u16,     // 2 (fee per unit of buyer notional, set only by the oracle's record_outcome)
+    // Layout 4
+    pub baseline_draw_slot: u64,    // 8 (baseline signals: slot whose hash flips the direction, 0 = drawn or not a baseline)
+}
+
+/// A buyer's paid access to one signal
+#[account]
+pub struct SignalAccess {
+    pub buyer: Pubkey,              // 32
+    pub signal: Pubkey,             // 32
+    pub payment_mint: Pubkey,       // 32 (default = paid in SOL)
+    pub price_paid: u64,            // 8 (lamports, or base units of payment_mint)
+    pub purchased_at: i64,          // 8
+    pub delivered_at: i64,          // 8 (buyer-acked receipt of the content, 0 = not acked)
+    pub intent_id: u64,             // 8 (client-supplied purchase intent, 0 = none)
+    pub spend_reported: bool,       // 1 (outcome folded into the buyer's SpendReport)
+    pub refunded: bool,             // 1 (price returned after the signal was voided)
+    pub escrow_lamports: u64,       // 8 (SOL proceeds held in this account for the provider, 0 = released)
+    pub bump: u8,                   // 1
+    pub refund_bps: u16,            // 2 (guaranteed share of the escrow refunded on SL, 0 = none or already split)
+    pub refund_lamports: u64,       // 8 (split off for the buyer once the outcome is in, 0 = claimed)
+    pub credit_provider: Pubkey,    // 32 (provider whose next signal this buyer can claim free after a withdrawal, default = none)
+    pub notional_lamports: u64,     // 8 (performance-fee notional the escrow covers, 0 = flat price)
+    pub escrow_reclaimed: u64,      // 8 (escrow the buyer took back after the signal was voided)
+}
+
+impl SignalAccess {
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 1 + 2 + 8 + 32 + 8 + 8 + ACCOUNT_RESERVE_LARGE;
+    
+    /// Whether the escrow waits for the signal's outcome instead of delivery or the escrow window
+    pub fn escrow_held_for_outcome(&self) -> bool {
+        self.refund_bps > 0 || self.notional_lamports > 0
+    }
+    
+    /// Split a guaranteed escrow between provider and buyer by the signal's outcome
+    /// The buyer's share is refund_bps of the escrow if the signal hit SL or expired unrevealed;
+    /// everything left in escrow_lamports is the provider's. No-op once split or without a guarantee.
+    pub fn split_guaranteed_escrow(&mut self, commit: &SignalCommit) -> Result<()> {
+        if self.refund_bps == 0 {
+            return Ok(());
+        }
+        require!(commit.outcome_recorded || commit.expired, AgentAlphaError::OutcomeNotRecorded);
+        require!(commit.outcome != OUTCOME_VOIDED, AgentAlphaError::SignalVoided);
+        
+        let lost = commit.expired || commit.outcome == 2;
+        let refund = if lost { self.escrow_lamports * self.refund_bps as u64 / 10000 } else { 0 };
+        self.escrow_lamports -= refund;
+        self.refund_lamports = refund;
+        self.refund_bps = 0;
+        Ok(())
+    }
+}
+
+/// Provider-issued discount, redeemed at purchase by supplying the code
+/// PDA seeds: [b"coupon", provider, coupon_id (LE)]
+#[account]
+pub struct Coupon {
+    pub provider: Pubkey,           // 32
+    pub code_hash: [u8; 32],        // 32 (Coupon::code_hash of this account and the code)
+    pub percent_off: u8,            // 1
+    pub max_uses: u32,              // 4
+    pub uses: u32,                  // 4
+    pub expires_at: i64,            // 8
+    pub bump: u8,                   // 1
+    pub coupon_id: u64,             // 8 (PDA seed, chosen by the provider)
+}
+
+impl Coupon {
+    pub const SIZE: usize = 8 + 32 + 32 + 1 + 4 + 4 + 8 + 1 + 8 + ACCOUNT_RESERVE;
+    
+    /// Hash a coupon stores for `code`: SHA256(coupon address || code)
+    pub fn code_hash(coupon: &Pubkey, code: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(coupon.as_ref());
+        hasher.update(code.as_bytes());
+        hasher.finalize().into()
+    }
+    
+    /// Check the code and limits, then count one use
+    pub fn redeem(&mut self, coupon: &Pubkey, code: &str, now: i64) -> Result<()> {
+        require!(Self::code_hash(coupon, code) == self.code_hash, AgentAlphaError::InvalidCoupon);
+        require!(now < self.expires_at, AgentAlphaError::CouponExpired);
+        require!(self.uses < self.max_uses, AgentAlphaError::CouponExhausted);
+        self.uses += 1;
+        Ok(())
+    }
+}
+
+/// Provider reputation returned by get_reputation. Fields are only ever appended;
+/// a change in meaning ships as a new struct and version.
+/// - score_bps: headline hit rate shrunk towards 50% by sample size, (correct + 5) / (total + 10)
+/// - sample_size: settled headline signals (experimental signals excluded)
+/// - hit_rate_bps / avg_return_bps: raw headline stats over sample_size
+/// - last_updated: unix time of the last headline settlement (0 = never)
+/// - flags: REPUTATION_FLAG_* bits
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReputationV1 {
+    pub version: u8,
+    pub score_bps: u16,
+    pub sample_size: u64,
+    pub hit_rate_bps: u16,
+    pub avg_return_bps: i64,
+    pub last_updated: i64,
+    pub flags: u8,
+}
+
+/// A provider's weekly activity (returned by get_activity)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ActivityConsistency {
+    pub weekly_bits: u64,           // Bit n = committed a signal n weeks ago (bit 0 = this week)
+    pub active_weeks: u8,
+    pub weeks_tracked: u8,          // ACTIVITY_WEEKS, or fewer for a provider registered since
+    pub consistency_bps: u16,
+}
+
+/// A provider's headline record against the protocol baseline (returned by get_outperformance)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Outperformance {
+    pub hit_rate_bps: u16,
+    pub baseline_hit_rate_bps: u16,
+    pub excess_hit_rate_bps: i32,
+    pub avg_return_bps: i64,
+    pub baseline_avg_return_bps: i64,
+    pub excess_return_bps: i64,
+    pub sample_size: u64,
+    pub baseline_sample_size: u64,
+}
+
+/// What record_outcome would store for a set of oracle inputs (returned by preview_settlement)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SettlementPreview {
+    pub outcome: u8,                // Resolved outcome (1=TP_HIT, 2=SL_HIT, 3=EXPIRED, 6=BREAK_EVEN, 7=NOT_FILLED)
+    pub was_correct: bool,
+    pub return_bps: i32,
+    pub tp_progress_bps: i32,
+    pub avg_entry_cents: u64,       // Laddered only
+    pub filled_weight_bps: u16,     // Laddered only
+    pub both_touched: bool,
+    pub precedence_applied: u8,
+    pub vol_regime: u8,
+    pub counts_toward_reputation: bool,
+}
+
+/// Revealed signal data, as hashed at commit time
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SignalPayload {
+    pub token: String,
+    pub direction: u8,                   // 0=BUY, 1=SELL
+    pub entry_cents: u64,
+    pub tp_cents: u64,
+    pub sl_cents: u64,
+    pub timeframe_hours: u8,             // 1-72
+    pub confidence: u8,                  // 0-100
+    pub quote_currency: u8,              // 0=USD, 1=SOL, 2=BTC, 3=ETH, 4=SPL mint
+    pub quote_mint: Option<Pubkey>,      // Required when quote_currency=4
+    pub min_notional_cents: Option<u64>, // Not hashed
+    pub max_notional_cents: Option<u64>, // Not hashed
+    pub benchmark: u8,                   // 0=NONE, 1=SOL, 2=BTC, 3=ETH (not hashed)
+    pub entry_ladder: Vec<EntryLevel>,   // Empty, or 2-3 levels starting at entry_cents
+}
+
+/// One provider registered by register_providers_batch (register_provider's arguments)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProviderOnboarding {
+    pub name: String,
+    pub endpoint: String,
+    pub categories: Vec<u8>,
+    pub price_lamports: u64,
+}
+
+/// One level of a laddered entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct EntryLevel {
+    pub price_cents: u64,           // 8
+    pub weight_bps: u16,            // 2 (share of the position, levels sum to 10000)
+}
+
+impl SignalPayload {
+    /// HASH_SCHEMA_V1 hash: SHA256 of the preimage
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.preimage().as_bytes());
+        hasher.finalize().into()
+    }
+    
+    /// HASH_SCHEMA_V2 hash: the preimage under the domain::COMMIT_V2 tag
+    pub fn domain_hash(&self) -> [u8; 32] {
+        domain::tagged_hash(domain::COMMIT_V2, self.preimage().as_bytes())
+    }
+    
+    /// Hash preimage: "{token}:{direction}:{entry}:{tp}:{sl}:{timeframe}:{confidence}"
+    /// Non-USD quotes append ":{quote_currency}" (and ":{quote_mint}" for SPL quotes)
+    /// Laddered entries append ":{price}@{weight}" per level
+    pub fn preimage(&self) -> String {
+        let mut data_to_hash = format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.token, self.direction, self.entry_cents, self.tp_cents, self.sl_cents,
+            self.timeframe_hours, self.confidence
+        );
+        if self.quote_currency != QUOTE_USD {
+            data_to_hash.push_str(&format!(":{}", self.quote_currency));
+        }
+        if let Some(mint) = self.quote_mint {
+            data_to_hash.push_str(&format!(":{}", mint));
+        }
+        for level in &self.entry_ladder {
+            data_to_hash.push_str(&format!(":{}@{}", level.price_cents, level.weight_bps));
+        }
+        data_to_hash
+    }
+    
+    /// Legacy program preimage: "{token}:{direction}:{confidence}:{committed_at}"
+    /// Only token, direction and confidence are bound; the prices are taken as revealed
+    pub fn legacy_hash(&self, committed_at: i64) -> [u8; 32] {
+        let data_to_hash = format!("{}:{}:{}:{}", self.token, self.direction, self.confidence, committed_at);
+        let mut hasher = Sha256::new();
+        hasher.update(data_to_hash.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+impl SignalCommit {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1 + 1 + (1 + 32) + (1 + 8) + (1 + 32) + 1 + 1 + 8 + 1 + 2 + 2 + 8 + 1 + 1 + 32 + 4 + (4 + 16) + 1 + 8 + 8 + 8 + 1 + 1 + 1 + (1 + 32) + (1 + 8) + (1 + 8) + 1 + (4 + 3 * 10) + 32 * MAX_CITATIONS + 8 + 1 + 4 + 1 + 8 + 4 + 8 + 2 + 1 + 8 + 1 + 4 + 4 + 4 + 1 + 1 + 1 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 8 + 32 + 32 + 8 + 8 + 32 + 1 + 1 + 1 + 2 + 2 + 8 + ACCOUNT_RESERVE_LARGE;
+    
+    /// Whether the account was written with at least layout `version` (see Provider::has_layout)
+    pub fn has_layout(&self, version: u8) -> bool {
+        self.layout_version >= version
+    }
+    
+    /// Whether the signal settled BREAK_EVEN or NOT_FILLED (no reputation effect)
+    pub fn has_neutral_outcome(&self) -> bool {
+        self.outcome_recorded && matches!(self.outcome, OUTCOME_BREAK_EVEN | OUTCOME_NOT_FILLED)
+    }
+    
+    /// Performance fee the signal is sold under (0 = flat price, always for layouts before 3)
+    pub fn performance_fee_bps(&self) -> u16 {
+        if self.has_layout(3) { self.perf_fee_bps } else { 0 }
+    }
+    
+    /// Fee per unit of notional for a realized `return_bps`, capped at a 100% return
+    pub fn performance_fee_rate_bps(&self, return_bps: i32) -> u16 {
+        (self.performance_fee_bps() as i64 * return_bps.clamp(0, 10000) as i64 / 10000) as u16
+    }
+    
+    /// Whether buyers refunded for this signal get a free claim on the provider's next one
+    pub fn grants_replacement_credit(&self) -> bool {
+        self.has_layout(2) && self.replacement_credit
+    }
+    
+    /// Whether this is a baseline signal whose direction hasn't been drawn yet
+    pub fn awaits_baseline_draw(&self) -> bool {
+        self.has_layout(4) && self.baseline_draw_slot > 0
+    }
+    
+    /// Validate a payload against this commit and store it as revealed
+    /// `token_registry` is the TokenRegistry PDA for the payload's token (may be uninitialized)
+    /// The hash may match either the V1 or the legacy preimage; the matching schema is recorded
+    pub fn reveal(&mut self, payload: SignalPayload, token_registry: &AccountInfo, config: &Config, now: i64) -> Result<()> {
+        require!(!self.revealed, AgentAlphaError::AlreadyRevealed);
+        require!(!self.expired, AgentAlphaError::SignalExpired);
+        require!(!self.awaits_baseline_draw(), AgentAlphaError::BaselineAwaitingDraw);
+        require!(payload.token.len() <= 16, AgentAlphaError::TokenTooLong);
+        require!(payload.direction <= 1, AgentAlphaError::InvalidDirection);
+        require!((1..=72).contains(&payload.timeframe_hours), AgentAlphaError::InvalidTimeframe);
+        require!(payload.confidence <= 100, AgentAlphaError::InvalidConfidence);
+        require!(payload.quote_currency <= QUOTE_SPL, AgentAlphaError::InvalidQuoteCurrency);
+        require!(
+            (payload.quote_currency == QUOTE_SPL) == payload.quote_mint.is_some(),
+            AgentAlphaError::InvalidQuoteMint
+        );
+        if let (Some(min), Some(max)) = (payload.min_notional_cents, payload.max_notional_cents) {
+            require!(min <= max, AgentAlphaError::InvalidNotionalRange);
+        }
+        require!(payload.benchmark <= BENCH_ETH, AgentAlphaError::InvalidBenchmark);
+        if !payload.entry_ladder.is_empty() {
+            let ladder = &payload.entry_ladder;
+            require!(
+                (2..=MAX_ENTRY_LEVELS).contains(&ladder.len())
+                    && ladder[0].price_cents == payload.entry_cents
+                    && ladder.iter().map(|l| l.weight_bps as u32).sum::<u32>() == 10000
+                    && ladder.iter().all(|l| l.weight_bps > 0),
+                AgentAlphaError::InvalidEntryLadder
+            );
+            // Each level scales in further from the entry, without crossing the SL
+            let deeper = |a: u64, b: u64| if payload.direction == 0 { b < a } else { b > a };
+            require!(
+                ladder.windows(2).all(|w| deeper(w[0].price_cents, w[1].price_cents))
+                    && ladder.iter().all(|l| deeper(l.price_cents, payload.sl_cents)),
+                AgentAlphaError::InvalidEntryLadder
+            );
+        }
+        
+        // Registered tokens must quote prices on their tick grid
+        if !token_registry.data_is_empty() {
+            let entry = TokenRegistry::try_deserialize(&mut &token_registry.try_borrow_data()?[..])?;
+            require!(
+                entry.is_on_tick(payload.entry_cents)
+                    && entry.is_on_tick(payload.tp_cents)
+                    && entry.is_on_tick(payload.sl_cents)
+                    && payload.entry_ladder.iter().all(|l| entry.is_on_tick(l.price_cents)),
+                AgentAlphaError::PriceOffTick
+            );
+        }
+        
+        self.hash_schema = if payload.domain_hash() == self.signal_hash {
+            HASH_SCHEMA_V2
+        } else if payload.hash() == self.signal_hash {
+            HASH_SCHEMA_V1
+        } else if payload.legacy_hash(self.committed_at) == self.signal_hash {
+            HASH_SCHEMA_LEGACY
+        } else {
+            return err!(AgentAlphaError::HashMismatch);
+        };
+        require!(
+            config.accepts_reveal(self.hash_schema, self.committed_at),
+            AgentAlphaError::HashSchemaDisabled
+        );
+        
+        self.revealed = true;
+        self.token = payload.token;
+        self.direction = payload.direction;
+        self.entry_cents = payload.entry_cents;
+        self.tp_cents = payload.tp_cents;
+        self.sl_cents = payload.sl_cents;
+        self.timeframe_hours = payload.timeframe_hours;
+        self.confidence = payload.confidence;
+        self.quote_currency = payload.quote_currency;
+        self.quote_mint = payload.quote_mint;
+        self.min_notional_cents = payload.min_notional_cents;
+        self.max_notional_cents = payload.max_notional_cents;
+        self.benchmark = payload.benchmark;
+        self.entry_ladder = payload.entry_ladder;
+        self.revealed_at = now;
+        
+        emit!(SignalRevealed {
+            event_seq: event_seq(),
+            provider: self.provider,
+            signal_hash: self.signal_hash,
+            token: self.token.clone(),
+            direction: self.direction,
+            entry_cents: self.entry_cents,
+            tp_cents: self.tp_cents,
+            sl_cents: self.sl_cents,
+            timeframe_hours: self.timeframe_hours,
+            confidence: self.confidence,
+            quote_currency: self.quote_currency,
+            quote_mint: self.quote_mint,
+            min_notional_cents: self.min_notional_cents,
+            max_notional_cents: self.max_notional_cents,
+            benchmark: self.benchmark,
+            entry_ladder: self.entry_ladder.clone(),
+        });
+        
+        Ok(())
+    }
+    
+    /// Weighted average entry and filled weight of the ladder levels reached,
+    /// given the most adverse price of the window (the first level always fills)
+    pub fn ladder_fill(&self, adverse_extreme_cents: u64) -> (u64, u16) {
+        let (mut notional, mut weight) = (0u128, 0u128);
+        for (i, level) in self.entry_ladder.iter().enumerate() {
+            let reached = i == 0
+                || if self.direction == 0 {
+                    adverse_extreme_cents <= level.price_cents
+                } else {
+                    adverse_extreme_cents >= level.price_cents
+                };
+            if reached {
+                notional += level.price_cents as u128 * level.weight_bps as u128;
+                weight += level.weight_bps as u128;
+            }
+        }
+        ((notional / weight) as u64, weight as u16)
+    }
+    
+    /// Return from `entry_cents` to `price_cents` in the signal's direction
+    pub fn return_from_entry_bps(&self, entry_cents: u64, price_cents: u64) -> i32 {
+        let diff = price_cents as i128 - entry_cents as i128;
+        let signed = if self.direction == 0 { diff } else { -diff };
+        (signed * 10000 / entry_cents as i128).clamp(i32::MIN as i128, i32::MAX as i128) as i32
+    }
+    
+    /// Absolute pnl in quote cents of a position of `size_cents` notional entered and exited at the given prices
+    pub fn pnl_from_entry_cents(&self, entry_cents: u64, exit_cents: u64, size_cents: u64) -> i64 {
+        let diff = exit_cents as i128 - entry_cents as i128;
+        let signed = if self.direction == 0 { diff } else { -diff };
+        (signed * size_cents as i128 / entry_cents as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+    
+    /// Price of this signal given the provider's base price, marked up by exclusivity tier
+    pub fn tier_price(&self, base_price_lamports: u64) -> u64 {
+        let multiplier_bps = match self.exclusivity {
+            TIER_LIMITED => TIER_LIMITED_PRICE_BPS,
+            TIER_EXCLUSIVE => TIER_EXCLUSIVE_PRICE_BPS,
+            _ => 10000,
+        };
+        (base_price_lamports as u128 * multiplier_bps as u128 / 10000) as u64
+    }
+    
+    /// Stop loss the oracle settles against (an amendment's SL overrides the revealed one)
+    pub fn effective_sl_cents(&self) -> u64 {
+        self.amended_sl_cents.unwrap_or(self.sl_cents)
+    }
+    
+    /// Whether an amendment may move the SL to `sl_cents`: a mark no older than
+    /// MAX_AMENDMENT_MARK_AGE_SECS shows the price hasn't reached it
+    pub fn accepts_amended_sl(&self, sl_cents: u64, now: i64) -> bool {
+        if self.last_mark_at == 0 || now - self.last_mark_at > MAX_AMENDMENT_MARK_AGE_SECS {
+            return false;
+        }
+        if self.direction == 0 { self.last_mark_cents > sl_cents } else { self.last_mark_cents < sl_cents }
+    }
+    
+    /// Share of the entry-to-TP distance reached at `price_cents`, clamped to +/-100%
+    pub fn tp_progress_bps(&self, price_cents: u64) -> i32 {
+        let (moved, target) = if self.direction == 0 {
+            (price_cents as i128 - self.entry_cents as i128, self.tp_cents as i128 - self.entry_cents as i128)
+        } else {
+            (self.entry_cents as i128 - price_cents as i128, self.entry_cents as i128 - self.tp_cents as i128)
+        };
+        if target <= 0 { return 0; }
+        (moved * 10000 / target).clamp(-10000, 10000) as i32
+    }
+    
+    /// Volatility regime of the window, from the oracle's high/low range relative to entry
+    pub fn vol_regime(&self, high_cents: u64, low_cents: u64) -> u8 {
+        if self.entry_cents == 0 || high_cents == 0 || low_cents == 0 { return REGIME_UNKNOWN; }
+        let range_bps = (high_cents - low_cents) as u128 * 10000 / self.entry_cents as u128;
+        let bucket = REGIME_RANGE_BPS
+            .iter()
+            .position(|&bound| range_bps < bound as u128)
+            .unwrap_or(REGIME_RANGE_BPS.len());
+        bucket as u8 + 1
+    }
+    
+    /// Resolve oracle settlement inputs into what record_outcome stores
+    /// Laddered signals settle against the average entry of the levels the price reached, and a
+    /// BOTH_TOUCHED report is resolved by the configured precedence
+    #[allow(clippy::too_many_arguments)]
+    pub fn resolve_settlement(
+        &self,
+        outcome: u8,
+        final_price_cents: u64,
+        return_bps: i32,
+        adverse_extreme_cents: u64,
+        window_high_cents: u64,
+        window_low_cents: u64,
+        config: &Config,
+    ) -> Result<SettlementPreview> {
+        require!(
+            (1..=OUTCOME_BOTH_TOUCHED).contains(&outcome) || outcome == OUTCOME_NOT_FILLED,
+            AgentAlphaError::InvalidOutcome
+        );
+        require!(window_low_cents <= window_high_cents, AgentAlphaError::InvalidSettlementProof);
+        
+        let (avg_entry_cents, filled_weight_bps, return_bps) = if self.entry_ladder.is_empty() {
+            (self.avg_entry_cents, self.filled_weight_bps, return_bps)
+        } else {
+            require!(adverse_extreme_cents > 0, AgentAlphaError::InvalidSettlementProof);
+            let (avg_entry_cents, filled_weight_bps) = self.ladder_fill(adverse_extreme_cents);
+            (avg_entry_cents, filled_weight_bps, self.return_from_entry_bps(avg_entry_cents, final_price_cents))
+        };
+        
+        let both_touched = outcome == OUTCOME_BOTH_TOUCHED;
+        let precedence = config.both_touched_precedence;
+        let (outcome, return_bps) = if both_touched {
+            let entry_cents = if self.entry_ladder.is_empty() { self.entry_cents } else { avg_entry_cents };
+            match precedence {
+                PRECEDENCE_BEST_CASE => (1, self.return_from_entry_bps(entry_cents, self.tp_cents)),
+                PRECEDENCE_EXPIRE => (3, return_bps),
+                _ => (2, self.return_from_entry_bps(entry_cents, self.effective_sl_cents())),
+            }
+        } else if outcome == OUTCOME_NOT_FILLED {
+            (outcome, 0)
+        } else {
+            (outcome, return_bps)
+        };
+        let outcome = break_even_outcome(outcome, return_bps, config);
+        
+        // TP_HIT (1) = correct, SL_HIT (2) = wrong, EXPIRED (3) = correct if profitable
+        // BREAK_EVEN and NOT_FILLED are neither
+        let was_correct = match outcome {
+            1 => true,
+            3 => return_bps > 0,
+            _ => false,
+        };
+        
+        Ok(SettlementPreview {
+            outcome,
+            was_correct,
+            return_bps,
+            tp_progress_bps: if outcome == 3 { self.tp_progress_bps(final_price_cents) } else { 0 },
+            avg_entry_cents,
+            filled_weight_bps,
+            both_touched,
+            precedence_applied: if both_touched { precedence } else { 0 },
+            vol_regime: self.vol_regime(window_high_cents, window_low_cents),
+            counts_toward_reputation: !self.experimental
+                && self.confidence >= config.min_reputation_confidence
+                && !matches!(outcome, OUTCOME_BREAK_EVEN | OUTCOME_NOT_FILLED),
+        })
+    }
+    
+    /// Squared error of the stated confidence against the settled outcome, in bps (Brier score)
+    pub fn brier_bps(&self) -> u64 {
+        let forecast_bps = self.confidence as i64 * 100;
+        let observed_bps = if self.was_correct { 10000 } else { 0 };
+        ((forecast_bps - observed_bps).pow(2) / 10000) as u64
+    }
+    
+    /// Outcome of a window that traded between `low_cents` and `high_cents`: NOT_FILLED if it never
+    /// reached the entry, BOTH_TOUCHED if it reached TP and SL (which came first is unknown), else
+    /// whichever was reached, or EXPIRED
+    pub fn window_outcome(&self, high_cents: u64, low_cents: u64) -> u8 {
+        if !(low_cents..=high_cents).contains(&self.entry_cents) {
+            return OUTCOME_NOT_FILLED;
+        }
+        let (favorable, adverse) = if self.direction == 0 { (high_cents, low_cents) } else { (low_cents, high_cents) };
+        match (self.outcome_at_price(favorable) == 1, self.outcome_at_price(adverse) == 2) {
+            (true, true) => OUTCOME_BOTH_TOUCHED,
+            (true, false) => 1,
+            (false, true) => 2,
+            (false, false) => 3,
+        }
+    }
+    
+    /// Record a resolved settlement at `final_price_cents` (settled_by and the proof are the caller's)
+    pub fn apply_settlement(
+        &mut self,
+        settlement: &SettlementPreview,
+        final_price_cents: u64,
+        benchmark_return_bps: i32,
+        now: i64,
+    ) {
+        self.outcome_recorded = true;
+        self.outcome = settlement.outcome;
+        self.final_price_cents = final_price_cents;
+        self.was_correct = settlement.was_correct;
+        self.return_bps = settlement.return_bps;
+        self.evaluated_at = now;
+        self.tp_progress_bps = settlement.tp_progress_bps;
+        self.benchmark_return_bps = benchmark_return_bps;
+        self.perf_fee_rate_bps = self.performance_fee_rate_bps(settlement.return_bps);
+        self.avg_entry_cents = settlement.avg_entry_cents;
+        self.filled_weight_bps = settlement.filled_weight_bps;
+        self.both_touched = settlement.both_touched;
+        self.precedence_applied = settlement.precedence_applied;
+        self.vol_regime = settlement.vol_regime;
+    }
+    
+    /// Outcome implied by a settlement price: TP or SL if crossed, otherwise EXPIRED
+    pub fn outcome_at_price(&self, price_cents: u64) -> u8 {
+        let (tp_hit, sl_hit) = if self.direction == 0 {
+            (price_cents >= self.tp_cents, price_cents <= self.effective_sl_cents())
+        } else {
+            (price_cents <= self.tp_cents, price_cents >= self.effective_sl_cents())
+        };
+        if tp_hit { 1 } else if sl_hit { 2 } else { 3 }
+    }
+    
+    /// Return in excess of the declared benchmark (a SELL is measured against a short benchmark)
+    pub fn alpha_bps(&self) -> i32 {
+        let benchmark_bps = if self.direction == 0 {
+            self.benchmark_return_bps
+        } else {
+            self.benchmark_return_bps.saturating_neg()
+        };
+        self.return_bps.saturating_sub(benchmark_bps)
+    }
+    
+    /// Slippage of a fill against the stated entry (positive = worse than entry)
+    pub fn slippage_bps(&self, fill_price_cents: u64) -> i32 {
+        let diff = fill_price_cents as i128 - self.entry_cents as i128;
+        let signed = if self.direction == 0 { diff } else { -diff };
+        (signed * 10000 / self.entry_cents as i128).clamp(i32::MIN as i128, i32::MAX as i128) as i32
+    }
+    
+    /// Symbol of the quote leg, used by the oracle to select the settlement feed
+    pub fn quote_symbol(&self) -> String {
+        match (self.quote_currency, self.quote_mint) {
+            (QUOTE_SOL, _) => "SOL".to_string(),
+            (QUOTE_BTC, _) => "BTC".to_string(),
+            (QUOTE_ETH, _) => "ETH".to_string(),
+            (QUOTE_SPL, Some(mint)) => mint.to_string(),
+            _ => "USD".to_string(),
+        }
+    }
+}
+
+#[account]
+pub struct Config {
+    pub admin: Pubkey,              // 32
+    pub attestation_authority: Pubkey, // 32
+    pub price_oracle: Pubkey,       // 32 (posts token USD rates)
+    // Keeper rewards, paid from settlement deposits and topped up from this account's reserve
+    pub settle_reward_lamports: u64, // 8
+    pub expire_reward_lamports: u64, // 8
+    pub both_touched_precedence: u8, // 1 (0=WORST_CASE, 1=BEST_CASE, 2=EXPIRE)
+    pub hash_schema_disabled_at: [i64; 4], // 32 (per hash schema version from 1, 0 = accepting commits)
+    pub min_reputation_confidence: u8, // 1 (signals revealed below this are excluded from headline stats)
+    pub vesting_bps: u16,           // 2 (share of a new provider's SOL proceeds held back to vest, 0 = off)
+    pub vesting_period_secs: i64,   // 8 (how long a provider counts as new, and the vesting duration after that)
+    // Settlement circuit breaker, counted over BREAKER_WINDOW_SECS windows
+    pub guardian: Pubkey,           // 32 (resets a tripped breaker)
+    pub breaker_max_settlements: u32, // 4 (settlements per window, 0 = no volume limit)
+    pub breaker_max_loss_bps: u16,  // 2 (share of negative returns per window, 0 = no loss limit)
+    pub breaker_window_start: i64,  // 8
+    pub breaker_window_settlements: u32, // 4
+    pub breaker_window_losses: u32, // 4
+    pub breaker_tripped_at: i64,    // 8 (0 = reputation updates apply normally)
+    // Random audits of settled signals by a secondary oracle
+    pub audit_oracle: Pubkey,       // 32
+    pub audit_rate_bps: u16,        // 2 (share of settled signals drawn for audit, 0 = off)
+    pub audit_epoch: u32,           // 4 (AUDIT_EPOCH_SECS epoch the seed was drawn for)
+    pub audit_seed: [u8; 32],       // 32 (slot hash at the epoch's first draw)
+    pub baseline_provider: Pubkey,  // 32 (protocol provider running the coin-flip baseline, default = none)
+    pub bump: u8,                   // 1
+    pub protocol_fee_bps: u16,      // 2 (cut of every SOL payment routed to the treasury, 0 = off)
+    pub treasury: Pubkey,           // 32
+    pub break_even_bps: u16,        // 2 (EXPIRED signals closing this close to entry settle as BREAK_EVEN, 0 = off)
+    pub settlement_oracle: Pubkey,  // 32 (records outcomes and posts marks; escrow releases depend on it)
+    pub keeper: Pubkey,             // 32 (keeper whose rewards the reserve tops up, default = oracle only)
+}
+
+impl Config {
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1 + 32 + 1 + 2 + 8 + 32 + 4 + 2 + 8 + 4 + 4 + 8 + 32 + 2 + 4 + 32 + 32 + 1 + 2 + 32 + 2 + 32 + 32 + ACCOUNT_RESERVE_LARGE;
+    
+    /// Protocol fee on a SOL payment of `lamports`
+    pub fn protocol_fee(&self, lamports: u64) -> u64 {
+        lamports * self.protocol_fee_bps as u64 / 10000
+    }
+    
+    /// The treasury account passed for a fee payment, checked against the configured treasury
+    pub fn treasury_account<'info>(&self, treasury: Option<&UncheckedAccount<'info>>) -> Result<AccountInfo<'info>> {
+        treasury
+            .filter(|t| t.key() == self.treasury)
+            .map(|t| t.to_account_info())
+            .ok_or(error!(AgentAlphaError::MissingTreasuryAccount))
+    }
+    
+    /// The treasury token account passed for a fee paid in `mint`, checked to be the treasury's
+    pub fn treasury_token_account<'info>(
+        &self,
+        treasury: Option<&Account<'info, TokenAccount>>,
+        mint: Pubkey,
+    ) -> Result<AccountInfo<'info>> {
+        treasury
+            .filter(|t| t.owner == self.treasury && t.mint == mint)
+            .map(|t| t.to_account_info())
+            .ok_or(error!(AgentAlphaError::MissingTreasuryAccount))
+    }
+    
+    /// Count a settlement toward the circuit breaker's window; returns whether it just tripped the breaker
+    pub fn observe_settlement(&mut self, return_bps: i32, now: i64) -> bool {
+        if now >= self.breaker_window_start + BREAKER_WINDOW_SECS {
+            self.breaker_window_start = now;
+            self.breaker_window_settlements = 0;
+            self.breaker_window_losses = 0;
+        }
+        self.breaker_window_settlements += 1;
+        if return_bps < 0 {
+            self.breaker_window_losses += 1;
+        }
+        if self.breaker_tripped_at != 0 {
+            return false;
+        }
+        let volume_spike = self.breaker_max_settlements > 0
+            && self.breaker_window_settlements > self.breaker_max_settlements;
+        let loss_spike = self.breaker_max_loss_bps > 0
+            && self.breaker_window_settlements >= BREAKER_MIN_SAMPLE
+            && self.breaker_window_losses as u64 * 10000 > self.breaker_max_loss_bps as u64 * self.breaker_window_settlements as u64;
+        if volume_spike || loss_spike {
+            self.breaker_tripped_at = now;
+        }
+        volume_spike || loss_spike
+    }
+    
+    /// Portion of `proceeds` held back from a provider registered at `created_at`
+    pub fn vesting_holdback(&self, created_at: i64, proceeds: u64, now: i64) -> u64 {
+        if now < created_at + self.vesting_period_secs {
+            proceeds * self.vesting_bps as u64 / 10000
+        } else {
+            0
+        }
+    }
+    
+    /// Whether a keeper's rewards may be topped up from the reserve: the settlement oracle or the configured keeper
+    pub fn is_trusted_keeper(&self, keeper: &Pubkey) -> bool {
+        *keeper == self.settlement_oracle || (*keeper == self.keeper && self.keeper != Pubkey::default())
+    }
+    
+    /// Whether new signals may be committed under a hash schema version
+    pub fn accepts_commits(&self, schema: u8) -> bool {
+        self.hash_schema_disabled_at[(schema - 1) as usize] == 0
+    }
+    
+    /// Whether new signals may be committed at all: the hash isn't known until reveal, so it's
+    /// enough that one of the current schemas is enabled
+    pub fn accepts_new_commits(&self) -> bool {
+        self.accepts_commits(HASH_SCHEMA_V1) || self.accepts_commits(HASH_SCHEMA_V2)
+    }
+    
+    /// Whether a commit may be revealed: its schema is enabled, or it was committed before the schema was disabled
+    /// V1 and LEGACY commits are also bounded by their fixed sunset dates, whatever the admin sets
+    pub fn accepts_reveal(&self, schema: u8, committed_at: i64) -> bool {
+        let disabled_at = self.hash_schema_disabled_at[(schema - 1) as usize];
+        let sunset = match schema {
+            HASH_SCHEMA_V1 => HASH_SCHEMA_V1_SUNSET,
+            HASH_SCHEMA_LEGACY => LEGACY_HASH_SCHEMA_CUTOFF,
+            _ => i64::MAX,
+        };
+        committed_at < sunset && (disabled_at == 0 || committed_at < disabled_at)
+    }
+}
+
+#[account]
+pub struct TokenRegistry {
+    pub symbol: String,             // 4 + 16
+    pub tick_size_cents: u64,       // 8
+    pub mint: Pubkey,               // 32 (SPL mint, default = none)
+    pub decimals: u8,               // 1
+    pub usd_rate_micros: u64,       // 8 (USD micro-dollars per whole token, posted by the price oracle)
+    pub rate_updated_at: i64,       // 8
+    pub halted_at: i64,             // 8 (trading halt declared by the admin, 0 = trading)
+    pub bump: u8,                   // 1
+    pub display_decimals: u8,       // 1 (fractional digits prices are shown with)
+    pub display_exponent: i8,       // 1 (power of ten prices are shown at)
+    pub display_updated_at: i64,    // 8 (0 = never set, prices show with PriceDisplay::DEFAULT)
+    pub social_settlement: bool,    // 1 (no oracle coverage: signals may settle by reporter panel)
+}
+
+impl TokenRegistry {
+    pub const SIZE: usize = 8 + (4 + 16) + 8 + 32 + 1 + 8 + 8 + 8 + 1 + 1 + 1 + 8 + 1 + ACCOUNT_RESERVE_LARGE;
+    
+    pub fn is_on_tick(&self, price_cents: u64) -> bool {
+        price_cents.checked_rem(self.tick_size_cents) == Some(0)
+    }
+    
+    pub fn rate_is_fresh(&self, now: i64) -> bool {
+        self.usd_rate_micros > 0 && now - self.rate_updated_at <= MAX_RATE_AGE_SECS
+    }
+    
+    /// How clients render this token's prices
+    pub fn price_display(&self) -> PriceDisplay {
+        if self.display_updated_at == 0 {
+            return PriceDisplay::DEFAULT;
+        }
+        PriceDisplay { decimals: self.display_decimals, exponent: self.display_exponent }
+    }
+    
+    /// A price in cents rendered with this token's display metadata
+    pub fn format_price(&self, price_cents: u64) -> String {
+        format_price_cents(price_cents, self.price_display())
+            .or_else(|| format_price_cents(price_cents, PriceDisplay::DEFAULT))
+            .unwrap_or_default()
+    }
+    
+    /// Token base units worth `lamports` at the posted USD rates, rounded up (None on overflow)
+    pub fn token_amount_for_lamports(&self, lamports: u64, sol_usd_rate_micros: u64) -> Option<u64> {
+        let numerator = (lamports as u128)
+            .checked_mul(sol_usd_rate_micros as u128)?
+            .checked_mul(10u128.checked_pow(self.decimals as u32)?)?;
+        let denominator = 1_000_000_000u128 * self.usd_rate_micros as u128;
+        u64::try_from(numerator.div_ceil(denominator)).ok()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CurvePoint {
+    pub timestamp: i64,             // 8
+    pub cumulative_return_bps: i64, // 8
+}
+
+/// Ring buffer of the last CURVE_POINTS settlements for sparkline rendering
+#[account]
+pub struct ProviderCurve {
+    pub provider: Pubkey,                     // 32
+    pub head: u8,                             // 1 (next slot to write)
+    pub len: u8,                              // 1
+    pub points: [CurvePoint; CURVE_POINTS],   // 16 * 64
+    pub bump: u8,                             // 1
+}
+
+impl ProviderCurve {
+    pub const SIZE: usize = 8 + 32 + 1 + 1 + 16 * CURVE_POINTS + 1 + ACCOUNT_RESERVE;
+    
+    pub fn push(&mut self, timestamp: i64, cumulative_return_bps: i64) {
+        self.points[self.head as usize] = CurvePoint { timestamp, cumulative_return_bps };
+        self.head = ((self.head as usize + 1) % CURVE_POINTS) as u8;
+        if (self.len as usize) < CURVE_POINTS {
+            self.len += 1;
+        }
+    }
+    
+    /// Stored points, oldest first
+    pub fn chronological(&self) -> impl Iterator<Item = &CurvePoint> {
+        let start = (self.head as usize + CURVE_POINTS - self.len as usize) % CURVE_POINTS;
+        (0..self.len as usize).map(move |i| &self.points[(start + i) % CURVE_POINTS])
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct OracleTask {
+    pub signal: Pubkey,             // 32
+    pub due_at: i64,                // 8 (end of the signal's evaluation window)
+    pub state: u8,                  // 1 (0=EMPTY, 1=PENDING, 2=CLAIMED)
+    pub claimed_by: Pubkey,         // 32
+    pub claimed_at: i64,            // 8
+}
+
+/// Settlement work queue that oracles poll instead of scanning every SignalCommit
+#[account]
+pub struct OracleTaskQueue {
+    pub tasks: [OracleTask; TASK_QUEUE_SLOTS], // 81 * 64
+    pub bump: u8,                              // 1
+}
+
+impl OracleTaskQueue {
+    pub const SIZE: usize = 8 + 81 * TASK_QUEUE_SLOTS + 1 + ACCOUNT_RESERVE;
+    
+    /// Queue a signal for settlement in the first free slot; a full queue is skipped
+    /// (oracles fall back to scanning) rather than blocking the reveal
+    pub fn enqueue(&mut self, signal: Pubkey, due_at: i64) {
+        if let Some((index, task)) = self.tasks.iter_mut().enumerate().find(|(_, t)| t.state == TASK_EMPTY) {
+            *task = OracleTask { signal, due_at, state: TASK_PENDING, claimed_by: Pubkey::default(), claimed_at: 0 };
+            emit!(OracleTaskChanged {
+                event_seq: event_seq(),
+                index: index as u8,
+                signal,
+                state: TASK_PENDING,
+                oracle: Pubkey::default(),
+            });
+        }
+    }
+}
+
+/// Escrowed reward for the best call on a token within a window
+#[account]
+pub struct SignalBounty {
+    pub creator: Pubkey,            // 32
+    pub bounty_id: u64,             // 8
+    pub token: String,              // 4 + 16
+    pub reward_lamports: u64,       // 8
+    pub min_confidence: u8,         // 1
+    pub created_at: i64,            // 8
+    pub deadline: i64,              // 8 (entries must be committed before, and revealed and entered by, this time)
+    pub entries: u32,               // 4
+    pub best_signal: Option<Pubkey>, // 1 + 32
+    pub best_return_bps: i32,       // 4
+    pub bump: u8,                   // 1
+}
+
+impl SignalBounty {
+    pub const SIZE: usize = 8 + 32 + 8 + (4 + 16) + 8 + 1 + 8 + 8 + 4 + (1 + 32) + 4 + 1 + ACCOUNT_RESERVE;
+    
+    /// End of the scoring window: entries revealed by the deadline have settled MAX_TIMEFRAME_SECS
+    /// after it and can be scored for BOUNTY_SCORING_SECS more; after that the winner is final and
+    /// the bounty can be claimed or closed
+    pub fn claimable_at(&self) -> i64 {
+        self.deadline + MAX_TIMEFRAME_SECS + BOUNTY_SCORING_SECS
+    }
+}
+
+/// A buyer's realized fill for a signal (one per reporter per signal)
+#[account]
+pub struct FillReport {
+    pub signal: Pubkey,             // 32
+    pub reporter: Pubkey,           // 32
+    pub fill_price_cents: u64,      // 8
+    pub slippage_bps: i32,          // 4
+    pub reported_at: i64,           // 8
+    pub bump: u8,                   // 1
+}
+
+impl FillReport {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 4 + 8 + 1 + ACCOUNT_RESERVE;
+}
+
+/// A buyer's logged execution of a purchased signal
+#[account]
+pub struct ExecutionRecord {
+    pub buyer: Pubkey,              // 32
+    pub signal: Pubkey,             // 32
+    pub provider: Pubkey,           // 32
+    pub entry_price_cents: u64,     // 8
+    pub exit_price_cents: u64,      // 8
+    pub size_cents: u64,            // 8 (position notional)
+    pub realized_return_bps: i32,   // 4
+    pub realized_pnl_cents: i64,    // 8 (absolute pnl on size_cents, in quote cents)
+    pub slippage_bps: i32,          // 4 (entry vs the signal's stated entry)
+    pub pnl_hash: [u8; 32],         // 32
+    pub recorded_at: i64,           // 8
+    pub bump: u8,                   // 1
+}
+
+impl ExecutionRecord {
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 4 + 8 + 4 + 32 + 8 + 1 + ACCOUNT_RESERVE;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Notice {
+    pub kind: u8,                   // 1 (1=NEW_SIGNAL, 2=OUTCOME)
+    pub signal: Pubkey,             // 32
+    pub posted_at: i64,             // 8
+}
+
+/// Pull-based catch-up queue for a buyer's agent
+/// Notice `seq` lives in slot `seq % MAILBOX_SLOTS`; unacked notices are [acked_seq, next_seq)
+#[account]
+pub struct Mailbox {
+    pub owner: Pubkey,                        // 32
+    pub next_seq: u64,                        // 8
+    pub acked_seq: u64,                       // 8
+    pub dropped: u64,                         // 8 (unacked notices overwritten when full)
+    pub notices: [Notice; MAILBOX_SLOTS],     // 41 * 16
+    pub bump: u8,                             // 1
+}
+
+impl Mailbox {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 41 * MAILBOX_SLOTS + 1 + ACCOUNT_RESERVE;
+    
+    /// Append a notice, overwriting the oldest unacked one when full
+    pub fn push(&mut self, notice: Notice) -> u64 {
+        let seq = self.next_seq;
+        if seq - self.acked_seq == MAILBOX_SLOTS as u64 {
+            self.acked_seq += 1;
+            self.dropped += 1;
+        }
+        self.notices[(seq % MAILBOX_SLOTS as u64) as usize] = notice;
+        self.next_seq += 1;
+        seq
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BundleMember {
+    pub provider: Pubkey,           // 32
+    pub split_bps: u16,             // 2 (share of bundle revenue)
+}
+
+/// Curated product over several providers with its own price and reputation
+#[account]
+pub struct Bundle {
+    pub curator: Pubkey,            // 32
+    pub bundle_id: u64,             // 8
+    pub name: String,               // 4 + 64
+    pub price_lamports: u64,        // 8
+    pub members: Vec<BundleMember>, // 4 + 34 * 8
+    pub total_signals: u64,         // 8
+    pub correct_signals: u64,       // 8
+    pub total_return_bps: i64,      // 8
+    pub created_at: i64,            // 8
+    pub bump: u8,                   // 1
+}
+
+impl Bundle {
+    pub const SIZE: usize = 8 + 32 + 8 + (4 + 64) + 8 + (4 + 34 * MAX_BUNDLE_MEMBERS) + 8 + 8 + 8 + 8 + 1 + ACCOUNT_RESERVE_LARGE;
+    
+    pub fn hit_rate_bps(&self) -> u64 {
+        if self.total_signals == 0 { return 0; }
+        (self.correct_signals * 10000) / self.total_signals
+    }
+    
+    /// Share of bundle revenue kept by the curator
+    pub fn curator_split_bps(&self) -> u16 {
+        10000 - self.members.iter().map(|m| m.split_bps).sum::<u16>()
+    }
+}
+
+#[account]
+pub struct BundleTally {
+    pub bump: u8,                   // 1
+}
+
+impl BundleTally {
+    pub const SIZE: usize = 8 + 1 + ACCOUNT_RESERVE;
+}
+
+/// Curator stake vouching for a provider; earns a share of its sales, slashed on proven fraud
+#[account]
+pub struct CuratorBond {
+    pub curator: Pubkey,            // 32
+    pub provider: Pubkey,           // 32
+    pub amount: u64,                // 8
+    pub reward_index: u128,         // 16 (provider index at last settlement)
+    pub unbond_at: i64,             // 8 (0 = bonded)
+    pub bonded_at: i64,             // 8
+    pub bump: u8,                   // 1
+}
+
+impl CuratorBond {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 16 + 8 + 8 + 1 + ACCOUNT_RESERVE;
+    
+    /// Lamports the bond votes with: none once unbonding or behind a provider proven fraudulent
+    pub fn voting_weight(&self, provider: &Provider) -> u64 {
+        if self.unbond_at != 0 || provider.fraud_proven {
+            return 0;
+        }
+        self.amount
+    }
+    
+    /// Rewards accrued since the last settlement (none while unbonding)
+    pub fn pending_rewards(&self, index: u128) -> u64 {
+        if self.unbond_at != 0 {
+            return 0;
+        }
+        (self.amount as u128 * (index - self.reward_index) / REWARD_INDEX_SCALE) as u64
+    }
+}
+
+/// A proposed change to one governed Config parameter
+#[account]
+pub struct Proposal {
+    pub proposer: Pubkey,           // 32
+    pub id: u64,                    // 8 (chosen by the proposer, unique per proposer)
+    pub param: u8,                  // 1 (PARAM_*)
+    pub value: u64,                 // 8 (numeric parameters)
+    pub key_value: Pubkey,          // 32 (oracle parameters)
+    pub created_at: i64,            // 8 (bonds made from here on don't vote)
+    pub voting_ends_at: i64,        // 8
+    pub yes_weight: u64,            // 8
+    pub no_weight: u64,             // 8
+    pub executed: bool,             // 1
+    pub bump: u8,                   // 1
+}
+
+impl Proposal {
+    pub const SIZE: usize = 8 + 32 + 8 + 1 + 8 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + ACCOUNT_RESERVE;
+    
+    pub fn passed(&self) -> bool {
+        self.yes_weight > self.no_weight
+            && self.yes_weight.saturating_add(self.no_weight) >= GOVERNANCE_QUORUM_LAMPORTS
+    }
+    
+    /// The bounds the matching admin setter enforces
+    pub fn validate(&self, config: &Config) -> Result<()> {
+        let valid = match self.param {
+            PARAM_PROTOCOL_FEE_BPS => {
+                require!(
+                    self.value == 0 || config.treasury != Pubkey::default(),
+                    AgentAlphaError::MissingTreasuryAccount
+                );
+                self.value <= MAX_PROTOCOL_FEE_BPS as u64
+            }
+            PARAM_BREAK_EVEN_BPS => self.value <= MAX_BREAK_EVEN_BPS as u64,
+            PARAM_BOTH_TOUCHED_PRECEDENCE => self.value <= PRECEDENCE_EXPIRE as u64,
+            PARAM_MIN_REPUTATION_CONFIDENCE => self.value <= 100,
+            PARAM_AUDIT_RATE_BPS => self.value <= 10000,
+            PARAM_SETTLEMENT_ORACLE | PARAM_PRICE_ORACLE => self.key_value != Pubkey::default(),
+            PARAM_AUDIT_ORACLE => true,
+            _ => false,
+        };
+        require!(valid, AgentAlphaError::InvalidGovernedParam);
+        Ok(())
+    }
+    
+    /// Write the change into Config (after validate)
+    pub fn apply(&self, config: &mut Config) {
+        match self.param {
+            PARAM_PROTOCOL_FEE_BPS => config.protocol_fee_bps = self.value as u16,
+            PARAM_BREAK_EVEN_BPS => config.break_even_bps = self.value as u16,
+            PARAM_BOTH_TOUCHED_PRECEDENCE => config.both_touched_precedence = self.value as u8,
+            PARAM_MIN_REPUTATION_CONFIDENCE => config.min_reputation_confidence = self.value as u8,
+            PARAM_AUDIT_RATE_BPS => config.audit_rate_bps = self.value as u16,
+            PARAM_SETTLEMENT_ORACLE => config.settlement_oracle = self.key_value,
+            PARAM_PRICE_ORACLE => config.price_oracle = self.key_value,
+            PARAM_AUDIT_ORACLE => config.audit_oracle = self.key_value,
+            _ => {}
+        }
+    }
+}
+
+/// A curator bond's vote on a proposal
+#[account]
+pub struct Vote {
+    pub proposal: Pubkey,           // 32
+    pub curator_bond: Pubkey,       // 32
+    pub weight: u64,                // 8
+    pub approve: bool,              // 1
+    pub bump: u8,                   // 1
+}
+
+impl Vote {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1 + 1 + ACCOUNT_RESERVE;
+}
+
+/// A buyer following a provider (counted in Provider::follower_count)
+#[account]
+pub struct Follow {
+    pub provider: Pubkey,           // 32
+    pub follower: Pubkey,           // 32
+    pub followed_at: i64,           // 8
+    pub bump: u8,                   // 1
+}
+
+impl Follow {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1 + ACCOUNT_RESERVE;
+}
+
+/// Collects forfeited confidence stakes and pays bonuses on winning ones
+#[account]
+pub struct StakePool {
+    pub total_forfeited: u64,       // 8
+    pub total_bonuses: u64,         // 8
+    pub bump: u8,                   // 1
+}
+
+impl StakePool {
+    pub const SIZE: usize = 8 + 8 + 8 + 1 + ACCOUNT_RESERVE;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct EmbargoRule {
+    pub category: u8,               // 1
+    pub buyer_classes: u8,          // 1 (buyers with any of these class bits are blocked)
+}
+
+/// Provider's purchase embargo: blocks signal categories for classes of buyers
+#[account]
+pub struct PurchasePolicy {
+    pub provider: Pubkey,           // 32
+    pub classifier: Pubkey,         // 32 (assigns BuyerClass records)
+    pub rules: Vec<EmbargoRule>,    // 4 + 2 * 8
+    pub bump: u8,                   // 1
+}
+
+impl PurchasePolicy {
+    pub const SIZE: usize = 8 + 32 + 32 + (4 + 2 * 8) + 1 + ACCOUNT_RESERVE;
+    
+    pub fn blocks(&self, category: u8, buyer_classes: u8) -> bool {
+        self.rules.iter().any(|r| r.category == category && r.buyer_classes & buyer_classes != 0)
+    }
+}
+
+/// A buyer's classes under one purchase policy
+#[account]
+pub struct BuyerClass {
+    pub policy: Pubkey,             // 32
+    pub buyer: Pubkey,              // 32
+    pub classes: u8,                // 1 (bitmask)
+    pub updated_at: i64,            // 8
+    pub bump: u8,                   // 1
+}
+
+impl BuyerClass {
+    pub const SIZE: usize = 8 + 32 + 32 + 1 + 8 + 1 + ACCOUNT_RESERVE;
+}
+
+/// A staked price reporter for oracle-free settlement
+#[account]
+pub struct Reporter {
+    pub authority: Pubkey,          // 32
+    pub stake_lamports: u64,        // 8 (held in this account, less slashes)
+    pub observations: u64,          // 8
+    pub outliers: u64,              // 8 (observations slashed as outliers)
+    pub slashed_lamports: u64,      // 8
+    pub unbond_at: i64,             // 8 (0 = active)
+    pub bump: u8,                   // 1
+}
+
+impl Reporter {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + ACCOUNT_RESERVE;
+}
+
+/// Active reporters, in registration order; social-settlement panels are drawn from it
+#[account]
+pub struct ReporterSet {
+    pub members: Vec<Pubkey>,       // 4 + 32 * MAX_PANEL_REPORTERS
+    pub bump: u8,                   // 1
+}
+
+impl ReporterSet {
+    pub const SIZE: usize = 8 + (4 + 32 * MAX_PANEL_REPORTERS) + 1 + ACCOUNT_RESERVE;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PriceObservation {
+    pub reporter: Pubkey,           // 32
+    pub price_cents: u64,           // 8 (at the end of the window)
+    pub high_cents: u64,            // 8 (over the window)
+    pub low_cents: u64,             // 8 (over the window)
+    pub outlier: bool,              // 1 (set at finalization)
+    pub slashed: bool,              // 1
+}
+
+/// Reporter-panel settlement of one signal the oracle did not settle
+#[account]
+pub struct SocialSettlement {
+    pub signal: Pubkey,             // 32
+    pub opened_by: Pubkey,          // 32
+    pub opened_at: i64,             // 8
+    pub observations: Vec<PriceObservation>, // 4 + 58 * SOCIAL_PANEL_SIZE (revealed)
+    pub median_price_cents: u64,    // 8 (median close of the non-outlier observations)
+    pub finalized: bool,            // 1
+    pub bump: u8,                   // 1
+    pub panel: [Pubkey; SOCIAL_PANEL_SIZE], // 32 * SOCIAL_PANEL_SIZE (drawn at opening)
+    pub commitments: [[u8; 32]; SOCIAL_PANEL_SIZE], // 32 * SOCIAL_PANEL_SIZE (per panel seat, zero = none)
+    pub commit_deadline: i64,       // 8
+    pub reveal_deadline: i64,       // 8
+    pub no_shows_slashed: u8,       // 1 (bit per panel seat slashed for not revealing)
+}
+
+impl SocialSettlement {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + (4 + 58 * SOCIAL_PANEL_SIZE) + 8 + 1 + 1 + 32 * SOCIAL_PANEL_SIZE + 32 * SOCIAL_PANEL_SIZE + 8 + 8 + 1 + ACCOUNT_RESERVE;
+    
+    /// Panel seat of a reporter, None if it wasn't drawn
+    pub fn seat(&self, reporter: &Pubkey) -> Option<usize> {
+        self.panel.iter().position(|p| p == reporter)
+    }
+    
+    /// Commitment a panel member submits: domain::OBSERVATION_V1 tag over signal || reporter || price (LE) || salt
+    pub fn observation_hash(
+        signal: &Pubkey,
+        reporter: &Pubkey,
+        price_cents: u64,
+        high_cents: u64,
+        low_cents: u64,
+        salt: &[u8; 32],
+    ) -> [u8; 32] {
+        let mut message = Vec::with_capacity(32 + 32 + 8 + 8 + 8 + 32);
+        message.extend_from_slice(signal.as_ref());
+        message.extend_from_slice(reporter.as_ref());
+        message.extend_from_slice(&price_cents.to_le_bytes());
+        message.extend_from_slice(&high_cents.to_le_bytes());
+        message.extend_from_slice(&low_cents.to_le_bytes());
+        message.extend_from_slice(salt);
+        domain::tagged_hash(domain::OBSERVATION_V2, &message)
+    }
+    
+    /// Median close, high and low of the observations not flagged as outliers (of all of them
+    /// when every one is)
+    pub fn median_observation(&self) -> (u64, u64, u64) {
+        let mut kept: Vec<&PriceObservation> = self.observations.iter().filter(|o| !o.outlier).collect();
+        if kept.is_empty() {
+            kept = self.observations.iter().collect();
+        }
+        (
+            median_cents(kept.iter().map(|o| o.price_cents).collect()),
+            median_cents(kept.iter().map(|o| o.high_cents).collect()),
+            median_cents(kept.iter().map(|o| o.low_cents).collect()),
+        )
+    }
+}
+
+/// A hot key allowed to buy signals for its owner, paid from this account's balance
+#[account]
+pub struct BuyerSession {
+    pub owner: Pubkey,              // 32
+    pub session_key: Pubkey,        // 32
+    pub daily_budget_lamports: u64, // 8
+    pub day: i64,                   // 8 (UTC day index spent_today applies to)
+    pub spent_today: u64,           // 8
+    pub expires_at: i64,            // 8
+    pub providers: Vec<Pubkey>,     // 4 + 32 * 8 (approved providers)
+    pub created_at: i64,            // 8
+    pub bump: u8,                   // 1
+}
+
+impl BuyerSession {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + (4 + 32 * MAX_SESSION_PROVIDERS) + 8 + 1 + ACCOUNT_RESERVE;
+    
+    /// Check a purchase against the session's expiry, provider list and daily budget, and count it
+    pub fn authorize_spend(&mut self, provider: &Pubkey, lamports: u64, now: i64) -> Result<()> {
+        require!(now < self.expires_at, AgentAlphaError::SessionExpired);
+        require!(self.providers.contains(provider), AgentAlphaError::ProviderNotApproved);
+        let day = now.div_euclid(86400);
+        if day != self.day {
+            self.day = day;
+            self.spent_today = 0;
+        }
+        require!(
+            self.spent_today + lamports <= self.daily_budget_lamports,
+            AgentAlphaError::SessionBudgetExceeded
+        );
+        self.spent_today += lamports;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ProviderSpend {
+    pub provider: Pubkey,           // 32
+    pub spent_lamports: u64,        // 8
+    pub purchases: u32,             // 4
+    pub settled: u32,               // 4
+    pub correct: u32,               // 4
+    pub total_return_bps: i64,      // 8
+}
+
+/// A buyer's signal spend and the settled performance of what they bought, per provider
+#[account]
+pub struct SpendReport {
+    pub buyer: Pubkey,              // 32
+    pub total_spent_lamports: u64,  // 8 (SOL list price, including token-paid purchases)
+    pub total_purchases: u64,       // 8
+    pub total_settled: u64,         // 8
+    pub total_correct: u64,         // 8
+    pub total_return_bps: i64,      // 8
+    pub providers: Vec<ProviderSpend>, // 4 + 60 * MAX_SPEND_PROVIDERS
+    pub bump: u8,                   // 1
+}
+
+impl SpendReport {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + (4 + 60 * MAX_SPEND_PROVIDERS) + 1 + ACCOUNT_RESERVE;
+    
+    /// The provider's itemized entry, added while there is room
+    fn entry(&mut self, provider: Pubkey) -> Option<&mut ProviderSpend> {
+        let index = match self.providers.iter().position(|p| p.provider == provider) {
+            Some(index) => index,
+            None if self.providers.len() < MAX_SPEND_PROVIDERS => {
+                self.providers.push(ProviderSpend { provider, ..Default::default() });
+                self.providers.len() - 1
+            }
+            None => return None,
+        };
+        self.providers.get_mut(index)
+    }
+    
+    pub fn record_purchase(&mut self, provider: Pubkey, price_lamports: u64) {
+        self.total_spent_lamports += price_lamports;
+        self.total_purchases += 1;
+        if let Some(entry) = self.entry(provider) {
+            entry.spent_lamports += price_lamports;
+            entry.purchases += 1;
+        }
+    }
+    
+    pub fn record_outcome(&mut self, provider: Pubkey, was_correct: bool, return_bps: i32) {
+        self.total_settled += 1;
+        self.total_return_bps += return_bps as i64;
+        if was_correct {
+            self.total_correct += 1;
+        }
+        if let Some(entry) = self.entry(provider) {
+            entry.settled += 1;
+            entry.total_return_bps += return_bps as i64;
+            if was_correct {
+                entry.correct += 1;
+            }
+        }
+    }
+}
+
+/// Marketplace-wide open interest in one token: the revealed signals still awaiting settlement
+#[account]
+pub struct TokenStats {
+    pub token: String,              // 4 + 16
+    pub active_signals: u32,        // 4
+    pub active_buys: u32,           // 4
+    pub active_sells: u32,          // 4
+    pub active_confidence_sum: u64, // 8
+    pub total_revealed: u64,        // 8
+    pub total_settled: u64,         // 8
+    pub total_correct: u64,         // 8
+    pub updated_at: i64,            // 8
+    pub bump: u8,                   // 1
+}
+
+impl TokenStats {
+    pub const SIZE: usize = 8 + (4 + 16) + 4 + 4 + 4 + 8 + 8 + 8 + 8 + 8 + 1 + ACCOUNT_RESERVE;
+    
+    /// Count a just-revealed signal as active
+    pub fn record_reveal(&mut self, commit: &mut SignalCommit, now: i64) {
+        commit.in_token_stats = true;
+        self.active_signals += 1;
+        if commit.direction == 0 {
+            self.active_buys += 1;
+        } else {
+            self.active_sells += 1;
+        }
+        self.active_confidence_sum += commit.confidence as u64;
+        self.total_revealed += 1;
+        self.updated_at = now;
+    }
+    
+    /// Move a settled signal out of the active set; false if it was never counted
+    pub fn record_settlement(&mut self, commit: &mut SignalCommit, now: i64) -> bool {
+        if !std::mem::take(&mut commit.in_token_stats) {
+            return false;
+        }
+        self.active_signals -= 1;
+        if commit.direction == 0 {
+            self.active_buys -= 1;
+        } else {
+            self.active_sells -= 1;
+        }
+        self.active_confidence_sum -= commit.confidence as u64;
+        self.total_settled += 1;
+        if commit.was_correct {
+            self.total_correct += 1;
+        }
+        self.updated_at = now;
+        true
+    }
+    
+    /// Net direction of the active signals in bps (+10000 = all BUY, -10000 = all SELL)
+    pub fn direction_skew_bps(&self) -> i16 {
+        if self.active_signals == 0 {
+            return 0;
+        }
+        ((self.active_buys as i64 - self.active_sells as i64) * 10000 / self.active_signals as i64) as i16
+    }
+    
+    /// Mean stated confidence of the active signals (0-100)
+    pub fn avg_confidence(&self) -> u8 {
+        self.active_confidence_sum.checked_div(self.active_signals as u64).unwrap_or(0) as u8
+    }
+}
+
+/// Reputation-weighted consensus on one token: each open revealed signal counts with its
+/// provider's reputation score at reveal times its stated confidence
+#[account]
+pub struct TokenConsensus {
+    pub token: String,              // 4 + 16
+    pub open_signals: u32,          // 4
+    pub buy_weight: u64,            // 8 (sum of score_bps * confidence over open BUY signals)
+    pub sell_weight: u64,           // 8 (same over open SELL signals)
+    pub updated_at: i64,            // 8
+    pub bump: u8,                   // 1
+}
+
+impl TokenConsensus {
+    pub const SIZE: usize = 8 + (4 + 16) + 4 + 8 + 8 + 8 + 1 + ACCOUNT_RESERVE;
+    
+    /// Add a just-revealed signal with its provider's current reputation score
+    pub fn record_reveal(&mut self, commit: &mut SignalCommit, score_bps: u16, now: i64) {
+        let weight = score_bps as u32 * commit.confidence as u32;
+        if weight == 0 {
+            return;
+        }
+        commit.consensus_weight = weight;
+        self.open_signals += 1;
+        if commit.direction == 0 {
+            self.buy_weight += weight as u64;
+        } else {
+            self.sell_weight += weight as u64;
+        }
+        self.updated_at = now;
+    }
+    
+    /// Remove a settled signal with the weight it was added with; false if it was never counted
+    pub fn record_settlement(&mut self, commit: &mut SignalCommit, now: i64) -> bool {
+        let weight = std::mem::take(&mut commit.consensus_weight) as u64;
+        if weight == 0 {
+            return false;
+        }
+        self.open_signals -= 1;
+        if commit.direction == 0 {
+            self.buy_weight -= weight;
+        } else {
+            self.sell_weight -= weight;
+        }
+        self.updated_at = now;
+        true
+    }
+    
+    /// Net weighted direction in bps (+10000 = all weight on BUY, -10000 = all on SELL, 0 = none open)
+    pub fn consensus_bps(&self) -> i16 {
+        let total = self.buy_weight + self.sell_weight;
+        if total == 0 {
+            return 0;
+        }
+        ((self.buy_weight as i128 - self.sell_weight as i128) * 10000 / total as i128) as i16
+    }
+}
+
+/// An independent prober authorized to attest provider endpoint health
+#[account]
+pub struct Prober {
+    pub authority: Pubkey,          // 32
+    pub probes: u64,                // 8
+    pub registered_at: i64,         // 8
+    pub bump: u8,                   // 1
+}
+
+impl Prober {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 1 + ACCOUNT_RESERVE;
+}
+
+/// A provider's price for signals of one category, with the same notice on increases as price_lamports
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CategoryPrice {
+    pub category: u8,               // 1
+    pub price_lamports: u64,        // 8
+    pub scheduled_price_lamports: u64, // 8 (announced increase, 0 = none)
+    pub price_effective_at: i64,    // 8
+}
+
+impl CategoryPrice {
+    /// Price buyers pay at `now`, taking any announced increase into account
+    pub fn effective_price_lamports(&self, now: i64) -> u64 {
+        if self.scheduled_price_lamports > 0 && now >= self.price_effective_at {
+            self.scheduled_price_lamports
+        } else {
+            self.price_lamports
+        }
+    }
+    
+    /// Change the price: decreases apply immediately, increases after PRICE_NOTICE_SECS
+    /// Returns whether the change was scheduled rather than applied
+    pub fn reprice(&mut self, price_lamports: u64, now: i64) -> bool {
+        self.price_lamports = self.effective_price_lamports(now);
+        if price_lamports <= self.price_lamports {
+            self.price_lamports = price_lamports;
+            self.scheduled_price_lamports = 0;
+            self.price_effective_at = 0;
+            false
+        } else {
+            self.scheduled_price_lamports = price_lamports;
+            self.price_effective_at = now + PRICE_NOTICE_SECS;
+            true
+        }
+    }
+}
+
+/// One prober observation of a provider's delivery endpoint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct EndpointProbe {
+    pub prober: Pubkey,             // 32 (default = empty slot)
+    pub observed_at: i64,           // 8
+    pub latency_ms: u32,            // 4
+    pub live: bool,                 // 1
+}
+
+/// Audit agreement record of one settling oracle
+#[account]
+pub struct OracleRecord {
+    pub oracle: Pubkey,             // 32
+    pub audits: u64,                // 8
+    pub agreements: u64,            // 8
+    pub bump: u8,                   // 1
+}
+
+impl OracleRecord {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 1 + ACCOUNT_RESERVE;
+    
+    /// Share of audited settlements the secondary oracle agreed with (10000 with no audits)
+    pub fn agreement_bps(&self) -> u64 {
+        (self.agreements * 10000).checked_div(self.audits).unwrap_or(10000)
+    }
+}
+
+/// Which protocol features this deployment has switched on, for client feature detection
+#[account]
+pub struct FeatureRegistry {
+    pub activated_slots: [u64; MAX_FEATURES], // 8 * MAX_FEATURES (indexed by FEATURE_*, 0 = not active)
+    pub updated_at: i64,                      // 8
+    pub bump: u8,                             // 1
+}
+
+impl FeatureRegistry {
+    pub const SIZE: usize = 8 + 8 * MAX_FEATURES + 8 + 1 + ACCOUNT_RESERVE;
+    
+    pub fn is_active(&self, feature: u8) -> bool {
+        self.activated_slots.get(feature as usize).is_some_and(|slot| *slot != 0)
+    }
+}
+
+/// Sealed-bid auction for one EXCLUSIVE signal
+#[account]
+pub struct SignalAuction {
+    pub signal: Pubkey,             // 32
+    pub provider: Pubkey,           // 32
+    pub reserve_lamports: u64,      // 8
+    pub bidding_ends_at: i64,       // 8
+    pub reveal_ends_at: i64,        // 8
+    pub bid_count: u16,             // 2
+    pub revealed_count: u16,        // 2
+    pub highest_bid_lamports: u64,  // 8
+    pub highest_bidder: Pubkey,     // 32 (default = no valid bid revealed)
+    pub settled: bool,              // 1
+    pub bump: u8,                   // 1
+}
+
+impl SignalAuction {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 2 + 2 + 8 + 32 + 1 + 1 + ACCOUNT_RESERVE;
+}
+
+/// A buyer's sealed bid in a SignalAuction, holding its escrowed deposit
+#[account]
+pub struct AuctionBid {
+    pub auction: Pubkey,            // 32
+    pub bidder: Pubkey,             // 32
+    pub bid_hash: [u8; 32],         // 32
+    pub deposit_lamports: u64,      // 8
+    pub amount_lamports: u64,       // 8 (set on reveal)
+    pub revealed: bool,             // 1
+    pub committed_at: i64,          // 8
+    pub bump: u8,                   // 1
+}
+
+impl AuctionBid {
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1 + 8 + 1 + ACCOUNT_RESERVE;
+    
+    /// Hash a bidder commits to: SHA256(domain::AUCTION_BID_V1 || bidder || amount_lamports LE || salt)
+    pub fn bid_hash(bidder: &Pubkey, amount_lamports: u64, salt: &[u8; 32]) -> [u8; 32] {
+        domain::tagged_hash(domain::AUCTION_BID_V1, &Self::bid_preimage(bidder, amount_lamports, salt))
+    }
+    
+    /// Bid hash without the domain tag, accepted for bids committed before UNTAGGED_MESSAGES_CUTOFF
+    pub fn untagged_bid_hash(bidder: &Pubkey, amount_lamports: u64, salt: &[u8; 32]) -> [u8; 32] {
+        Sha256::digest(Self::bid_preimage(bidder, amount_lamports, salt)).into()
+    }
+    
+    fn bid_preimage(bidder: &Pubkey, amount_lamports: u64, salt: &[u8; 32]) -> Vec<u8> {
+        let mut preimage = Vec::with_capacity(32 + 8 + 32);
+        preimage.extend_from_slice(bidder.as_ref());
+        preimage.extend_from_slice(&amount_lamports.to_le_bytes());
+        preimage.extend_from_slice(salt);
+        preimage
+    }
+}
+
+/// A front-end or agent framework that routes purchases for a share of the price
+#[account]
+pub struct Integrator {
+    pub authority: Pubkey,          // 32
+    pub fee_bps: u16,               // 2
+    pub purchases_routed: u64,      // 8
+    pub fees_earned: u64,           // 8
+    pub fees_unclaimed: u64,        // 8 (held in this account until claimed)
+    pub registered_at: i64,         // 8
+    pub bump: u8,                   // 1
+    pub approved: bool,             // 1 (set by the admin; only approved integrators are paid)
+}
+
+impl Integrator {
+    pub const SIZE: usize = 8 + 32 + 2 + 8 + 8 + 8 + 8 + 1 + 1 + ACCOUNT_RESERVE;
+}
+
+/// A buyer's time-based access to a provider's signals
+/// PDA seeds: [b"subscription", provider, buyer]
+#[account]
+pub struct Subscription {
+    pub buyer: Pubkey,              // 32
+    pub provider: Pubkey,           // 32
+    pub tier: u8,                   // 1 (highest exclusivity tier covered)
+    pub started_at: i64,            // 8
+    pub expires_at: i64,            // 8
+    pub total_paid: u64,            // 8
+    pub bump: u8,                   // 1
+    // Auto-renewal (zero in older accounts' reserve: off)
+    pub renewal_cap_lamports: u64,  // 8 (renewal spend allowed per RENEWAL_MONTH_SECS month, 0 = off)
+    pub renewal_month: u16,         // 2 (month renewal_spent applies to)
+    pub renewal_spent: u64,         // 8
+    // Price lock (zero in older accounts' reserve: the provider's current price applies)
+    pub locked_period_price: u64,   // 8 (per SUBSCRIPTION_PERIOD_DAYS, as last paid at the provider's price)
+    pub locked_renewals: u8,        // 1 (renewals left at locked_period_price)
+    // Seats (older accounts read an empty list from their reserve and grow when a seat is added)
+    pub seats: Vec<Pubkey>,         // 4 + 32 * MAX_SUBSCRIPTION_SEATS (keys sharing the subscription)
+    pub escrow_lamports: u64,       // 8 (payments not yet earned by the provider, held in this account)
+    pub escrow_from: i64,           // 8 (escrow is earned evenly from here until expires_at)
+    // (older accounts read no escrow: what they paid went to the provider at payment)
+    pub credit_secs_applied: u64,   // 8 (the provider's subscriber_credit_secs when last applied)
+    pub paused_secs_applied: u64,   // 8 (the provider's paused seconds when last applied)
+}
+
+impl Subscription {
+    pub const SIZE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 1 + 8 + 2 + 8 + 8 + 1 + (4 + 32 * MAX_SUBSCRIPTION_SEATS) + 8 + 8 + 8 + 8 + ACCOUNT_RESERVE;
+    
+    /// Whether the subscription grants access to a signal of `exclusivity` at `now`
+    pub fn covers(&self, provider: &Provider, exclusivity: u8, now: i64) -> bool {
+        now < self.credited_expires_at(provider, now) && exclusivity <= self.tier
+    }
+    
+    /// SLA credit the provider has granted since the subscription last applied it; owed only to a
+    /// subscription that ran through the last epoch that earned credit
+    pub fn pending_credit_secs(&self, provider: &Provider) -> i64 {
+        let (credit_secs, credit_through) = provider.subscriber_credit();
+        if self.expires_at < credit_through {
+            return 0;
+        }
+        credit_secs.saturating_sub(self.credit_secs_applied) as i64
+    }
+    
+    /// Time sales have been paused by `now` since the subscription last applied it, given its
+    /// expiry without it; one that lapsed before the latest pause began is owed only the earlier
+    /// pauses, which leave it lapsed
+    pub fn pending_pause_secs(&self, provider: &Provider, expires_at: i64, now: i64) -> i64 {
+        let (paused, began, paused_before) = provider.pause_clock(now);
+        if paused_before >= self.paused_secs_applied {
+            let owed_before = (paused_before - self.paused_secs_applied) as i64;
+            if expires_at + owed_before <= began {
+                return owed_before;
+            }
+        }
+        paused.saturating_sub(self.paused_secs_applied) as i64
+    }
+    
+    /// Expiry extended by the pending SLA credit and the time sales have been paused by `now`
+    pub fn credited_expires_at(&self, provider: &Provider, now: i64) -> i64 {
+        let expires_at = self.expires_at + self.pending_credit_secs(provider);
+        expires_at + self.pending_pause_secs(provider, expires_at, now)
+    }
+    
+    /// Extend the subscription by its pending SLA credit and pause time; a lapsed one forfeits them
+    pub fn apply_credit(&mut self, provider: &Provider, now: i64) {
+        self.expires_at = self.credited_expires_at(provider, now);
+        self.credit_secs_applied = provider.subscriber_credit().0;
+        self.paused_secs_applied = provider.pause_clock(now).0;
+    }
+    
+    /// Whether `key` is the subscriber or holds one of its seats
+    pub fn has_member(&self, key: &Pubkey) -> bool {
+        self.buyer == *key || self.seats.contains(key)
+    }
+    
+    /// Escrow the provider has earned by `now`
+    pub fn earned_escrow(&self, now: i64) -> u64 {
+        if now >= self.expires_at {
+            self.escrow_lamports
+        } else if now <= self.escrow_from {
+            0
+        } else {
+            (self.escrow_lamports as u128 * (now - self.escrow_from) as u128
+                / (self.expires_at - self.escrow_from) as u128) as u64
+        }
+    }
+    
+    /// Take the escrow earned by `until` out of escrow_lamports; the rest is earned from then on
+    pub fn take_earned(&mut self, until: i64) -> u64 {
+        let earned = self.earned_escrow(until);
+        self.escrow_lamports -= earned;
+        self.escrow_from = self.escrow_from.max(until);
+        earned
+    }
+    
+    /// Whether renew_subscription may extend the subscription at `now`
+    pub fn renewal_due(&self, now: i64) -> bool {
+        (self.expires_at - AUTO_RENEW_WINDOW_SECS..self.expires_at + AUTO_RENEW_WINDOW_SECS).contains(&now)
+    }
+    
+    /// Period price of a renewal given the tier's current one, and whether it is the locked rate
+    /// The locked rate applies while a locked renewal remains and it is below the current price
+    pub fn renewal_price(&self, current_price: u64) -> (u64, bool) {
+        if self.locked_renewals > 0 && self.locked_period_price > 0 && self.locked_period_price < current_price {
+            (self.locked_period_price, true)
+        } else {
+            (current_price, false)
+        }
+    }
+    
+    /// Record a payment's rate: one at the locked rate uses up a locked renewal, one at the
+    /// provider's price becomes the locked rate, for one renewal if the provider offers the lock
+    pub fn record_rate(&mut self, period_price: u64, at_locked_rate: bool, lock_renewal: bool) {
+        if at_locked_rate {
+            self.locked_renewals -= 1;
+        } else {
+            self.locked_period_price = period_price;
+            self.locked_renewals = lock_renewal as u8;
+        }
+    }
+    
+    /// Check an auto-renewal against the buyer's monthly cap, and count it
+    pub fn authorize_renewal(&mut self, lamports: u64, now: i64) -> Result<()> {
+        require!(self.renewal_cap_lamports > 0, AgentAlphaError::AutoRenewOff);
+        let month = (now / RENEWAL_MONTH_SECS) as u16;
+        if month != self.renewal_month {
+            self.renewal_month = month;
+            self.renewal_spent = 0;
+        }
+        require!(
+            self.renewal_spent + lamports <= self.renewal_cap_lamports,
+            AgentAlphaError::RenewalCapExceeded
+        );
+        self.renewal_spent += lamports;
+        Ok(())
+    }
+}
+
+/// Lamports a buyer set aside for auto-renewals; the balance is what it holds above rent
+/// PDA seeds: [b"buyer_credit", buyer]
+#[account]
+pub struct BuyerCredit {
+    pub buyer: Pubkey,              // 32
+    pub total_deposited: u64,       // 8
+    pub total_spent: u64,           // 8
+    pub bump: u8,                   // 1
+}
+
+impl BuyerCredit {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 1 + ACCOUNT_RESERVE;
+}
+
+/// A buyer's prepaid signal credits with a provider, spent one per purchase_signal
+/// PDA seeds: [b"credits", provider, buyer]
+#[account]
+pub struct Credits {
+    pub buyer: Pubkey,              // 32
+    pub provider: Pubkey,           // 32
+    pub remaining: u32,             // 4
+    pub total_purchased: u32,       // 4
+    pub total_paid: u64,            // 8
+    pub last_purchased_at: i64,     // 8
+    pub bump: u8,                   // 1
+}
+
+impl Credits {
+    pub const SIZE: usize = 8 + 32 + 32 + 4 + 4 + 8 + 8 + 1 + ACCOUNT_RESERVE;
+}
+
+// ==================== EVENTS ====================
+
+#[event]
+pub struct ProviderRegistered {
+    pub provider: Pubkey,
+    pub authority: Pubkey,
+    pub name: String,
+    pub endpoint: String,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct RecoveryKeyChanged {
+    pub provider: Pubkey,
+    pub recovery_key: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ProviderFrozenChanged {
+    pub provider: Pubkey,
+    pub frozen: bool,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct AuthorityRotationScheduled {
+    pub provider: Pubkey,
+    pub current_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub rotation_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct AuthorityRotated {
+    pub provider: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct PriceChangeScheduled {
+    pub provider: Pubkey,
+    pub current_price_lamports: u64,
+    pub new_price_lamports: u64,
+    pub effective_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct CategoryPriceChanged {
+    pub provider: Pubkey,
+    pub category: u8,
+    pub price_lamports: u64,            // Price buyers pay now (the base price once cleared)
+    pub scheduled_price_lamports: u64,  // Announced increase, 0 = none
+    pub effective_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct EndpointChangeProposed {
+    pub provider: Pubkey,
+    pub current_endpoint: String,
+    pub new_endpoint: String,
+    pub effective_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct EndpointChanged {
+    pub provider: Pubkey,
+    pub previous_endpoint: String,
+    pub new_endpoint: String,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct EndpointChangeCancelled {
+    pub provider: Pubkey,
+    pub cancelled_endpoint: String,
+    pub cancelled_by: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ProviderPauseChanged {
+    pub provider: Pubkey,
+    pub accepting_purchases: bool,
+    pub pause_start: i64,
+    pub pause_end: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ProviderSuspensionChanged {
+    pub provider: Pubkey,
+    pub suspended: bool,
+    pub at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ProviderSchemaChanged {
+    pub provider: Pubkey,
+    pub previous_schema: u8,
+    pub payload_schema: u8,
+    pub previous_min_client_version: u32,
+    pub min_client_version: u32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct AttestationUpdated {
+    pub provider: Pubkey,
+    pub attestation_hash: [u8; 32],
+    pub tee_verified: bool,
+    pub verifier: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct TokenRegistered {
+    pub symbol: String,
+    pub tick_size_cents: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SignalCommitted {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub committed_at: i64,
+    pub experimental: bool,
+    pub exclusivity: u8,
+    pub max_buyers: u16,
+    pub category: u8,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SignalRevealed {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub token: String,
+    pub direction: u8,
+    pub entry_cents: u64,
+    pub tp_cents: u64,
+    pub sl_cents: u64,
+    pub timeframe_hours: u8,
+    pub confidence: u8,
+    pub quote_currency: u8,
+    pub quote_mint: Option<Pubkey>,
+    pub min_notional_cents: Option<u64>,
+    pub max_notional_cents: Option<u64>,
+    pub benchmark: u8,
+    pub entry_ladder: Vec<EntryLevel>,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SignalPurchased {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub buyer: Pubkey,
+    pub price_lamports: u64,
+    pub payment_mint: Pubkey,
+    pub price_paid: u64,
+    pub exclusivity: u8,
+    pub buyer_count: u16,
+    pub session_key: Pubkey,
+    pub subscribed: bool,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct CouponCreated {
+    pub provider: Pubkey,
+    pub coupon: Pubkey,
+    pub percent_off: u8,
+    pub max_uses: u32,
+    pub expires_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct CouponRedeemed {
+    pub coupon: Pubkey,
+    pub buyer: Pubkey,
+    pub uses: u32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SignalAmended {
+    pub parent: Pubkey,
+    pub amendment: Pubkey,
+    pub previous_sl_cents: u64,
+    pub new_sl_cents: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct FillReported {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub reporter: Pubkey,
+    pub fill_price_cents: u64,
+    pub slippage_bps: i32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct NoticePosted {
+    pub mailbox: Pubkey,
+    pub seq: u64,
+    pub kind: u8,
+    pub signal: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SignalExpired {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub expired_by: Pubkey,
+    pub settlement_reward: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct OutcomeRecorded {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub outcome: u8,
+    pub was_correct: bool,
+    pub return_bps: i32,
+    pub tp_progress_bps: i32,
+    pub benchmark_return_bps: i32,
+    pub both_touched: bool,
+    pub vol_regime: u8,
+    pub experimental: bool,
+    pub total_signals: u64,
+    pub correct_signals: u64,
+    pub settled_by: Pubkey,
+    pub settlement_reward: u64,
+    pub price_feed: Pubkey,
+    pub publish_slot: u64,
+    pub publish_time: i64,
+    pub update_hash: [u8; 32],
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct BountyCreated {
+    pub bounty: Pubkey,
+    pub creator: Pubkey,
+    pub token: String,
+    pub reward_lamports: u64,
+    pub deadline: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct BountyEntered {
+    pub bounty: Pubkey,
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct BountyClaimed {
+    pub bounty: Pubkey,
+    pub winner: Pubkey,
+    pub reward_lamports: u64,
+    pub event_seq: u64,
+}
+
+/// Emitted instead of failing when a request is intentionally turned into a no-op
+#[event]
+pub struct BusinessWarning {
+    pub code: u16,
+    pub subject: Pubkey,
+    pub actor: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct BundleCreated {
+    pub bundle: Pubkey,
+    pub curator: Pubkey,
+    pub name: String,
+    pub price_lamports: u64,
+    pub member_count: u8,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct BundleOutcomeTallied {
+    pub bundle: Pubkey,
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub was_correct: bool,
+    pub return_bps: i32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct CuratorBonded {
+    pub provider: Pubkey,
+    pub curator: Pubkey,
+    pub amount: u64,
+    pub total_bonded: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct CuratorRewardsClaimed {
+    pub provider: Pubkey,
+    pub curator: Pubkey,
+    pub amount: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct CuratorUnbondStarted {
+    pub provider: Pubkey,
+    pub curator: Pubkey,
+    pub amount: u64,
+    pub unbond_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct CuratorSlashed {
+    pub provider: Pubkey,
+    pub curator: Pubkey,
+    pub amount: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub param: u8,
+    pub value: u64,
+    pub key_value: Pubkey,
+    pub voting_ends_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub curator: Pubkey,
+    pub curator_bond: Pubkey,
+    pub approve: bool,
+    pub weight: u64,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub param: u8,
+    pub value: u64,
+    pub key_value: Pubkey,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DeliveryAcked {
+    pub provider: Pubkey,
+    pub signal: Pubkey,
+    pub buyer: Pubkey,
+    pub latency_secs: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct TokenRateUpdated {
+    pub symbol: String,
+    pub usd_rate_micros: u64,
+    pub updated_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct TokenDisplayUpdated {
+    pub symbol: String,
+    pub decimals: u8,
+    pub exponent: i8,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct RevealCorrected {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub original_entry_cents: u64,
+    pub original_tp_cents: u64,
+    pub original_sl_cents: u64,
+    pub entry_cents: u64,
+    pub tp_cents: u64,
+    pub sl_cents: u64,
+    pub corrected_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ProviderCapacityChanged {
+    pub provider: Pubkey,
+    pub capacity_usd: u64,
+    pub max_followers: u32,
+    pub follower_count: u32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct FollowerChanged {
+    pub provider: Pubkey,
+    pub follower: Pubkey,
+    pub following: bool,
+    pub follower_count: u32,
+    pub max_followers: u32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct KeeperRewardsChanged {
+    pub settle_reward_lamports: u64,
+    pub expire_reward_lamports: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SlaChanged {
+    pub provider: Pubkey,
+    pub min_signals: u16,
+    pub max_reveal_delay_secs: u32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SlaEpochEvaluated {
+    pub provider: Pubkey,
+    pub epoch: u32,
+    pub met: bool,
+    pub signals: u16,
+    pub late_reveals: u16,
+    pub met_epochs: u32,
+    pub missed_epochs: u32,
+    pub subscriber_credit_secs: u64, // owed to each active subscriber for this roll
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ExecutionRecorded {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub buyer: Pubkey,
+    pub entry_price_cents: u64,
+    pub exit_price_cents: u64,
+    pub size_cents: u64,
+    pub realized_return_bps: i32,
+    pub realized_pnl_cents: i64,
+    pub slippage_bps: i32,
+    pub pnl_hash: [u8; 32],
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct StakeSettled {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub stake: u64,
+    pub returned: u64,
+    pub bonus: u64,
+    pub forfeited: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct OracleTaskChanged {
+    pub index: u8,
+    pub signal: Pubkey,
+    pub state: u8,
+    pub oracle: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct PurchasePolicyChanged {
+    pub provider: Pubkey,
+    pub policy: Pubkey,
+    pub classifier: Pubkey,
+    pub rules: Vec<EmbargoRule>,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ReporterRegistered {
+    pub reporter: Pubkey,
+    pub stake_lamports: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SocialSettlementOpened {
+    pub signal: Pubkey,
+    pub window_end: i64,
+    pub panel: [Pubkey; SOCIAL_PANEL_SIZE],
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ObservationCommitted {
+    pub signal: Pubkey,
+    pub reporter: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ObservationSubmitted {
+    pub signal: Pubkey,
+    pub reporter: Pubkey,
+    pub price_cents: u64,
+    pub high_cents: u64,
+    pub low_cents: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SocialSettlementFinalized {
+    pub signal: Pubkey,
+    pub median_price_cents: u64,
+    pub median_high_cents: u64,
+    pub median_low_cents: u64,
+    pub outliers: u8,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ReporterSlashed {
+    pub signal: Pubkey,
+    pub reporter: Pubkey,
+    pub price_cents: u64,
+    pub median_price_cents: u64,
+    pub amount: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct HashSchemaToggled {
+    pub schema: u8,
+    pub enabled: bool,
+    pub disabled_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ProviderDigest {
+    pub provider: Pubkey,
+    pub total_signals: u64,
+    pub correct_signals: u64,
+    pub total_return_bps: i64,
+    pub expired_signals: u64,
+    pub total_tp_progress_bps: i64,
+    pub benchmarked_signals: u64,
+    pub total_alpha_bps: i64,
+    pub exp_total_signals: u64,
+    pub exp_correct_signals: u64,
+    pub low_conf_signals: u64,
+    pub low_conf_correct_signals: u64,
+    pub fill_reports: u64,
+    pub total_slippage_bps: i64,
+    pub executions: u64,
+    pub total_realized_pnl_cents: i64,
+    pub open_signals: u16,
+    pub last_settled_at: i64,
+    pub curve_root: [u8; 32],
+    pub curve_points: u8,
+    pub exported_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct BuyerSessionChanged {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub daily_budget_lamports: u64,
+    pub expires_at: i64,
+    pub providers: Vec<Pubkey>,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct BuyerSessionClosed {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct MarkPosted {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub mark_price_cents: u64,
+    pub unrealized_return_bps: i32,
+    pub publish_time: i64,
+    pub posted_by: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct BadgeChanged {
+    pub provider: Pubkey,
+    pub previous_badge: u8,
+    pub badge: u8,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ContentCommitted {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub content_root: [u8; 32],
+    pub chunk_count: u32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SpendReportUpdated {
+    pub buyer: Pubkey,
+    pub total_spent_lamports: u64,
+    pub total_settled: u64,
+    pub total_return_bps: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct TokenStatsUpdated {
+    pub token: String,
+    pub active_signals: u32,
+    pub direction_skew_bps: i16,
+    pub avg_confidence: u8,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct TokenConsensusUpdated {
+    pub token: String,
+    pub open_signals: u32,
+    pub consensus_bps: i16,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct TokenHaltChanged {
+    pub symbol: String,
+    pub halted_at: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SignalVoided {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub token: String,
+    pub voided_by: Pubkey,
+    pub settlement_reward: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct PurchaseRefunded {
+    pub signal: Pubkey,
+    pub buyer: Pubkey,
+    pub lamports: u64,             // Or base units of payment_mint
+    pub payment_mint: Pubkey,      // default = SOL
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct PurchaseEscrowReleased {
+    pub signal: Pubkey,
+    pub buyer: Pubkey,
+    pub lamports: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct EndpointProbed {
+    pub provider: Pubkey,
+    pub prober: Pubkey,
+    pub live: bool,
+    pub latency_ms: u32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SourcesCited {
+    pub provider: Pubkey,
+    pub signal_hash: [u8; 32],
+    pub citations: [Pubkey; MAX_CITATIONS],
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct CitationRoyaltiesClaimed {
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct VestedEarningsReleased {
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct WithdrawalScheduled {
+    pub provider: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub unlocks_at: i64,
+    pub kind: u8,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct WithdrawalCancelled {
+    pub provider: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct WithdrawalPolicyChanged {
+    pub provider: Pubkey,
+    pub allowlist: Vec<Pubkey>,
+    pub delay_secs: u32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct TokenHoldbackReleased {
+    pub provider: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,       // The provider's vault, or the treasury's account when clawed back
+    pub amount: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct VestingClawedBack {
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct CircuitBreakerTripped {
+    pub window_settlements: u32,
+    pub window_losses: u32,
+    pub tripped_at: i64,
+    pub event_seq: u64,
 }
 
-// ==================== ACCOUNTS ====================
+#[event]
+pub struct CircuitBreakerReset {
+    pub guardian: Pubkey,
+    pub tripped_at: i64,
+    pub event_seq: u64,
+}
 
-#[derive(Accounts)]
-#[instruction(name: String)]
-pub struct RegisterProvider<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = Provider::SIZE,
-        seeds = [b"provider", authority.key().as_ref()],
-        bump
-    )]
-    pub provider: Account<'info, Provider>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct AuditDrawn {
+    pub signal: Pubkey,
+    pub settled_by: Pubkey,
+    pub epoch: u32,
+    pub event_seq: u64,
 }
 
-#[derive(Accounts)]
-pub struct UpdateProvider<'info> {
-    #[account(
-        mut,
-        seeds = [b"provider", authority.key().as_ref()],
-        bump = provider.bump,
-        has_one = authority
-    )]
-    pub provider: Account<'info, Provider>,
-    
-    pub authority: Signer<'info>,
+#[event]
+pub struct AuditSubmitted {
+    pub signal: Pubkey,
+    pub settled_by: Pubkey,
+    pub settled_price_cents: u64,
+    pub audit_price_cents: u64,
+    pub agreed: bool,
+    pub audits: u64,
+    pub agreements: u64,
+    pub event_seq: u64,
 }
 
-#[derive(Accounts)]
-#[instruction(signal_hash: [u8; 32])]
-pub struct CommitSignal<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = SignalCommit::SIZE,
-        seeds = [b"signal", provider.key().as_ref(), &signal_hash],
-        bump
-    )]
-    pub signal_commit: Account<'info, SignalCommit>,
-    
-    #[account(
-        seeds = [b"provider", authority.key().as_ref()],
-        bump = provider.bump,
-        has_one = authority
-    )]
-    pub provider: Account<'info, Provider>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct BaselineSignalCommitted {
+    pub signal: Pubkey,
+    pub token: String,
+    pub entry_cents: u64,
+    pub draw_slot: u64,
+    pub event_seq: u64,
 }
 
-#[derive(Accounts)]
-pub struct RevealSignal<'info> {
-    #[account(
-        mut,
-        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
-        bump = signal_commit.bump
-    )]
-    pub signal_commit: Account<'info, SignalCommit>,
-    
-    #[account(
-        seeds = [b"provider", authority.key().as_ref()],
-        bump = provider.bump,
-        has_one = authority
-    )]
-    pub provider: Account<'info, Provider>,
-    
-    pub authority: Signer<'info>,
+#[event]
+pub struct BaselineSignalOpened {
+    pub signal: Pubkey,
+    pub token: String,
+    pub direction: u8,
+    pub entry_cents: u64,
+    pub tp_cents: u64,
+    pub sl_cents: u64,
+    pub event_seq: u64,
 }
 
-#[derive(Accounts)]
-pub struct RecordOutcome<'info> {
-    #[account(
-        mut,
-        seeds = [b"signal", provider.key().as_ref(), &signal_commit.signal_hash],
-        bump = signal_commit.bump
-    )]
-    pub signal_commit: Account<'info, SignalCommit>,
-    
-    #[account(
-        mut,
-        constraint = signal_commit.provider == provider.key()
-    )]
-    pub provider: Account<'info, Provider>,
-    
-    /// Oracle authority - trusted to report outcomes
-    pub oracle: Signer<'info>,
+#[event]
+pub struct FeatureActivationChanged {
+    pub feature: u8,
+    pub activated_slot: u64, // 0 = deactivated
+    pub event_seq: u64,
 }
 
-// ==================== STATE ====================
+#[event]
+pub struct AuctionOpened {
+    pub auction: Pubkey,
+    pub signal: Pubkey,
+    pub reserve_lamports: u64,
+    pub bidding_ends_at: i64,
+    pub reveal_ends_at: i64,
+    pub event_seq: u64,
+}
 
-#[account]
-pub struct Provider {
-    pub authority: Pubkey,        // 32
-    pub name: String,             // 4 + 64
-    pub endpoint: String,         // 4 + 256
-    pub categories: Vec<u8>,      // 4 + 8
-    pub price_lamports: u64,      // 8
-    pub total_signals: u64,       // 8
-    pub correct_signals: u64,     // 8
-    pub total_return_bps: i64,    // 8
-    pub created_at: i64,          // 8
-    pub updated_at: i64,          // 8
-    pub bump: u8,                 // 1
+#[event]
+pub struct BidCommitted {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub deposit_lamports: u64,
+    pub event_seq: u64,
 }
 
-impl Provider {
-    pub const SIZE: usize = 8 + 32 + (4 + 64) + (4 + 256) + (4 + 8) + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 64;
-    
-    pub fn hit_rate_bps(&self) -> u64 {
-        if self.total_signals == 0 { return 0; }
-        (self.correct_signals * 10000) / self.total_signals
-    }
-    
-    pub fn avg_return_bps(&self) -> i64 {
-        if self.total_signals == 0 { return 0; }
-        self.total_return_bps / self.total_signals as i64
-    }
+#[event]
+pub struct BidRevealed {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount_lamports: u64,
+    pub leading: bool,
+    pub event_seq: u64,
 }
 
-#[account]
-pub struct SignalCommit {
-    pub provider: Pubkey,           // 32
-    pub signal_hash: [u8; 32],      // 32
-    pub committed_at: i64,          // 8
-    pub revealed: bool,             // 1
-    pub outcome_recorded: bool,     // 1
-    // Revealed data
-    pub token: String,              // 4 + 16
-    pub direction: u8,              // 1 (0=BUY, 1=SELL)
-    pub entry_cents: u64,           // 8
-    pub tp_cents: u64,              // 8
-    pub sl_cents: u64,              // 8
-    pub timeframe_hours: u8,        // 1
-    pub confidence: u8,             // 1
-    pub revealed_at: i64,           // 8
-    // Outcome data
-    pub outcome: u8,                // 1 (1=TP_HIT, 2=SL_HIT, 3=EXPIRED)
-    pub final_price_cents: u64,     // 8
-    pub was_correct: bool,          // 1
-    pub return_bps: i32,            // 4
-    pub evaluated_at: i64,          // 8
-    pub bump: u8,                   // 1
+#[event]
+pub struct AuctionSettled {
+    pub auction: Pubkey,
+    pub signal: Pubkey,
+    pub winner: Pubkey,          // default = ended unsold
+    pub price_lamports: u64,
+    pub event_seq: u64,
 }
 
-impl SignalCommit {
-    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1 + 1 + (4 + 16) + 1 + 8 + 8 + 8 + 1 + 1 + 8 + 1 + 8 + 1 + 4 + 8 + 1 + 64;
+#[event]
+pub struct BidWithdrawn {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub deposit_lamports: u64,
+    pub event_seq: u64,
 }
 
-// ==================== EVENTS ====================
+#[event]
+pub struct ProviderOnboarded {
+    pub provider: Pubkey,
+    pub platform: Pubkey,
+    pub platform_fee_bps: u16,
+    pub event_seq: u64,
+}
 
 #[event]
-pub struct ProviderRegistered {
+pub struct IntegratorApprovalChanged {
+    pub integrator: Pubkey,
+    pub approved: bool,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct IntegratorFeeChanged {
+    pub integrator: Pubkey,
+    pub fee_bps: u16,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct IntegratorFeesClaimed {
+    pub integrator: Pubkey,
+    pub amount: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct PriceMintChanged {
     pub provider: Pubkey,
-    pub authority: Pubkey,
-    pub name: String,
-    pub endpoint: String,
+    pub price_mint: Pubkey,      // default = SOL only
+    pub price_token_amount: u64,
+    pub event_seq: u64,
 }
 
 #[event]
-pub struct SignalCommitted {
+pub struct TokenEarningsWithdrawn {
     pub provider: Pubkey,
-    pub signal_hash: [u8; 32],
-    pub committed_at: i64,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub event_seq: u64,
 }
 
 #[event]
-pub struct SignalRevealed {
+pub struct Subscribed {
     pub provider: Pubkey,
-    pub signal_hash: [u8; 32],
-    pub token: String,
-    pub direction: u8,
-    pub entry_cents: u64,
-    pub tp_cents: u64,
-    pub sl_cents: u64,
-    pub timeframe_hours: u8,
-    pub confidence: u8,
+    pub buyer: Pubkey,
+    pub tier: u8,
+    pub days: u16,
+    pub lamports: u64,
+    pub expires_at: i64,
+    pub event_seq: u64,
 }
 
 #[event]
-pub struct OutcomeRecorded {
+pub struct SubscriptionRenewed {
+    pub provider: Pubkey,
+    pub buyer: Pubkey,
+    pub tier: u8,
+    pub lamports: u64,
+    pub expires_at: i64,
+    pub spent_this_month: u64,
+    pub at_locked_rate: bool,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SubscriptionEscrowReleased {
+    pub provider: Pubkey,
+    pub buyer: Pubkey,
+    pub lamports: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SubscriptionRefunded {
+    pub provider: Pubkey,
+    pub buyer: Pubkey,
+    pub lamports: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct AutoRenewChanged {
+    pub provider: Pubkey,
+    pub buyer: Pubkey,
+    pub cap_lamports: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct BuyerCreditChanged {
+    pub buyer: Pubkey,
+    pub balance_lamports: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SubscriptionSeatChanged {
+    pub provider: Pubkey,
+    pub buyer: Pubkey,
+    pub seat: Pubkey,
+    pub added: bool,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct BundlePurchased {
+    pub provider: Pubkey,
+    pub buyer: Pubkey,
+    pub signals: u32,
+    pub lamports: u64,
+    pub remaining: u32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct GuaranteeRefunded {
+    pub signal: Pubkey,
+    pub buyer: Pubkey,
+    pub lamports: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct ProtocolFeeChanged {
+    pub protocol_fee_bps: u16,
+    pub treasury: Pubkey,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SignalWithdrawn {
     pub provider: Pubkey,
     pub signal_hash: [u8; 32],
-    pub outcome: u8,
-    pub was_correct: bool,
+    pub buyer_count: u16,
+    pub refunded: u16,
+    pub replacement_credit: bool,
+    pub self_voided_signals: u32,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct PerformanceFeeSettled {
+    pub signal: Pubkey,
+    pub buyer: Pubkey,
+    pub notional_lamports: u64,
     pub return_bps: i32,
-    pub total_signals: u64,
-    pub correct_signals: u64,
+    pub fee_lamports: u64,
+    pub returned_lamports: u64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub layout_version: u8,
+    pub event_seq: u64,
 }
 
 // ==================== ERRORS ====================
@@ -419,6 +13290,8 @@ pub enum AgentAlphaError {
     EndpointTooLong,
     #[msg("Too many categories (max 8)")]
     TooManyCategories,
+    #[msg("Invalid pause window (start must not be after end)")]
+    InvalidPauseWindow,
     #[msg("Token symbol too long (max 16 chars)")]
     TokenTooLong,
     #[msg("Invalid direction (must be 0=BUY or 1=SELL)")]
@@ -427,7 +13300,13 @@ pub enum AgentAlphaError {
     InvalidTimeframe,
     #[msg("Invalid confidence (must be 0-100)")]
     InvalidConfidence,
-    #[msg("Invalid outcome (must be 1=TP_HIT, 2=SL_HIT, or 3=EXPIRED)")]
+    #[msg("Invalid quote currency (must be 0=USD, 1=SOL, 2=BTC, 3=ETH, or 4=SPL)")]
+    InvalidQuoteCurrency,
+    #[msg("Quote mint must be set if and only if quote currency is SPL")]
+    InvalidQuoteMint,
+    #[msg("Invalid notional range (min must not exceed max)")]
+    InvalidNotionalRange,
+    #[msg("Invalid outcome (must be 1=TP_HIT, 2=SL_HIT, 3=EXPIRED, 4=BOTH_TOUCHED, or 7=NOT_FILLED)")]
     InvalidOutcome,
     #[msg("Signal already revealed")]
     AlreadyRevealed,
@@ -437,4 +13316,442 @@ pub enum AgentAlphaError {
     HashMismatch,
     #[msg("Outcome already recorded for this signal")]
     OutcomeAlreadyRecorded,
+    #[msg("Parent signal belongs to a different provider")]
+    ParentProviderMismatch,
+    #[msg("Parent signal already settled")]
+    ParentAlreadySettled,
+    #[msg("Parent signal account missing or does not match")]
+    MissingParentSignal,
+    #[msg("Amendment must match the parent's token and direction")]
+    AmendmentMismatch,
+    #[msg("Settlement proof is from the future")]
+    InvalidSettlementProof,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Tick size must be greater than zero")]
+    InvalidTickSize,
+    #[msg("Price is not a multiple of the token's tick size")]
+    PriceOffTick,
+    #[msg("Outcome not recorded yet")]
+    OutcomeNotRecorded,
+    #[msg("Bounty reward must be greater than zero")]
+    InvalidBountyReward,
+    #[msg("Bounty window must be positive")]
+    InvalidBountyWindow,
+    #[msg("Signal already entered into a bounty")]
+    AlreadyInBounty,
+    #[msg("Signal does not meet the bounty's terms")]
+    BountyEntryMismatch,
+    #[msg("Bounty entries may still be settling")]
+    BountyStillOpen,
+    #[msg("Signal is not the bounty's best entry")]
+    NotBountyWinner,
+    #[msg("Bounty has a winner and must be claimed")]
+    BountyHasWinner,
+    #[msg("Provider has reached its maximum number of open signals")]
+    TooManyOpenSignals,
+    #[msg("Signal expired without being revealed")]
+    SignalExpired,
+    #[msg("Reveal window has not passed yet")]
+    RevealWindowOpen,
+    #[msg("Fill price and signal entry must be non-zero")]
+    InvalidFillPrice,
+    #[msg("Invalid notice kind (must be 1=NEW_SIGNAL or 2=OUTCOME)")]
+    InvalidNoticeKind,
+    #[msg("Ack sequence outside the mailbox's pending range")]
+    InvalidAckSeq,
+    #[msg("Attestation hash does not match the provider's submission")]
+    AttestationMismatch,
+    #[msg("Invalid payload schema (must be 0=JSON_V1, 1=BINARY_V2, or 2=ENCRYPTED)")]
+    InvalidPayloadSchema,
+    #[msg("Provider is suspended")]
+    ProviderSuspended,
+    #[msg("Bundle needs 1-8 members with splits totalling at most 10000 bps")]
+    InvalidBundleMembers,
+    #[msg("Signal's provider is not a member of this bundle")]
+    NotBundleMember,
+    #[msg("Signal is experimental or predates the bundle")]
+    BundleSignalIneligible,
+    #[msg("Provider is frozen by its recovery key")]
+    ProviderFrozen,
+    #[msg("Provider must be frozen first")]
+    ProviderNotFrozen,
+    #[msg("No authority rotation is pending")]
+    NoPendingRotation,
+    #[msg("Authority rotation delay has not passed")]
+    RotationDelayActive,
+    #[msg("Batch must contain 1-10 payloads, each with a commit and token registry account")]
+    InvalidBatchSize,
+    #[msg("Token registry account does not match the revealed token")]
+    InvalidTokenRegistry,
+    #[msg("Signal belongs to a different provider")]
+    SignalProviderMismatch,
+    #[msg("Amendments must be revealed with reveal_signal")]
+    AmendmentInBatch,
+    #[msg("Invalid exclusivity (0=UNLIMITED, 1=LIMITED with max_buyers > 0, 2=EXCLUSIVE)")]
+    InvalidExclusivity,
+    #[msg("Provider is not accepting purchases")]
+    ProviderNotAcceptingPurchases,
+    #[msg("Signal is settled or expired")]
+    SignalClosed,
+    #[msg("Signal has reached its maximum number of buyers")]
+    SignalSoldOut,
+    #[msg("Invalid coupon")]
+    InvalidCoupon,
+    #[msg("Coupon has expired")]
+    CouponExpired,
+    #[msg("Coupon has no uses left")]
+    CouponExhausted,
+    #[msg("Invalid benchmark (must be 0=NONE, 1=SOL, 2=BTC, or 3=ETH)")]
+    InvalidBenchmark,
+    #[msg("Curator share too high (max 5000 bps)")]
+    InvalidCuratorShare,
+    #[msg("Bond amount must be greater than zero")]
+    InvalidBondAmount,
+    #[msg("Bond is already unbonding")]
+    AlreadyUnbonding,
+    #[msg("Bond has not finished unbonding")]
+    UnbondNotReady,
+    #[msg("Provider was proven fraudulent; its bonds can only be slashed")]
+    ProviderFraudProven,
+    #[msg("Provider has not been proven fraudulent")]
+    ProviderNotFraudulent,
+    #[msg("Invalid entry ladder (2-3 levels from entry_cents toward the SL, weights summing to 10000 bps)")]
+    InvalidEntryLadder,
+    #[msg("Delivery already acknowledged")]
+    DeliveryAlreadyAcked,
+    #[msg("Delivery time must be between purchase and now")]
+    InvalidDeliveryTime,
+    #[msg("Token payment needs the payment and SOL registries, both token accounts and the token program")]
+    MissingPaymentAccounts,
+    #[msg("Provider does not accept this token as payment")]
+    PaymentMintNotAccepted,
+    #[msg("Token account mint or owner does not match the payment")]
+    InvalidPaymentAccount,
+    #[msg("Token USD rate is missing or stale")]
+    StaleTokenRate,
+    #[msg("Reveal can no longer be corrected (already corrected, window passed, purchased, settled or an amendment)")]
+    CorrectionNotAllowed,
+    #[msg("Signal already purchased by this buyer")]
+    AlreadyPurchased,
+    #[msg("Execution prices and size must be greater than zero")]
+    InvalidExecution,
+    #[msg("Signal has no stake to settle")]
+    NoStake,
+    #[msg("No matching task in that queue slot")]
+    InvalidTask,
+    #[msg("Signal's evaluation window has not ended")]
+    TaskNotDue,
+    #[msg("Category is not one of the provider's categories")]
+    InvalidCategory,
+    #[msg("Provider's purchase policy and the buyer's class record must be supplied")]
+    MissingPurchasePolicy,
+    #[msg("Signal category is embargoed for this buyer")]
+    SignalEmbargoed,
+    #[msg("Too many embargo rules (max 8)")]
+    TooManyEmbargoRules,
+    #[msg("Invalid precedence (must be 0=WORST_CASE, 1=BEST_CASE, or 2=EXPIRE)")]
+    InvalidPrecedence,
+    #[msg("No endpoint change is pending")]
+    NoPendingEndpoint,
+    #[msg("Endpoint change notice period has not elapsed")]
+    EndpointNoticeActive,
+    #[msg("Reporter stake below the minimum")]
+    InsufficientReporterStake,
+    #[msg("Oracle grace period after the signal's window has not elapsed")]
+    OracleGraceActive,
+    #[msg("Social settlement already finalized")]
+    SocialSettlementFinalized,
+    #[msg("Invalid price observation")]
+    InvalidObservation,
+    #[msg("Reporter panel is full")]
+    PanelFull,
+    #[msg("Reporter already submitted an observation")]
+    DuplicateObservation,
+    #[msg("Reporter panel is not complete")]
+    PanelIncomplete,
+    #[msg("Observation was not flagged as an outlier")]
+    NotAnOutlier,
+    #[msg("Observation already slashed")]
+    AlreadySlashed,
+    #[msg("Relayed commit nonce does not match the provider's relay nonce")]
+    InvalidRelayNonce,
+    #[msg("Preceding instruction must be an ed25519 signature verification")]
+    MissingSignatureVerification,
+    #[msg("Signature verification does not cover the provider authority and commit message")]
+    InvalidSignatureVerification,
+    #[msg("Signal hash schema is disabled for new commits")]
+    HashSchemaDisabled,
+    #[msg("Invalid hash schema version")]
+    InvalidHashSchema,
+    #[msg("Too many approved providers for a session (max 8)")]
+    TooManySessionProviders,
+    #[msg("Buyer session has expired")]
+    SessionExpired,
+    #[msg("Provider is not approved for this session")]
+    ProviderNotApproved,
+    #[msg("Purchase exceeds the session's daily budget")]
+    SessionBudgetExceeded,
+    #[msg("Session balance cannot cover the purchase")]
+    InsufficientSessionFunds,
+    #[msg("Session purchases can only be paid in SOL")]
+    SessionPaysSolOnly,
+    #[msg("Invalid mark (price must be positive and newer than the last mark)")]
+    InvalidMark,
+    #[msg("Content attachment already committed")]
+    ContentAlreadyCommitted,
+    #[msg("Content commitment needs at least one chunk")]
+    InvalidContentCommitment,
+    #[msg("Purchase outcome already reported")]
+    AlreadyReported,
+    #[msg("Token is not halted, or the signal was revealed after the halt")]
+    TokenNotHalted,
+    #[msg("Signal was voided")]
+    SignalVoided,
+    #[msg("Signal was not voided")]
+    SignalNotVoided,
+    #[msg("Purchase already refunded")]
+    AlreadyRefunded,
+    // No longer returned (token purchases refund from the vault); kept so later codes don't shift
+    #[msg("Only SOL payments can be refunded on-chain")]
+    RefundOnlyInSol,
+    #[msg("This prober attested the provider too recently")]
+    ProbeTooSoon,
+    #[msg("Buyer does not hold the provider's gate token")]
+    AccessGateNotMet,
+    #[msg("A gated feed needs a positive minimum holding")]
+    InvalidAccessGate,
+    #[msg("Citations can only change before the signal's first sale")]
+    CitationsLocked,
+    #[msg("A signal cannot cite its own provider or the same provider twice")]
+    InvalidCitation,
+    #[msg("Cited provider account missing")]
+    MissingCitedProvider,
+    #[msg("No royalties to claim")]
+    NoRoyalties,
+    #[msg("Vesting share must be at most 10000 bps")]
+    InvalidVesting,
+    #[msg("Nothing has vested")]
+    NothingVested,
+    #[msg("Loss threshold must be at most 10000 bps")]
+    InvalidBreakerThreshold,
+    #[msg("Circuit breaker is not tripped")]
+    BreakerNotTripped,
+    #[msg("Signal has no held-back reputation update")]
+    NoPendingReputation,
+    #[msg("Reputation updates are paused by the circuit breaker")]
+    ReputationPaused,
+    #[msg("Audit rate must be at most 10000 bps")]
+    InvalidAuditRate,
+    #[msg("Signal's audit was already drawn")]
+    AuditAlreadyDrawn,
+    #[msg("Signals are drawn for audit from the epoch after they settled")]
+    AuditTooEarly,
+    #[msg("Signal was not selected for audit")]
+    NotSelectedForAudit,
+    #[msg("Audit price must be positive")]
+    InvalidAuditPrice,
+    #[msg("Account is not the protocol baseline provider")]
+    NotBaselineProvider,
+    #[msg("Baseline entry must be a positive on-tick price of a trading token")]
+    InvalidBaselineEntry,
+    
+    #[msg("Unknown feature id")]
+    UnknownFeature,
+    
+    #[msg("Withdrawal destination is not on the provider's allowlist")]
+    WithdrawalDestinationNotAllowed,
+    #[msg("A withdrawal is already scheduled")]
+    WithdrawalPending,
+    #[msg("No withdrawal is scheduled")]
+    NoPendingWithdrawal,
+    #[msg("Withdrawal delay has not passed yet")]
+    WithdrawalDelayActive,
+    #[msg("Withdrawal allowlist is too long or holds the default key")]
+    InvalidWithdrawalAllowlist,
+    
+    #[msg("Only EXCLUSIVE signals can be auctioned")]
+    AuctionRequiresExclusive,
+    #[msg("Auction phases must be between 1 second and MAX_AUCTION_PHASE_SECS")]
+    InvalidAuctionWindow,
+    #[msg("Signal is being sold by auction")]
+    SignalAuctioned,
+    #[msg("Bidding has closed")]
+    BiddingClosed,
+    #[msg("Bids can only be revealed between the bidding and reveal deadlines")]
+    BidRevealClosed,
+    #[msg("Bid must be positive, at least the reserve and covered by the deposit")]
+    InvalidBid,
+    #[msg("Auction reveal phase has not ended")]
+    AuctionNotEnded,
+    #[msg("Auction already settled")]
+    AuctionAlreadySettled,
+    #[msg("Auction has no valid bid")]
+    NoWinningBid,
+    #[msg("Auction has a winning bid to settle")]
+    AuctionHasWinner,
+    #[msg("The leading bid is locked until the auction settles")]
+    WinningBidLocked,
+    
+    #[msg("Purchase has nothing escrowed")]
+    NothingEscrowed,
+    #[msg("Purchase escrow window has not ended")]
+    EscrowWindowActive,
+    
+    #[msg("Account is not the PDA of the signing provider authority")]
+    InvalidProviderAccount,
+    #[msg("Platform fee exceeds MAX_PLATFORM_FEE_BPS")]
+    InvalidPlatformFee,
+    #[msg("The provider's onboarding platform account is required")]
+    MissingPlatformAccount,
+    
+    #[msg("Integrator fee exceeds MAX_INTEGRATOR_FEE_BPS")]
+    InvalidIntegratorFee,
+    #[msg("No integrator fees to claim")]
+    NoIntegratorFees,
+    
+    #[msg("Withdrawal amount is zero or exceeds the vault balance")]
+    InsufficientVaultBalance,
+    
+    #[msg("Provider does not offer subscriptions")]
+    SubscriptionsNotOffered,
+    #[msg("Subscription length must be 1..=MAX_SUBSCRIPTION_DAYS days")]
+    InvalidSubscriptionLength,
+    #[msg("Subscription has expired or does not cover this signal's tier")]
+    SubscriptionNotActive,
+    
+    #[msg("Escrow is guaranteed against the outcome; use claim_payout once it is recorded")]
+    EscrowHeldForOutcome,
+    #[msg("No money-back refund is due")]
+    NoRefundDue,
+    #[msg("Refund share must be at most 10000 bps")]
+    InvalidRefundBps,
+    
+    #[msg("Protocol fee exceeds MAX_PROTOCOL_FEE_BPS")]
+    InvalidProtocolFee,
+    #[msg("The configured protocol treasury account is required")]
+    MissingTreasuryAccount,
+    
+    #[msg("Refund account does not belong to this signal's purchase")]
+    InvalidRefundAccount,
+    #[msg("Replacement credit is not held with this provider")]
+    NoReplacementCredit,
+    
+    #[msg("State invariant violated (debug-invariants build)")]
+    InvariantViolated,
+    
+    #[msg("Performance fee exceeds MAX_PERF_FEE_BPS")]
+    InvalidPerformanceFee,
+    #[msg("Signal is sold under a performance fee; a notional is required")]
+    MissingNotional,
+    #[msg("Purchase was not made under a performance fee")]
+    NotPerformanceFeePurchase,
+    #[msg("Performance-fee purchases are paid out by settle_performance_fee")]
+    PerformanceFeePurchase,
+    
+    #[msg("Break-even band exceeds MAX_BREAK_EVEN_BPS")]
+    InvalidBreakEvenBand,
+    
+    #[msg("Too many category prices (max 8)")]
+    TooManyCategoryPrices,
+    
+    #[msg("Price display out of range (decimals up to 8, exponent -8 to 18)")]
+    InvalidPriceDisplay,
+    
+    #[msg("Bundle size must be between 1 and MAX_BUNDLE_SIGNALS")]
+    InvalidBundleSize,
+    #[msg("Bundle discount exceeds MAX_BUNDLE_DISCOUNT_BPS")]
+    InvalidBundleDiscount,
+    #[msg("Provider does not offer signal bundles")]
+    BundlesNotOffered,
+    #[msg("No bundle credits remaining")]
+    NoBundleCredits,
+    #[msg("Bundle credits only cover flat-priced TIER_UNLIMITED signals at or below the base price")]
+    BundleCreditNotApplicable,
+    
+    #[msg("Reporter set is full")]
+    ReporterSetFull,
+    #[msg("Reporter was not drawn for this panel")]
+    NotOnPanel,
+    #[msg("Observation window has closed")]
+    ObservationWindowClosed,
+    #[msg("Observations can't be revealed until the commit window closes")]
+    ObservationWindowOpen,
+    #[msg("Observation does not match its commitment")]
+    ObservationMismatch,
+    #[msg("Token has oracle coverage; its signals are settled by the oracle")]
+    OracleCoverageAvailable,
+    
+    #[msg("Bounty is no longer taking entries")]
+    BountyClosed,
+    #[msg("Bounty scoring window has ended")]
+    BountyScoringClosed,
+    
+    #[msg("Signal's category price can't be converted to the provider's price mint")]
+    CategoryTokenPriceUnavailable,
+    
+    #[msg("Integrator is not approved, or belongs to the buyer")]
+    IntegratorNotApproved,
+    
+    #[msg("Mailbox owner has neither bought the signal nor subscribed to the provider")]
+    NoticeRecipientNotBuyer,
+    
+    #[msg("Account is not in its original layout")]
+    NotOriginalLayout,
+    
+    #[msg("Auto-renewal is off for this subscription")]
+    AutoRenewOff,
+    #[msg("Subscription is not within its renewal window")]
+    RenewalNotDue,
+    #[msg("Renewal would exceed the buyer's monthly cap")]
+    RenewalCapExceeded,
+    #[msg("Buyer credit balance too low")]
+    InsufficientBuyerCredit,
+    
+    #[msg("Key already holds this subscription or one of its seats")]
+    SeatAlreadyAdded,
+    #[msg("Subscription has no free seats")]
+    TooManySeats,
+    #[msg("Key does not hold a seat on this subscription")]
+    SeatNotFound,
+    #[msg("Buyer neither holds this subscription nor has a seat on it")]
+    NotSubscriptionMember,
+    
+    #[msg("Every buyer of the signal must be refunded in the withdrawal")]
+    IncompleteRefunds,
+    
+    #[msg("Subscription escrow has nothing earned to release")]
+    NothingEarned,
+    #[msg("Subscriptions are only refunded while the provider is suspended or proven fraudulent")]
+    SubscriptionNotRefundable,
+    
+    #[msg("The pending withdrawal is paid by the other completion instruction")]
+    WrongWithdrawalKind,
+    
+    #[msg("Bond carries too little weight to propose")]
+    InsufficientProposalWeight,
+    #[msg("Unknown governed parameter or value out of range")]
+    InvalidGovernedParam,
+    #[msg("Voting on this proposal has closed")]
+    VotingClosed,
+    #[msg("Voting on this proposal is still open")]
+    VotingOpen,
+    #[msg("Bond was made after the proposal, is unbonding or backs a fraudulent provider")]
+    BondNotEligibleToVote,
+    #[msg("Proposal did not pass")]
+    ProposalNotPassed,
+    #[msg("Proposal timelock has not run")]
+    ProposalTimelocked,
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+    
+    #[msg("Not a baseline signal awaiting its direction draw")]
+    BaselineAlreadyDrawn,
+    #[msg("The baseline signal's slot hash is not available yet or has aged out")]
+    BaselineDrawUnavailable,
+    #[msg("Baseline signals are only revealed by their direction draw")]
+    BaselineAwaitingDraw,
+    
+    #[msg("The audit oracle makes the first audit draw of each epoch")]
+    AuditEpochNotSeeded,
 }
@@ -0,0 +1,87 @@
+//! Address lookup table layout for a provider's hot account set
+//!
+//! Batch settlement touches the same program-wide and per-provider accounts in every
+//! transaction. Keepers create one lookup table per provider holding these accounts at
+//! fixed indices, then extend it with the provider's open SignalCommits and the price
+//! feeds they settle against (see docs/LOOKUP_TABLES.md).
+
+use anchor_lang::prelude::*;
+
+pub const ALT_PROGRAM: usize = 0;
+pub const ALT_SYSTEM_PROGRAM: usize = 1;
+pub const ALT_CONFIG: usize = 2;
+pub const ALT_ORACLE_QUEUE: usize = 3;
+pub const ALT_STAKE_POOL: usize = 4;
+pub const ALT_PROVIDER: usize = 5;
+pub const ALT_PROVIDER_CURVE: usize = 6;
+pub const ALT_SOL_REGISTRY: usize = 7;
+
+/// Number of fixed entries; commits and feeds are appended from this index
+pub const ALT_FIXED_ENTRIES: usize = 8;
+
+/// The fixed entries of a provider's lookup table, in index order
+pub fn provider_hot_accounts(provider: &Pubkey) -> [Pubkey; ALT_FIXED_ENTRIES] {
+    let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &crate::ID).0;
+    [
+        crate::ID,
+        anchor_lang::system_program::ID,
+        pda(&[b"config"]),
+        pda(&[b"oracle_queue"]),
+        pda(&[b"stake_pool"]),
+        *provider,
+        pda(&[b"curve", provider.as_ref()]),
+        pda(&[b"token", b"SOL"]),
+    ]
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canonical_json::{parse, JsonValue};
+    use std::str::FromStr;
+
+    /// Vectors shared with the TypeScript providerHotAccounts
+    const SHARED_VECTORS: &str = include_str!("../test-vectors/lookup_table.json");
+
+    fn member<'a>(vector: &'a JsonValue, name: &str) -> &'a JsonValue {
+        match vector {
+            JsonValue::Object(members) => &members.iter().find(|(k, _)| k == name).unwrap().1,
+            _ => panic!("vector is not an object"),
+        }
+    }
+
+    fn key(value: &JsonValue) -> Pubkey {
+        match value {
+            JsonValue::String(s) => Pubkey::from_str(s).unwrap(),
+            _ => panic!("key is not a string"),
+        }
+    }
+
+    #[test]
+    fn fixed_entries_match_the_shared_vectors() {
+        let JsonValue::Array(vectors) = parse(SHARED_VECTORS).unwrap() else {
+            panic!("vectors are not an array");
+        };
+        assert!(!vectors.is_empty());
+        for vector in &vectors {
+            let provider = key(member(vector, "provider"));
+            let JsonValue::Array(accounts) = member(vector, "accounts") else {
+                panic!("accounts are not an array");
+            };
+            let expected: Vec<Pubkey> = accounts.iter().map(key).collect();
+            assert_eq!(provider_hot_accounts(&provider).to_vec(), expected, "provider {}", provider);
+        }
+    }
+
+    #[test]
+    fn indices_name_their_accounts() {
+        let provider = Pubkey::new_unique();
+        let accounts = provider_hot_accounts(&provider);
+        assert_eq!(accounts[ALT_PROGRAM], crate::ID);
+        assert_eq!(accounts[ALT_PROVIDER], provider);
+        assert_eq!(accounts[ALT_CONFIG], Pubkey::find_program_address(&[b"config"], &crate::ID).0);
+        assert_eq!(accounts[ALT_PROVIDER_CURVE], Pubkey::find_program_address(&[b"curve", provider.as_ref()], &crate::ID).0);
+        assert_eq!(ALT_SOL_REGISTRY + 1, ALT_FIXED_ENTRIES);
+    }
+}
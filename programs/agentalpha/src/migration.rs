@@ -0,0 +1,305 @@
+//! Migration of accounts written in the original layout
+//!
+//! Before layout_version existed, Provider and SignalCommit gained fields in the middle of the
+//! struct (the attestation and TP-progress fields among them), so accounts created by the
+//! original deployment can't be read with the current layout. These are their original layouts:
+//! migrate_provider and migrate_signal_commit read an account with them, grow it to the current
+//! SIZE and rewrite it at the current layout version. Fields the original layout didn't have
+//! start where a new account's would.
+//!
+//! Accounts created since only gain fields after layout_version (see the layout policy in
+//! lib.rs) and never need migrating.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{
+    AgentAlphaError, Provider, SignalCommit, HASH_SCHEMA_V1, PROVIDER_LAYOUT_VERSION,
+    SIGNAL_COMMIT_LAYOUT_VERSION, SLA_EPOCH_SECS,
+};
+
+/// Brier score credited per migrated signal: the original layout kept no confidence history,
+/// so migrated signals count as an uninformative 50% forecast rather than a perfect one
+pub const ORIGINAL_BRIER_BPS: u64 = 2500;
+
+/// Provider as first deployed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OriginalProvider {
+    pub authority: Pubkey,        // 32
+    pub name: String,             // 4 + 64
+    pub endpoint: String,         // 4 + 256
+    pub categories: Vec<u8>,      // 4 + 8
+    pub price_lamports: u64,      // 8
+    pub total_signals: u64,       // 8
+    pub correct_signals: u64,     // 8
+    pub total_return_bps: i64,    // 8
+    pub created_at: i64,          // 8
+    pub updated_at: i64,          // 8
+    pub bump: u8,                 // 1
+}
+
+impl OriginalProvider {
+    pub const SIZE: usize = 8 + 32 + (4 + 64) + (4 + 256) + (4 + 8) + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 64;
+}
+
+/// SignalCommit as first deployed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OriginalSignalCommit {
+    pub provider: Pubkey,           // 32
+    pub signal_hash: [u8; 32],      // 32
+    pub committed_at: i64,          // 8
+    pub revealed: bool,             // 1
+    pub outcome_recorded: bool,     // 1
+    pub token: String,              // 4 + 16
+    pub direction: u8,              // 1
+    pub entry_cents: u64,           // 8
+    pub tp_cents: u64,              // 8
+    pub sl_cents: u64,              // 8
+    pub timeframe_hours: u8,        // 1
+    pub confidence: u8,             // 1
+    pub revealed_at: i64,           // 8
+    pub outcome: u8,                // 1
+    pub final_price_cents: u64,     // 8
+    pub was_correct: bool,          // 1
+    pub return_bps: i32,            // 4
+    pub evaluated_at: i64,          // 8
+    pub bump: u8,                   // 1
+}
+
+impl OriginalSignalCommit {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1 + 1 + (4 + 16) + 1 + 8 + 8 + 8 + 1 + 1 + 8 + 1 + 8 + 1 + 4 + 8 + 1 + 64;
+}
+
+/// Read account data in an original layout: `discriminator` must match and the data must be the
+/// original `size`, which no current-layout account has
+pub fn read_original<T: AnchorDeserialize>(data: &[u8], discriminator: &[u8], size: usize) -> Result<T> {
+    require!(
+        data.len() == size && data.starts_with(discriminator),
+        AgentAlphaError::NotOriginalLayout
+    );
+    T::deserialize(&mut &data[discriminator.len()..]).map_err(|_| error!(AgentAlphaError::NotOriginalLayout))
+}
+
+/// Grow a program account to `new_len`, with the payer funding the extra rent
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let shortfall = Rent::get()?.minimum_balance(new_len).saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Decode a zeroed account body: every field at its zero value, as a fresh reserve reads
+pub(crate) fn zeroed<T: AnchorDeserialize>(size: usize) -> Result<Box<T>> {
+    let zeros = vec![0u8; size];
+    Ok(Box::new(T::deserialize(&mut &zeros[..])?))
+}
+
+impl Provider {
+    /// The current layout of an original Provider, with the defaults init_provider gives new fields
+    pub fn from_original(original: OriginalProvider, now: i64) -> Result<Box<Provider>> {
+        let mut provider = zeroed::<Provider>(Provider::SIZE)?;
+        provider.authority = original.authority;
+        provider.seed_authority = original.authority;
+        provider.name = original.name;
+        provider.endpoint = original.endpoint;
+        provider.categories = original.categories;
+        provider.price_lamports = original.price_lamports;
+        provider.total_signals = original.total_signals;
+        provider.correct_signals = original.correct_signals;
+        provider.total_return_bps = original.total_return_bps;
+        provider.total_brier_bps = original.total_signals * ORIGINAL_BRIER_BPS;
+        provider.created_at = original.created_at;
+        provider.updated_at = original.updated_at;
+        provider.bump = original.bump;
+        provider.accepting_purchases = true;
+        provider.sla_epoch = (now / SLA_EPOCH_SECS) as u32;
+        provider.layout_version = PROVIDER_LAYOUT_VERSION;
+        Ok(provider)
+    }
+}
+
+impl SignalCommit {
+    /// The current layout of an original SignalCommit, with the defaults open_commit gives new fields
+    /// Original commits were hashed with the V1 preimage and hold no settlement deposit
+    pub fn from_original(original: OriginalSignalCommit) -> Result<Box<SignalCommit>> {
+        let mut commit = zeroed::<SignalCommit>(SignalCommit::SIZE)?;
+        commit.provider = original.provider;
+        commit.signal_hash = original.signal_hash;
+        commit.committed_at = original.committed_at;
+        commit.revealed = original.revealed;
+        commit.outcome_recorded = original.outcome_recorded;
+        commit.token = original.token;
+        commit.direction = original.direction;
+        commit.entry_cents = original.entry_cents;
+        commit.tp_cents = original.tp_cents;
+        commit.sl_cents = original.sl_cents;
+        commit.timeframe_hours = original.timeframe_hours;
+        commit.confidence = original.confidence;
+        commit.revealed_at = original.revealed_at;
+        commit.outcome = original.outcome;
+        commit.final_price_cents = original.final_price_cents;
+        commit.was_correct = original.was_correct;
+        commit.return_bps = original.return_bps;
+        commit.evaluated_at = original.evaluated_at;
+        commit.bump = original.bump;
+        commit.hash_schema = HASH_SCHEMA_V1;
+        commit.layout_version = SIGNAL_COMMIT_LAYOUT_VERSION;
+        Ok(commit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator};
+
+    fn original_account<T: AnchorSerialize>(discriminator: &[u8], body: &T, size: usize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        body.serialize(&mut data).unwrap();
+        assert!(data.len() <= size, "original body overflows its SIZE");
+        data.resize(size, 0);
+        data
+    }
+
+    fn rewrite<T: AccountSerialize>(account: &T, size: usize) -> Vec<u8> {
+        let mut data = Vec::with_capacity(size);
+        account.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= size, "current layout overflows its SIZE");
+        data.resize(size, 0);
+        data
+    }
+
+    #[test]
+    fn original_provider_round_trips_into_the_current_layout() {
+        let original = OriginalProvider {
+            authority: Pubkey::new_from_array([7; 32]),
+            name: "alpha-desk".to_string(),
+            endpoint: "https://alpha.example/signals".to_string(),
+            categories: vec![1, 3],
+            price_lamports: 50_000_000,
+            total_signals: 40,
+            correct_signals: 26,
+            total_return_bps: 12_345,
+            created_at: 1_700_000_000,
+            updated_at: 1_700_500_000,
+            bump: 254,
+        };
+        let data = original_account(Provider::DISCRIMINATOR, &original, OriginalProvider::SIZE);
+
+        // The current layout can't read it in place
+        assert!(Provider::try_deserialize(&mut &data[..]).is_err());
+
+        let read: OriginalProvider = read_original(&data, Provider::DISCRIMINATOR, OriginalProvider::SIZE).unwrap();
+        assert_eq!(read, original);
+        let migrated = Provider::from_original(read, 1_760_000_000).unwrap();
+        let data = rewrite(migrated.as_ref(), Provider::SIZE);
+        let provider = Provider::try_deserialize(&mut &data[..]).unwrap();
+
+        assert_eq!(provider.authority, original.authority);
+        assert_eq!(provider.seed_authority, original.authority);
+        assert_eq!(provider.name, original.name);
+        assert_eq!(provider.endpoint, original.endpoint);
+        assert_eq!(provider.categories, original.categories);
+        assert_eq!(provider.price_lamports, original.price_lamports);
+        assert_eq!(provider.hit_rate_bps(), 6500);
+        assert_eq!(provider.total_return_bps, original.total_return_bps);
+        assert_eq!(provider.total_brier_bps, 40 * ORIGINAL_BRIER_BPS);
+        assert_eq!(provider.created_at, original.created_at);
+        assert_eq!(provider.bump, original.bump);
+        assert!(provider.accepting_purchases);
+        assert!(provider.has_layout(PROVIDER_LAYOUT_VERSION));
+        assert_eq!(provider.attestation_hash, [0u8; 32]);
+        assert!(!provider.tee_verified);
+        assert!(provider.category_prices().is_empty());
+    }
+
+    #[test]
+    fn original_signal_commit_round_trips_into_the_current_layout() {
+        let original = OriginalSignalCommit {
+            provider: Pubkey::new_from_array([9; 32]),
+            signal_hash: [0xab; 32],
+            committed_at: 1_700_000_000,
+            revealed: true,
+            outcome_recorded: true,
+            token: "SOL".to_string(),
+            direction: 0,
+            entry_cents: 10_500,
+            tp_cents: 11_500,
+            sl_cents: 10_000,
+            timeframe_hours: 24,
+            confidence: 80,
+            revealed_at: 1_700_003_600,
+            outcome: 1,
+            final_price_cents: 11_500,
+            was_correct: true,
+            return_bps: 952,
+            evaluated_at: 1_700_050_000,
+            bump: 253,
+        };
+        let data = original_account(SignalCommit::DISCRIMINATOR, &original, OriginalSignalCommit::SIZE);
+
+        assert!(SignalCommit::try_deserialize(&mut &data[..]).is_err());
+
+        let read: OriginalSignalCommit =
+            read_original(&data, SignalCommit::DISCRIMINATOR, OriginalSignalCommit::SIZE).unwrap();
+        let migrated = SignalCommit::from_original(read).unwrap();
+        let data = rewrite(migrated.as_ref(), SignalCommit::SIZE);
+        let commit = SignalCommit::try_deserialize(&mut &data[..]).unwrap();
+
+        assert_eq!(commit.provider, original.provider);
+        assert_eq!(commit.signal_hash, original.signal_hash);
+        assert_eq!(commit.token, original.token);
+        assert_eq!(
+            (commit.entry_cents, commit.tp_cents, commit.sl_cents),
+            (original.entry_cents, original.tp_cents, original.sl_cents)
+        );
+        assert_eq!(commit.effective_sl_cents(), original.sl_cents);
+        assert_eq!(commit.outcome, original.outcome);
+        assert!(commit.was_correct);
+        assert_eq!(commit.return_bps, original.return_bps);
+        assert_eq!(commit.tp_progress_bps, 0);
+        assert_eq!(commit.hash_schema, HASH_SCHEMA_V1);
+        assert_eq!(commit.settlement_deposit, 0);
+        assert_eq!(commit.performance_fee_bps(), 0);
+        assert!(commit.has_layout(SIGNAL_COMMIT_LAYOUT_VERSION));
+    }
+
+    #[test]
+    fn read_original_rejects_current_layout_accounts() {
+        let original = OriginalProvider {
+            authority: Pubkey::new_from_array([7; 32]),
+            name: String::new(),
+            endpoint: String::new(),
+            categories: Vec::new(),
+            price_lamports: 0,
+            total_signals: 0,
+            correct_signals: 0,
+            total_return_bps: 0,
+            created_at: 0,
+            updated_at: 0,
+            bump: 255,
+        };
+        let migrated = Provider::from_original(original.clone(), 0).unwrap();
+        let current = rewrite(migrated.as_ref(), Provider::SIZE);
+        assert!(read_original::<OriginalProvider>(&current, Provider::DISCRIMINATOR, OriginalProvider::SIZE).is_err());
+
+        let wrong_type = original_account(SignalCommit::DISCRIMINATOR, &original, OriginalProvider::SIZE);
+        assert!(read_original::<OriginalProvider>(&wrong_type, Provider::DISCRIMINATOR, OriginalProvider::SIZE).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! Display formatting for on-chain prices
+//!
+//! Prices are stored as integer cents of the quote currency: a mantissa with a fixed exponent
+//! of -2. Each TokenRegistry entry carries the fractional digits to show and the power of ten
+//! to show them at, and clients render through `format_price_cents` so a price reads the same
+//! in every UI and agent. 6512345 cents of BTC shown with 1 decimal at exponent 3 is
+//! "65.1e3"; 10500 cents of SOL with the default display is "105.00".
+//!
+//! Rounding is half up at the last shown digit. The exponent suffix is omitted at exponent 0.
+
+/// Fractional digits a token may be displayed with
+pub const MAX_DISPLAY_DECIMALS: u8 = 8;
+
+/// Range of display exponents, bounded so scaling a u64 price stays within u128
+pub const MIN_DISPLAY_EXPONENT: i8 = -8;
+pub const MAX_DISPLAY_EXPONENT: i8 = 18;
+
+/// Exponent of stored prices (cents)
+pub const PRICE_CENTS_EXPONENT: i32 = -2;
+
+/// How a token's prices are rendered
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceDisplay {
+    /// Fractional digits shown
+    pub decimals: u8,
+    /// Power of ten the shown number is scaled by
+    pub exponent: i8,
+}
+
+impl PriceDisplay {
+    /// Whole units with cents, for tokens without display metadata
+    pub const DEFAULT: PriceDisplay = PriceDisplay { decimals: 2, exponent: 0 };
+
+    pub fn is_valid(&self) -> bool {
+        self.decimals <= MAX_DISPLAY_DECIMALS
+            && (MIN_DISPLAY_EXPONENT..=MAX_DISPLAY_EXPONENT).contains(&self.exponent)
+    }
+}
+
+/// The price as a mantissa of `display.decimals` fractional digits at `display.exponent`
+/// (the shown number is mantissa * 10^-decimals * 10^exponent), rounded half up
+/// None if the display is out of range
+pub fn display_mantissa(price_cents: u64, display: PriceDisplay) -> Option<u128> {
+    if !display.is_valid() {
+        return None;
+    }
+    let shift = display.decimals as i32 + PRICE_CENTS_EXPONENT - display.exponent as i32;
+    if shift >= 0 {
+        (price_cents as u128).checked_mul(10u128.pow(shift as u32))
+    } else {
+        let divisor = 10u128.pow(shift.unsigned_abs());
+        Some((price_cents as u128 + divisor / 2) / divisor)
+    }
+}
+
+/// Render a price in cents the way `display` asks, e.g. "105.00" or "65.1e3"
+/// None if the display is out of range
+pub fn format_price_cents(price_cents: u64, display: PriceDisplay) -> Option<String> {
+    let mantissa = display_mantissa(price_cents, display)?;
+    let scale = 10u128.pow(display.decimals as u32);
+    let mut out = (mantissa / scale).to_string();
+    if display.decimals > 0 {
+        out.push_str(&format!(".{:0width$}", mantissa % scale, width = display.decimals as usize));
+    }
+    if display.exponent != 0 {
+        out.push_str(&format!("e{}", display.exponent));
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canonical_json::{parse, JsonValue};
+
+    /// Vectors shared with the TypeScript AgentAlphaClient.formatPriceCents
+    const SHARED_VECTORS: &str = include_str!("../test-vectors/price_display.json");
+
+    fn member<'a>(vector: &'a JsonValue, name: &str) -> &'a JsonValue {
+        match vector {
+            JsonValue::Object(members) => &members.iter().find(|(k, _)| k == name).unwrap().1,
+            _ => panic!("vector is not an object"),
+        }
+    }
+
+    fn text<'a>(vector: &'a JsonValue, name: &str) -> &'a str {
+        match member(vector, name) {
+            JsonValue::String(s) => s,
+            _ => panic!("{} is not a string", name),
+        }
+    }
+
+    fn number(vector: &JsonValue, name: &str) -> f64 {
+        match member(vector, name) {
+            JsonValue::Number(n) => *n,
+            _ => panic!("{} is not a number", name),
+        }
+    }
+
+    #[test]
+    fn formats_the_shared_vectors() {
+        let JsonValue::Array(vectors) = parse(SHARED_VECTORS).unwrap() else {
+            panic!("vectors are not an array");
+        };
+        assert!(!vectors.is_empty());
+        for vector in &vectors {
+            let price_cents: u64 = text(vector, "price_cents").parse().unwrap();
+            let display = PriceDisplay {
+                decimals: number(vector, "decimals") as u8,
+                exponent: number(vector, "exponent") as i8,
+            };
+            assert_eq!(
+                format_price_cents(price_cents, display).as_deref(),
+                Some(text(vector, "display")),
+                "{} cents at {:?}",
+                price_cents,
+                display
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_displays_out_of_range() {
+        let out_of_range = [
+            PriceDisplay { decimals: MAX_DISPLAY_DECIMALS + 1, exponent: 0 },
+            PriceDisplay { decimals: 2, exponent: MIN_DISPLAY_EXPONENT - 1 },
+            PriceDisplay { decimals: 2, exponent: MAX_DISPLAY_EXPONENT + 1 },
+        ];
+        for display in out_of_range {
+            assert_eq!(format_price_cents(10500, display), None);
+        }
+        assert_eq!(format_price_cents(10500, PriceDisplay::DEFAULT).as_deref(), Some("105.00"));
+    }
+}
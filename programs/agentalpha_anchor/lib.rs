@@ -1,3 +1,5 @@
+// LEGACY: earlier program variant, not part of the workspace and not deployed.
+// The live program is programs/agentalpha (program id in programs/agentalpha/src/cluster.rs).
 use anchor_lang::prelude::*;
 use sha2::{Sha256, Digest};
 
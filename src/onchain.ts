@@ -6,11 +6,13 @@
  */
 
 import {
+  AddressLookupTableProgram,
   Connection,
   PublicKey,
   Keypair,
   SystemProgram,
   Transaction,
+  TransactionInstruction,
   sendAndConfirmTransaction,
 } from '@solana/web3.js';
 import { createHash } from 'crypto';
@@ -22,6 +24,14 @@ export const PROGRAM_ID = new PublicKey('6sDwzatESkmF5T3K3rfNta4DCRgH8z9ZdYoPXeM
 const PROVIDER_SEED = Buffer.from('provider');
 const SIGNAL_SEED = Buffer.from('signal');
 
+// Fixed entries of a provider's address lookup table (programs/agentalpha/src/lookup_table.rs)
+export const ALT_FIXED_ENTRIES = 8;
+// Addresses per extendLookupTable instruction, so each extend fits in one transaction
+const ALT_EXTEND_CHUNK = 30;
+
+// Domain tag of hash schema V2 commits (programs/agentalpha/src/domain.rs)
+const COMMIT_DOMAIN_V2 = Buffer.from('AGENTALPHA:COMMIT:V2');
+
 // Instruction discriminators (from Anchor IDL)
 const DISCRIMINATORS = {
   registerProvider: Buffer.from([254, 209, 54, 184, 46, 197, 109, 78]),
@@ -110,6 +120,7 @@ export class AgentAlphaClient {
   /**
    * Compute signal hash for commit-reveal
    * Format: "{token}:{direction}:{entry_cents}:{tp_cents}:{sl_cents}:{timeframe}:{confidence}"
+   * Only reveals for signals committed before 2027-04-01 00:00 UTC; use computeSignalHashV2
    */
   static computeSignalHash(signal: SignalInput): { hash: Uint8Array; input: string } {
     const dirNum = signal.direction === 'BUY' ? 0 : 1;
@@ -123,6 +134,43 @@ export class AgentAlphaClient {
     return { hash: new Uint8Array(hash), input };
   }
 
+  /**
+   * Domain-separated signal hash (hash schema V2): SHA256("AGENTALPHA:COMMIT:V2" || input)
+   * Same input string as computeSignalHash
+   */
+  static computeSignalHashV2(signal: SignalInput): { hash: Uint8Array; input: string } {
+    const { input } = AgentAlphaClient.computeSignalHash(signal);
+    const hash = createHash('sha256').update(COMMIT_DOMAIN_V2).update(input).digest();
+    
+    return { hash: new Uint8Array(hash), input };
+  }
+
+  /**
+   * Render an on-chain price in cents with a token's display metadata
+   * (TokenRegistry.display_decimals / display_exponent), matching the program's
+   * price_display::format_price_cents: rounded half up, "e{exponent}" suffix unless 0
+   * (both are checked against programs/agentalpha/test-vectors/price_display.json)
+   */
+  static formatPriceCents(priceCents: bigint | number, decimals = 2, exponent = 0): string {
+    const shift = decimals - 2 - exponent;
+    let mantissa = BigInt(priceCents);
+    if (shift >= 0) {
+      mantissa *= 10n ** BigInt(shift);
+    } else {
+      const divisor = 10n ** BigInt(-shift);
+      mantissa = (mantissa + divisor / 2n) / divisor;
+    }
+    const scale = 10n ** BigInt(decimals);
+    let out = (mantissa / scale).toString();
+    if (decimals > 0) {
+      out += '.' + (mantissa % scale).toString().padStart(decimals, '0');
+    }
+    if (exponent !== 0) {
+      out += `e${exponent}`;
+    }
+    return out;
+  }
+
   /**
    * Register a new provider on-chain
    */
@@ -332,6 +380,69 @@ export class AgentAlphaClient {
   }
 }
 
+/**
+ * The fixed entries of a provider's lookup table, in index order, matching the program's
+ * lookup_table::provider_hot_accounts (both are checked against
+ * programs/agentalpha/test-vectors/lookup_table.json; see docs/LOOKUP_TABLES.md)
+ */
+export function providerHotAccounts(provider: PublicKey): PublicKey[] {
+  const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, PROGRAM_ID)[0];
+  return [
+    PROGRAM_ID,
+    SystemProgram.programId,
+    pda(Buffer.from('config')),
+    pda(Buffer.from('oracle_queue')),
+    pda(Buffer.from('stake_pool')),
+    provider,
+    pda(Buffer.from('curve'), provider.toBuffer()),
+    pda(Buffer.from('token'), Buffer.from('SOL')),
+  ];
+}
+
+/**
+ * Instructions appending `addresses` to a lookup table, ALT_EXTEND_CHUNK per instruction
+ * Send each in its own transaction; new entries are usable from the next slot
+ */
+export function extendLookupTableInstructions(
+  lookupTable: PublicKey,
+  authority: PublicKey,
+  payer: PublicKey,
+  addresses: PublicKey[]
+): TransactionInstruction[] {
+  const instructions: TransactionInstruction[] = [];
+  for (let i = 0; i < addresses.length; i += ALT_EXTEND_CHUNK) {
+    instructions.push(
+      AddressLookupTableProgram.extendLookupTable({
+        lookupTable,
+        authority,
+        payer,
+        addresses: addresses.slice(i, i + ALT_EXTEND_CHUNK),
+      })
+    );
+  }
+  return instructions;
+}
+
+/**
+ * Instructions creating a keeper's lookup table for a provider: the create instruction, then
+ * extends holding the fixed entries followed by `extra` (open SignalCommits, their price feeds
+ * and TokenRegistry PDAs). The create and the first extend can share a transaction.
+ */
+export function buildProviderLookupTable(
+  authority: PublicKey,
+  payer: PublicKey,
+  provider: PublicKey,
+  recentSlot: number,
+  extra: PublicKey[] = []
+): { lookupTable: PublicKey; instructions: TransactionInstruction[] } {
+  const [create, lookupTable] = AddressLookupTableProgram.createLookupTable({ authority, payer, recentSlot });
+  const addresses = [...providerHotAccounts(provider), ...extra];
+  return {
+    lookupTable,
+    instructions: [create, ...extendLookupTableInstructions(lookupTable, authority, payer, addresses)],
+  };
+}
+
 /**
  * Create a client connected to devnet
  */